@@ -0,0 +1,618 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write as _,
+    io::{self, Cursor, Write},
+};
+
+use zip::{CompressionMethod, ZipWriter, write::SimpleFileOptions};
+
+use super::{
+    ImageData, ImageLoader, LineFormat, escape_xml, font_name, half_points, split_block_embeds,
+};
+use crate::{
+    Align, AttributeMap, Delta, Embed, Embeds, Formats, Inline, Insert, Line, ListKind,
+    SanitizePolicy,
+};
+
+const W: &str = "http://schemas.openxmlformats.org/wordprocessingml/2006/main";
+const R: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+const PACKAGE_RELATIONSHIPS: &str = "http://schemas.openxmlformats.org/package/2006/relationships";
+
+/// twips of one indent level, half an inch
+const INDENT: u32 = 720;
+/// the width between the default one inch margins of a letter page, in pixels
+const TEXT_WIDTH: f64 = 624.0;
+/// english metric units per pixel
+const EMU: f64 = 9525.0;
+/// the largest drawing extent Word opens, in english metric units
+const MAX_EXTENT: f64 = 27_273_042_316_900.0;
+
+/// the numbering.xml ids of the bullet and checkbox lists, ordered lists follow
+const BULLET_LIST: usize = 1;
+const CHECKED_LIST: usize = 2;
+const UNCHECKED_LIST: usize = 3;
+
+/// how a delta is written as a Word document, see [`Delta::to_docx_with`].
+#[derive(Default)]
+pub struct DocxExport {
+    images: Option<ImageLoader>,
+    sanitize: SanitizePolicy,
+}
+
+impl DocxExport {
+    pub fn builder() -> DocxExportBuilder {
+        DocxExportBuilder::new()
+    }
+}
+
+pub struct DocxExportBuilder {
+    export: DocxExport,
+}
+
+impl DocxExportBuilder {
+    pub fn new() -> Self {
+        Self {
+            export: DocxExport::default(),
+        }
+    }
+    /// the bytes of the image at a url, fetched by the caller. `data:` urls are decoded
+    /// without it, the images without bytes are written as their alt text
+    pub fn images(mut self, loader: impl Fn(&str) -> Option<Vec<u8>> + 'static) -> Self {
+        self.export.images = Some(Box::new(loader));
+        self
+    }
+    pub fn sanitize(mut self, policy: SanitizePolicy) -> Self {
+        self.export.sanitize = policy;
+        self
+    }
+    pub fn build(self) -> DocxExport {
+        self.export
+    }
+}
+
+impl Default for DocxExportBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Delta {
+    /// writes this document as a Word (`.docx`) file: headers use the `Heading 1`..`Heading 6`
+    /// styles, quotes and code blocks the `Quote` and `Code` styles, lists are numbered or
+    /// bulleted Word lists with their levels, formats become run properties, links become
+    /// hyperlinks and images are embedded.
+    ///
+    /// only the `data:` images are embedded, see [`Delta::to_docx_with`] to provide the bytes
+    /// of the others.
+    ///
+    /// ```
+    /// # use quill_wr::*;
+    /// let doc = delta!["Title", "\n" { header: 1 }, "a ", "b" { bold: true }, "\n"];
+    /// let docx = doc.to_docx().unwrap();
+    /// assert!(docx.starts_with(b"PK"));
+    /// ```
+    pub fn to_docx(&self) -> io::Result<Vec<u8>> {
+        self.to_docx_with(&DocxExport::default())
+    }
+    pub fn to_docx_with(&self, export: &DocxExport) -> io::Result<Vec<u8>> {
+        let document = self.sanitized(&export.sanitize);
+        let mut writer = DocxWriter {
+            export,
+            body: String::new(),
+            relationships: vec![],
+            links: HashMap::new(),
+            images: HashMap::new(),
+            media: vec![],
+            content_types: BTreeMap::new(),
+            drawings: 0,
+            ordered_lists: 0,
+            ordered_list: None,
+        };
+        for line in document.lines() {
+            writer.line(&line);
+        }
+        if writer.body.is_empty() {
+            writer.body.push_str("<w:p/>");
+        }
+        writer.package()
+    }
+}
+
+struct DocxWriter<'a> {
+    export: &'a DocxExport,
+    body: String,
+    /// the relationships of `document.xml` after styles and numbering, as xml
+    relationships: Vec<String>,
+    /// relationship ids by link url
+    links: HashMap<String, String>,
+    /// relationship ids and sizes by image url, `None` for the images without bytes
+    images: HashMap<String, Option<(String, ImageData)>>,
+    /// paths and bytes of the images in the package
+    media: Vec<(String, Vec<u8>)>,
+    /// image extensions and their content types
+    content_types: BTreeMap<&'static str, &'static str>,
+    drawings: usize,
+    ordered_lists: usize,
+    /// the numbering id of the ordered list the last line belongs to
+    ordered_list: Option<usize>,
+}
+
+impl DocxWriter<'_> {
+    fn line(&mut self, line: &Line) {
+        let (videos, content) = split_block_embeds(&line.content);
+        for op in videos {
+            if let Some(Insert::Embed(embed)) = op.as_insert() {
+                self.ordered_list = None;
+                let url = embed.value.as_str().unwrap_or_default();
+                let run = text_run(url, "");
+                let link = self.hyperlink(url, &run);
+                let _ = write!(self.body, "<w:p>{link}</w:p>");
+            }
+        }
+        // the newline after a video holds no paragraph of its own
+        if !videos.is_empty() && content.ops().is_empty() {
+            return;
+        }
+        let format = LineFormat::new(&line.blocks());
+        let mut properties = String::new();
+        let style = match (&format.code, format.list, format.header) {
+            (Some(_), ..) => Some("Code".to_string()),
+            (None, Some(_), _) => Some("ListParagraph".into()),
+            (None, None, Some(level)) => Some(format!("Heading{level}")),
+            (None, None, None) => format.blockquote.then(|| "Quote".into()),
+        };
+        if let Some(style) = style {
+            let _ = write!(properties, "<w:pStyle w:val=\"{style}\"/>");
+        }
+        match format.list.filter(|_| format.code.is_none()) {
+            Some(kind) => {
+                let list = match kind {
+                    ListKind::Ordered => self.ordered_list(),
+                    ListKind::Bullet => BULLET_LIST,
+                    ListKind::Checked => CHECKED_LIST,
+                    ListKind::Unchecked => UNCHECKED_LIST,
+                };
+                let _ = write!(
+                    properties,
+                    "<w:numPr><w:ilvl w:val=\"{}\"/><w:numId w:val=\"{list}\"/></w:numPr>",
+                    format.indent.min(8)
+                );
+            }
+            None => self.ordered_list = None,
+        }
+        if format.rtl {
+            properties.push_str("<w:bidi/>");
+        }
+        if format.list.is_none() && format.code.is_none() && format.indent > 0 {
+            let left = INDENT * u32::from(format.indent);
+            let _ = write!(properties, "<w:ind w:left=\"{left}\"/>");
+        }
+        if let Some(align) = format.align {
+            let justification = match align {
+                Align::Center => "center",
+                Align::Right => "right",
+                Align::Justify => "both",
+            };
+            let _ = write!(properties, "<w:jc w:val=\"{justification}\"/>");
+        }
+        let runs = self.runs(&content);
+        self.body.push_str("<w:p>");
+        if !properties.is_empty() {
+            let _ = write!(self.body, "<w:pPr>{properties}</w:pPr>");
+        }
+        self.body.push_str(&runs);
+        self.body.push_str("</w:p>");
+    }
+
+    /// a new numbering instance for every list, so that each one starts at 1
+    fn ordered_list(&mut self) -> usize {
+        *self.ordered_list.get_or_insert_with(|| {
+            self.ordered_lists += 1;
+            UNCHECKED_LIST + self.ordered_lists
+        })
+    }
+
+    /// the runs of a line, consecutive runs with the same link share one hyperlink
+    fn runs(&mut self, content: &Delta) -> String {
+        let mut xml = String::new();
+        let mut link: Option<(String, String)> = None;
+        for op in content.ops() {
+            let Some(insert) = op.as_insert() else {
+                continue;
+            };
+            let attributes = op.attributes().cloned().unwrap_or_default();
+            let formats = Formats::from_kvs(attributes.clone()).inlines;
+            // videos inside a line link to their url
+            let video = insert
+                .as_embed()
+                .filter(|embed| embed.kind == "video")
+                .and_then(|embed| embed.value.as_str());
+            let url = formats
+                .iter()
+                .find_map(|format| match format {
+                    Inline::Link(url) => Some(url.clone()),
+                    _ => None,
+                })
+                .or_else(|| video.map(str::to_string));
+            let properties = run_properties(&formats, url.is_some());
+            let run = match insert {
+                Insert::Text(text) => text_run(text, &properties),
+                Insert::Embed(embed) => self.embed(embed, &attributes, &properties),
+            };
+            if link.as_ref().map(|(open, _)| open) != url.as_ref()
+                && let Some((open, runs)) = link.take()
+            {
+                xml.push_str(&self.hyperlink(&open, &runs));
+            }
+            match url {
+                Some(url) => link.get_or_insert_with(|| (url, String::new())).1 += &run,
+                None => xml.push_str(&run),
+            }
+        }
+        if let Some((open, runs)) = link {
+            xml.push_str(&self.hyperlink(&open, &runs));
+        }
+        xml
+    }
+
+    /// adds a relationship of `document.xml`, `rId1` and `rId2` are styles and numbering
+    fn relationship(&mut self, kind: &str, target: &str, external: bool) -> String {
+        let id = format!("rId{}", self.relationships.len() + 3);
+        let mode = if external {
+            " TargetMode=\"External\""
+        } else {
+            ""
+        };
+        self.relationships.push(format!(
+            "<Relationship Id=\"{id}\" Type=\"{R}/{kind}\" Target=\"{}\"{mode}/>",
+            escape_xml(target)
+        ));
+        id
+    }
+
+    fn hyperlink(&mut self, url: &str, runs: &str) -> String {
+        let id = match self.links.get(url) {
+            Some(id) => id.clone(),
+            None => {
+                let id = self.relationship("hyperlink", &url.replace(' ', "%20"), true);
+                self.links.insert(url.to_string(), id.clone());
+                id
+            }
+        };
+        format!("<w:hyperlink r:id=\"{id}\" w:history=\"1\">{runs}</w:hyperlink>")
+    }
+
+    fn embed(&mut self, embed: &Embed, attributes: &AttributeMap, properties: &str) -> String {
+        let attribute = |name: &str| attributes.get(name).and_then(|value| value.as_str());
+        match Embeds::from(embed.clone()) {
+            Embeds::Image(src) => self
+                .image(
+                    &src,
+                    attribute("width"),
+                    attribute("height"),
+                    attribute("alt"),
+                )
+                .unwrap_or_else(|| text_run(attribute("alt").unwrap_or_default(), properties)),
+            Embeds::Video(url) | Embeds::Formula(url) => text_run(&url, properties),
+            Embeds::Custom(..) => String::new(),
+        }
+    }
+
+    /// an inline picture, `None` when the image has no bytes
+    fn image(
+        &mut self,
+        src: &str,
+        width: Option<&str>,
+        height: Option<&str>,
+        alt: Option<&str>,
+    ) -> Option<String> {
+        if !self.images.contains_key(src) {
+            let image = ImageData::load(src, self.export.images.as_ref()).map(|mut image| {
+                let path = format!("media/image{}.{}", self.media.len() + 1, image.extension);
+                let id = self.relationship("image", &path, false);
+                self.content_types.insert(image.extension, image.mime);
+                self.media.push((path, std::mem::take(&mut image.bytes)));
+                (id, image)
+            });
+            self.images.insert(src.to_string(), image);
+        }
+        let (id, image) = self.images.get(src)?.as_ref()?;
+        let (width, height) = image.display_size(width, height, TEXT_WIDTH);
+        let extent = |pixels: f64| (pixels * EMU).round().clamp(0.0, MAX_EXTENT);
+        let (cx, cy) = (extent(width), extent(height));
+        self.drawings += 1;
+        let number = self.drawings;
+        let description = escape_xml(alt.unwrap_or_default());
+        Some(format!(
+            "<w:r><w:drawing><wp:inline distT=\"0\" distB=\"0\" distL=\"0\" distR=\"0\">\
+             <wp:extent cx=\"{cx}\" cy=\"{cy}\"/>\
+             <wp:docPr id=\"{number}\" name=\"Picture {number}\" descr=\"{description}\"/>\
+             <wp:cNvGraphicFramePr><a:graphicFrameLocks noChangeAspect=\"1\"/>\
+             </wp:cNvGraphicFramePr>\
+             <a:graphic><a:graphicData \
+             uri=\"http://schemas.openxmlformats.org/drawingml/2006/picture\"><pic:pic>\
+             <pic:nvPicPr><pic:cNvPr id=\"{number}\" name=\"Picture {number}\"/><pic:cNvPicPr/>\
+             </pic:nvPicPr>\
+             <pic:blipFill><a:blip r:embed=\"{id}\"/><a:stretch><a:fillRect/></a:stretch>\
+             </pic:blipFill>\
+             <pic:spPr><a:xfrm><a:off x=\"0\" y=\"0\"/><a:ext cx=\"{cx}\" cy=\"{cy}\"/></a:xfrm>\
+             <a:prstGeom prst=\"rect\"><a:avLst/></a:prstGeom></pic:spPr>\
+             </pic:pic></a:graphicData></a:graphic></wp:inline></w:drawing></w:r>"
+        ))
+    }
+
+    fn package(self) -> io::Result<Vec<u8>> {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        let mut part = |path: &str, bytes: &[u8]| -> io::Result<()> {
+            zip.start_file(path, options)?;
+            zip.write_all(bytes)
+        };
+
+        let images = self
+            .content_types
+            .iter()
+            .map(|(extension, mime)| {
+                format!("<Default Extension=\"{extension}\" ContentType=\"{mime}\"/>")
+            })
+            .collect::<String>();
+        part(
+            "[Content_Types].xml",
+            format!(
+                "{XML_DECLARATION}<Types \
+                 xmlns=\"http://schemas.openxmlformats.org/package/2006/content-types\">\
+                 <Default Extension=\"rels\" \
+                 ContentType=\"application/vnd.openxmlformats-package.relationships+xml\"/>\
+                 <Default Extension=\"xml\" ContentType=\"application/xml\"/>{images}\
+                 <Override PartName=\"/word/document.xml\" ContentType=\"application/\
+                 vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml\"/>\
+                 <Override PartName=\"/word/styles.xml\" ContentType=\"application/\
+                 vnd.openxmlformats-officedocument.wordprocessingml.styles+xml\"/>\
+                 <Override PartName=\"/word/numbering.xml\" ContentType=\"application/\
+                 vnd.openxmlformats-officedocument.wordprocessingml.numbering+xml\"/>\
+                 </Types>"
+            )
+            .as_bytes(),
+        )?;
+        part(
+            "_rels/.rels",
+            format!(
+                "{XML_DECLARATION}<Relationships xmlns=\"{PACKAGE_RELATIONSHIPS}\">\
+                 <Relationship Id=\"rId1\" Type=\"{R}/officeDocument\" \
+                 Target=\"word/document.xml\"/></Relationships>"
+            )
+            .as_bytes(),
+        )?;
+        part(
+            "word/_rels/document.xml.rels",
+            format!(
+                "{XML_DECLARATION}<Relationships xmlns=\"{PACKAGE_RELATIONSHIPS}\">\
+                 <Relationship Id=\"rId1\" Type=\"{R}/styles\" Target=\"styles.xml\"/>\
+                 <Relationship Id=\"rId2\" Type=\"{R}/numbering\" Target=\"numbering.xml\"/>\
+                 {}</Relationships>",
+                self.relationships.concat()
+            )
+            .as_bytes(),
+        )?;
+        part(
+            "word/document.xml",
+            format!(
+                "{XML_DECLARATION}<w:document xmlns:w=\"{W}\" xmlns:r=\"{R}\" \
+                 xmlns:wp=\"http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing\" \
+                 xmlns:a=\"http://schemas.openxmlformats.org/drawingml/2006/main\" \
+                 xmlns:pic=\"http://schemas.openxmlformats.org/drawingml/2006/picture\">\
+                 <w:body>{}<w:sectPr><w:pgSz w:w=\"12240\" w:h=\"15840\"/>\
+                 <w:pgMar w:top=\"1440\" w:right=\"1440\" w:bottom=\"1440\" w:left=\"1440\" \
+                 w:header=\"720\" w:footer=\"720\" w:gutter=\"0\"/></w:sectPr></w:body>\
+                 </w:document>",
+                self.body
+            )
+            .as_bytes(),
+        )?;
+        part("word/styles.xml", styles().as_bytes())?;
+        part(
+            "word/numbering.xml",
+            numbering(self.ordered_lists).as_bytes(),
+        )?;
+        for (path, bytes) in &self.media {
+            part(&format!("word/{path}"), bytes)?;
+        }
+        Ok(zip.finish()?.into_inner())
+    }
+}
+
+const XML_DECLARATION: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n";
+
+/// the paragraph and character styles the document refers to, 11pt Calibri by default
+fn styles() -> String {
+    let mut styles = format!(
+        "{XML_DECLARATION}<w:styles xmlns:w=\"{W}\"><w:docDefaults><w:rPrDefault><w:rPr>\
+         <w:rFonts w:ascii=\"Calibri\" w:hAnsi=\"Calibri\" w:eastAsia=\"Calibri\" w:cs=\"Calibri\"/>\
+         <w:sz w:val=\"22\"/><w:szCs w:val=\"22\"/></w:rPr></w:rPrDefault><w:pPrDefault><w:pPr>\
+         <w:spacing w:after=\"160\" w:line=\"259\" w:lineRule=\"auto\"/></w:pPr></w:pPrDefault>\
+         </w:docDefaults>\
+         <w:style w:type=\"paragraph\" w:default=\"1\" w:styleId=\"Normal\"><w:name w:val=\"Normal\"/>\
+         <w:qFormat/></w:style>"
+    );
+    for (level, size) in (1..=6).zip([40, 32, 28, 24, 22, 22]) {
+        let _ = write!(
+            styles,
+            "<w:style w:type=\"paragraph\" w:styleId=\"Heading{level}\">\
+             <w:name w:val=\"heading {level}\"/><w:basedOn w:val=\"Normal\"/>\
+             <w:next w:val=\"Normal\"/><w:uiPriority w:val=\"9\"/><w:qFormat/>\
+             <w:pPr><w:keepNext/><w:keepLines/><w:spacing w:before=\"240\" w:after=\"80\"/>\
+             <w:outlineLvl w:val=\"{}\"/></w:pPr>\
+             <w:rPr><w:b/><w:bCs/><w:sz w:val=\"{size}\"/><w:szCs w:val=\"{size}\"/></w:rPr>\
+             </w:style>",
+            level - 1
+        );
+    }
+    styles.push_str(
+        "<w:style w:type=\"paragraph\" w:styleId=\"Quote\"><w:name w:val=\"Quote\"/>\
+         <w:basedOn w:val=\"Normal\"/><w:next w:val=\"Normal\"/><w:qFormat/>\
+         <w:pPr><w:pBdr><w:left w:val=\"single\" w:sz=\"18\" w:space=\"8\" w:color=\"CCCCCC\"/>\
+         </w:pBdr><w:ind w:left=\"360\"/></w:pPr><w:rPr><w:i/><w:iCs/>\
+         <w:color w:val=\"555555\"/></w:rPr></w:style>\
+         <w:style w:type=\"paragraph\" w:customStyle=\"1\" w:styleId=\"Code\">\
+         <w:name w:val=\"Code\"/><w:basedOn w:val=\"Normal\"/><w:qFormat/>\
+         <w:pPr><w:shd w:val=\"clear\" w:color=\"auto\" w:fill=\"F0F0F0\"/>\
+         <w:spacing w:after=\"0\" w:line=\"240\" w:lineRule=\"auto\"/></w:pPr>\
+         <w:rPr><w:rFonts w:ascii=\"Courier New\" w:hAnsi=\"Courier New\" w:cs=\"Courier New\"/>\
+         <w:sz w:val=\"20\"/><w:szCs w:val=\"20\"/></w:rPr></w:style>\
+         <w:style w:type=\"paragraph\" w:styleId=\"ListParagraph\">\
+         <w:name w:val=\"List Paragraph\"/><w:basedOn w:val=\"Normal\"/><w:uiPriority w:val=\"34\"/>\
+         <w:qFormat/><w:pPr><w:ind w:left=\"720\"/><w:contextualSpacing/></w:pPr></w:style>\
+         <w:style w:type=\"character\" w:styleId=\"Hyperlink\"><w:name w:val=\"Hyperlink\"/>\
+         <w:uiPriority w:val=\"99\"/><w:unhideWhenUsed/><w:rPr><w:color w:val=\"0563C1\"/>\
+         <w:u w:val=\"single\"/></w:rPr></w:style></w:styles>",
+    );
+    styles
+}
+
+/// one abstract list per list kind with nine levels, and a numbering instance restarting at
+/// 1 for each of the `ordered_lists`
+fn numbering(ordered_lists: usize) -> String {
+    let mut numbering = format!("{XML_DECLARATION}<w:numbering xmlns:w=\"{W}\">");
+    let kinds: [&dyn Fn(usize) -> (&'static str, String); 4] = [
+        &|_| ("bullet", "\u{2022}".into()),
+        &|_| ("bullet", "\u{2611}".into()),
+        &|_| ("bullet", "\u{2610}".into()),
+        // the counter styles Quill uses for nested ordered lists
+        &|level| {
+            let format = ["decimal", "lowerLetter", "lowerRoman"][level % 3];
+            (format, format!("%{}.", level + 1))
+        },
+    ];
+    for (abstract_id, kind) in kinds.iter().enumerate() {
+        let _ = write!(
+            numbering,
+            "<w:abstractNum w:abstractNumId=\"{abstract_id}\">\
+             <w:multiLevelType w:val=\"hybridMultilevel\"/>"
+        );
+        for level in 0..9 {
+            let (format, text) = kind(level);
+            let _ = write!(
+                numbering,
+                "<w:lvl w:ilvl=\"{level}\"><w:start w:val=\"1\"/><w:numFmt w:val=\"{format}\"/>\
+                 <w:lvlText w:val=\"{text}\"/><w:lvlJc w:val=\"left\"/><w:pPr>\
+                 <w:ind w:left=\"{}\" w:hanging=\"360\"/></w:pPr></w:lvl>",
+                INDENT * (level as u32 + 1)
+            );
+        }
+        numbering.push_str("</w:abstractNum>");
+    }
+    for (id, abstract_id) in [(BULLET_LIST, 0), (CHECKED_LIST, 1), (UNCHECKED_LIST, 2)] {
+        let _ = write!(
+            numbering,
+            "<w:num w:numId=\"{id}\"><w:abstractNumId w:val=\"{abstract_id}\"/></w:num>"
+        );
+    }
+    for list in 1..=ordered_lists {
+        let _ = write!(
+            numbering,
+            "<w:num w:numId=\"{}\"><w:abstractNumId w:val=\"3\"/>",
+            UNCHECKED_LIST + list
+        );
+        for level in 0..9 {
+            let _ = write!(
+                numbering,
+                "<w:lvlOverride w:ilvl=\"{level}\"><w:startOverride w:val=\"1\"/></w:lvlOverride>"
+            );
+        }
+        numbering.push_str("</w:num>");
+    }
+    numbering.push_str("</w:numbering>");
+    numbering
+}
+
+/// `<w:rPr>` in the order of the schema: style, fonts, bold, italic, strike, color, size,
+/// underline, shading and vertical alignment
+fn run_properties(formats: &[Inline], link: bool) -> String {
+    let mut properties = String::new();
+    if link {
+        properties.push_str("<w:rStyle w:val=\"Hyperlink\"/>");
+    }
+    let font = formats.iter().find_map(|format| match format {
+        Inline::Code => Some("Courier New".to_string()),
+        Inline::Font(font) => Some(font_name(font)),
+        _ => None,
+    });
+    if let Some(font) = font {
+        let font = escape_xml(&font);
+        let _ = write!(
+            properties,
+            "<w:rFonts w:ascii=\"{font}\" w:hAnsi=\"{font}\" w:cs=\"{font}\"/>"
+        );
+    }
+    let has = |format: Inline| formats.contains(&format);
+    if has(Inline::Bold) {
+        properties.push_str("<w:b/><w:bCs/>");
+    }
+    if has(Inline::Italic) {
+        properties.push_str("<w:i/><w:iCs/>");
+    }
+    if has(Inline::Strike) {
+        properties.push_str("<w:strike/>");
+    }
+    for format in formats {
+        if let Inline::Color(color) = format {
+            let _ = write!(
+                properties,
+                "<w:color w:val=\"{:02X}{:02X}{:02X}\"/>",
+                color.r, color.g, color.b
+            );
+        }
+    }
+    for format in formats {
+        if let Inline::Size(size) = format
+            && let Some(half_points) = half_points(size)
+        {
+            let _ = write!(
+                properties,
+                "<w:sz w:val=\"{half_points}\"/><w:szCs w:val=\"{half_points}\"/>"
+            );
+        }
+    }
+    if has(Inline::Underline) {
+        properties.push_str("<w:u w:val=\"single\"/>");
+    }
+    for format in formats {
+        if let Inline::BgColor(color) = format {
+            let _ = write!(
+                properties,
+                "<w:shd w:val=\"clear\" w:color=\"auto\" w:fill=\"{:02X}{:02X}{:02X}\"/>",
+                color.r, color.g, color.b
+            );
+        }
+    }
+    if has(Inline::Sub) {
+        properties.push_str("<w:vertAlign w:val=\"subscript\"/>");
+    } else if has(Inline::Sup) {
+        properties.push_str("<w:vertAlign w:val=\"superscript\"/>");
+    }
+    properties
+}
+
+fn text_run(text: &str, properties: &str) -> String {
+    if text.is_empty() {
+        return String::new();
+    }
+    let mut run = String::from("<w:r>");
+    if !properties.is_empty() {
+        let _ = write!(run, "<w:rPr>{properties}</w:rPr>");
+    }
+    for (index, part) in text.split('\t').enumerate() {
+        if index > 0 {
+            run.push_str("<w:tab/>");
+        }
+        if !part.is_empty() {
+            let _ = write!(
+                run,
+                "<w:t xml:space=\"preserve\">{}</w:t>",
+                escape_xml(part)
+            );
+        }
+    }
+    run.push_str("</w:r>");
+    run
+}
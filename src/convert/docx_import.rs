@@ -0,0 +1,1125 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    io::{Cursor, Read},
+};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use roxmltree::{Document, Node};
+use serde_json::Value;
+use zip::ZipArchive;
+
+use crate::{
+    Align, Block, Color, Delta, EmbedAttr, Embeds, Font, Inline, ListKind, SanitizePolicy, Schema,
+    Size,
+};
+
+/// twips of one indent level, half an inch
+const INDENT: f64 = 720.0;
+/// english metric units per pixel
+const EMU: f64 = 9525.0;
+/// the size of Word's default text, 11pt in half-points
+const DEFAULT_SIZE: f64 = 22.0;
+/// the default of [`DocxImportBuilder::max_size`], 64 MiB
+const MAX_SIZE: u64 = 64 << 20;
+
+/// an image stored in a Word document, see [`DocxImportBuilder::assets`]
+pub struct DocxAsset<'a> {
+    /// the path of the image in the package, like `word/media/image1.png`
+    pub path: &'a str,
+    pub content_type: &'a str,
+    pub bytes: &'a [u8],
+}
+
+type AssetFn = Box<dyn Fn(&DocxAsset) -> Option<String>>;
+
+/// why [`Delta::from_docx_with`] failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DocxImportError {
+    /// the bytes are not a zip archive
+    Zip(String),
+    /// a part the document cannot go without, by its path in the package
+    MissingPart(String),
+    /// a part that would take the bytes read past [`DocxImportBuilder::max_size`]
+    TooLarge(String),
+    Xml {
+        part: String,
+        message: String,
+    },
+}
+
+impl Display for DocxImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Zip(message) => write!(f, "not a docx package: {message}"),
+            Self::MissingPart(part) => write!(f, "missing part {part}"),
+            Self::TooLarge(part) => write!(f, "{part} is over the size limit"),
+            Self::Xml { part, message } => write!(f, "malformed {part}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for DocxImportError {}
+
+/// something of the document the delta does not keep, see [`DocxDocument::warnings`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DocxWarning {
+    /// a construct without a Quill equivalent: `"table"`, `"footnote"`, `"equation"`, ... or
+    /// an unknown element as `"<name>"`. the text of tables, text fields and links is kept
+    Unsupported(String),
+    /// an image left out, by its path in the package: its bytes are missing or over the size
+    /// limit, or the asset callback returned `None`
+    Image(String),
+}
+
+impl Display for DocxWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unsupported(construct) => write!(f, "unsupported {construct}"),
+            Self::Image(path) => write!(f, "image {path} left out"),
+        }
+    }
+}
+
+/// a Word document read by [`Delta::from_docx`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct DocxDocument {
+    pub delta: Delta,
+    /// each distinct warning once, in document order
+    pub warnings: Vec<DocxWarning>,
+}
+
+/// how a Word document is turned into a delta, see [`Delta::from_docx_with`].
+///
+/// the default keeps the formats of [`Schema::default`], sanitizes urls with
+/// [`SanitizePolicy::default`] and inlines images as `data:` urls.
+pub struct DocxImport {
+    schema: Schema,
+    sanitize: SanitizePolicy,
+    assets: Option<AssetFn>,
+    max_size: u64,
+}
+
+impl Default for DocxImport {
+    fn default() -> Self {
+        Self {
+            schema: Schema::default(),
+            sanitize: SanitizePolicy::default(),
+            assets: None,
+            max_size: MAX_SIZE,
+        }
+    }
+}
+
+impl DocxImport {
+    pub fn builder() -> DocxImportBuilder {
+        DocxImportBuilder::new()
+    }
+}
+
+pub struct DocxImportBuilder {
+    import: DocxImport,
+}
+
+impl DocxImportBuilder {
+    pub fn new() -> Self {
+        Self {
+            import: DocxImport::default(),
+        }
+    }
+    /// formats, line formats and embeds the schema rejects are left out
+    pub fn schema(mut self, schema: Schema) -> Self {
+        self.import.schema = schema;
+        self
+    }
+    pub fn sanitize(mut self, policy: SanitizePolicy) -> Self {
+        self.import.sanitize = policy;
+        self
+    }
+    /// gets each image stored in the document and returns its url in the delta, typically
+    /// after uploading it. `None` leaves the image out.
+    ///
+    /// without it png, jpeg, gif, bmp, webp and svg images become base64 `data:` urls and
+    /// the others, like emf, are left out.
+    pub fn assets(mut self, callback: impl Fn(&DocxAsset) -> Option<String> + 'static) -> Self {
+        self.import.assets = Some(Box::new(callback));
+        self
+    }
+    /// the most bytes read out of the package, uncompressed, 64 MiB by default. a document
+    /// going over it fails with [`DocxImportError::TooLarge`], images past it are left out.
+    pub fn max_size(mut self, bytes: u64) -> Self {
+        self.import.max_size = bytes;
+        self
+    }
+    pub fn build(self) -> DocxImport {
+        self.import
+    }
+}
+
+impl Default for DocxImportBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Delta {
+    /// reads a Word (`.docx`) file: paragraphs become lines, run properties become formats,
+    /// numbered paragraphs become lists with their level as `indent`, hyperlinks become links
+    /// and inline images become image embeds.
+    ///
+    /// `Heading 1`..`Heading 6` and `Title` paragraphs become headers, `Quote` paragraphs
+    /// quotes and paragraphs of a code style code blocks. run sizes become `small`, `large` or
+    /// `huge` relative to the paragraph text, monospace fonts become `code`, serif fonts the
+    /// `serif` font. what has no Quill equivalent, like tables and footnotes, is reported in
+    /// [`DocxDocument::warnings`].
+    ///
+    /// uses [`DocxImport::default`], see [`Delta::from_docx_with`].
+    ///
+    /// ```
+    /// # use quill_wr::*;
+    /// let docx = delta!["Title", "\n" { header: 1 }, "a ", "b" { bold: true }, "\n"]
+    ///     .to_docx()
+    ///     .unwrap();
+    /// let imported = Delta::from_docx(&docx).unwrap();
+    /// assert_eq!(
+    ///     imported.delta,
+    ///     delta!["Title", "\n" { header: 1 }, "a ", "b" { bold: true }, "\n"]
+    /// );
+    /// assert!(imported.warnings.is_empty());
+    /// ```
+    pub fn from_docx(docx: &[u8]) -> Result<DocxDocument, DocxImportError> {
+        Self::from_docx_with(docx, &DocxImport::default())
+    }
+    pub fn from_docx_with(
+        docx: &[u8],
+        import: &DocxImport,
+    ) -> Result<DocxDocument, DocxImportError> {
+        let mut package = Package::open(docx, import.max_size)?;
+        let part = package
+            .relationships("")?
+            .into_values()
+            .find(|relationship| relationship.kind == "officeDocument")
+            .map(|relationship| resolve("", &relationship.target))
+            .unwrap_or_else(|| "word/document.xml".into());
+        let relationships = package.relationships(&part)?;
+        // the xml of the styles and numbering parts, when the document has them
+        let mut related = |kind: &str| {
+            let path = relationships
+                .values()
+                .find(|relationship| relationship.kind == kind && !relationship.external)
+                .map(|relationship| resolve(&part, &relationship.target));
+            match path {
+                Some(path) => Ok(package.text(&path)?.map(|xml| (path, xml))),
+                None => Ok(None),
+            }
+        };
+        let styles = match related("styles")? {
+            Some((path, xml)) => Styles::parse(&parse(&path, &xml)?),
+            None => Styles::default(),
+        };
+        let numbering = match related("numbering")? {
+            Some((path, xml)) => Numbering::parse(&parse(&path, &xml)?),
+            None => Numbering::default(),
+        };
+        let xml = package
+            .text(&part)?
+            .ok_or_else(|| DocxImportError::MissingPart(part.clone()))?;
+        let document = parse(&part, &xml)?;
+        let body = child(document.root_element(), "body").ok_or_else(|| DocxImportError::Xml {
+            part: part.clone(),
+            message: "no body".into(),
+        })?;
+        let mut importer = Importer {
+            import,
+            package,
+            part,
+            relationships,
+            styles,
+            numbering,
+            delta: Delta::new(),
+            warnings: vec![],
+        };
+        importer.blocks(body);
+        let mut delta = importer.delta;
+        if delta.ops().is_empty() {
+            delta.insert_newline(&[]);
+        }
+        delta.sanitize(&import.sanitize);
+        Ok(DocxDocument {
+            delta,
+            warnings: importer.warnings,
+        })
+    }
+}
+
+struct Package<'a> {
+    archive: ZipArchive<Cursor<&'a [u8]>>,
+    /// the bytes left to read before [`DocxImportError::TooLarge`]
+    remaining: u64,
+}
+
+/// a relationship of a part, `kind` is the last segment of its type, like `hyperlink`
+struct Relationship {
+    kind: String,
+    target: String,
+    external: bool,
+}
+
+impl<'a> Package<'a> {
+    fn open(bytes: &'a [u8], max_size: u64) -> Result<Self, DocxImportError> {
+        ZipArchive::new(Cursor::new(bytes))
+            .map(|archive| Self {
+                archive,
+                remaining: max_size,
+            })
+            .map_err(|error| DocxImportError::Zip(error.to_string()))
+    }
+    /// the bytes of a part, `None` when it is missing or unreadable
+    fn bytes(&mut self, path: &str) -> Result<Option<Vec<u8>>, DocxImportError> {
+        let too_large = || DocxImportError::TooLarge(path.into());
+        let Ok(file) = self.archive.by_name(path) else {
+            return Ok(None);
+        };
+        if file.size() > self.remaining {
+            return Err(too_large());
+        }
+        // the size in the archive is not trusted, the read stops one byte past the limit
+        let mut bytes = vec![];
+        if file
+            .take(self.remaining.saturating_add(1))
+            .read_to_end(&mut bytes)
+            .is_err()
+        {
+            return Ok(None);
+        }
+        self.remaining = self
+            .remaining
+            .checked_sub(bytes.len() as u64)
+            .ok_or_else(too_large)?;
+        Ok(Some(bytes))
+    }
+    fn text(&mut self, path: &str) -> Result<Option<String>, DocxImportError> {
+        let Some(bytes) = self.bytes(path)? else {
+            return Ok(None);
+        };
+        Ok(String::from_utf8(bytes)
+            .ok()
+            .map(|text| text.trim_start_matches('\u{feff}').to_string()))
+    }
+    /// the relationships of a part by id, of the package itself for `""`
+    fn relationships(
+        &mut self,
+        part: &str,
+    ) -> Result<HashMap<String, Relationship>, DocxImportError> {
+        let (folder, name) = part.rsplit_once('/').unwrap_or(("", part));
+        let path = if folder.is_empty() {
+            format!("_rels/{name}.rels")
+        } else {
+            format!("{folder}/_rels/{name}.rels")
+        };
+        let Some(xml) = self.text(&path)? else {
+            return Ok(HashMap::new());
+        };
+        let document = parse(&path, &xml)?;
+        Ok(document
+            .root_element()
+            .children()
+            .filter(|node| is(*node, "Relationship"))
+            .filter_map(|node| {
+                let relationship = Relationship {
+                    kind: attribute(node, "Type")?.rsplit('/').next()?.to_string(),
+                    target: attribute(node, "Target")?.to_string(),
+                    external: attribute(node, "TargetMode") == Some("External"),
+                };
+                Some((attribute(node, "Id")?.to_string(), relationship))
+            })
+            .collect())
+    }
+}
+
+fn parse<'x>(part: &str, xml: &'x str) -> Result<Document<'x>, DocxImportError> {
+    Document::parse(xml).map_err(|error| DocxImportError::Xml {
+        part: part.into(),
+        message: error.to_string(),
+    })
+}
+
+/// the package path of a relationship target, relative to the folder of `part`
+fn resolve(part: &str, target: &str) -> String {
+    let mut segments: Vec<&str> = part.split('/').collect();
+    segments.pop();
+    if target.starts_with('/') {
+        segments.clear();
+    }
+    for segment in target.split('/') {
+        match segment {
+            ".." => {
+                segments.pop();
+            }
+            "." | "" => {}
+            _ => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
+
+// elements and attributes are matched by local name, the transitional and strict namespaces
+// of the format only differ there
+fn is(node: Node, name: &str) -> bool {
+    node.is_element() && node.tag_name().name() == name
+}
+
+fn child<'a, 'i>(node: Node<'a, 'i>, name: &str) -> Option<Node<'a, 'i>> {
+    node.children().find(|child| is(*child, name))
+}
+
+fn attribute<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    node.attributes()
+        .find(|attribute| attribute.name() == name)
+        .map(|attribute| attribute.value())
+}
+
+/// the `w:val` of a child property
+fn value<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    child(node, name).and_then(|property| attribute(property, "val"))
+}
+
+/// an on/off property, on unless its value turns it off
+fn toggle(node: Node, name: &str) -> Option<bool> {
+    child(node, name).map(|property| {
+        !matches!(
+            attribute(property, "val"),
+            Some("0" | "false" | "off" | "none")
+        )
+    })
+}
+
+/// `base` takes the values `other` sets
+fn inherit<T: Clone>(base: &mut Option<T>, other: &Option<T>) {
+    if other.is_some() {
+        base.clone_from(other);
+    }
+}
+
+#[derive(Clone, Default)]
+struct ParagraphProperties {
+    style: Option<String>,
+    numbering: Option<String>,
+    level: Option<u8>,
+    outline: Option<u8>,
+    align: Option<String>,
+    /// the left indent in twips
+    indent: Option<f64>,
+    rtl: Option<bool>,
+}
+
+impl ParagraphProperties {
+    fn parse(properties: Node) -> Self {
+        let numbering = child(properties, "numPr");
+        let indent = child(properties, "ind")
+            .and_then(|indent| attribute(indent, "left").or_else(|| attribute(indent, "start")));
+        Self {
+            style: value(properties, "pStyle").map(str::to_string),
+            numbering: numbering
+                .and_then(|numbering| value(numbering, "numId"))
+                .map(str::to_string),
+            level: numbering
+                .and_then(|numbering| value(numbering, "ilvl"))
+                .and_then(|level| level.parse().ok()),
+            outline: value(properties, "outlineLvl").and_then(|level| level.parse().ok()),
+            align: value(properties, "jc").map(str::to_string),
+            indent: indent.and_then(|indent| indent.parse().ok()),
+            rtl: toggle(properties, "bidi"),
+        }
+    }
+    fn inherit(&mut self, other: &Self) {
+        inherit(&mut self.numbering, &other.numbering);
+        inherit(&mut self.level, &other.level);
+        inherit(&mut self.outline, &other.outline);
+        inherit(&mut self.align, &other.align);
+        inherit(&mut self.indent, &other.indent);
+        inherit(&mut self.rtl, &other.rtl);
+    }
+}
+
+#[derive(Clone, Default)]
+struct RunProperties {
+    style: Option<String>,
+    bold: Option<bool>,
+    italic: Option<bool>,
+    underline: Option<bool>,
+    strike: Option<bool>,
+    /// `RRGGBB` or `auto`
+    color: Option<String>,
+    background: Option<Color>,
+    /// in half-points
+    size: Option<f64>,
+    font: Option<String>,
+    script: Option<String>,
+}
+
+impl RunProperties {
+    fn parse(properties: Node) -> Self {
+        let shading = child(properties, "shd")
+            .and_then(|shading| attribute(shading, "fill"))
+            .and_then(hex_color);
+        Self {
+            style: value(properties, "rStyle").map(str::to_string),
+            bold: toggle(properties, "b"),
+            italic: toggle(properties, "i"),
+            underline: toggle(properties, "u"),
+            strike: toggle(properties, "strike").or_else(|| toggle(properties, "dstrike")),
+            color: value(properties, "color").map(str::to_string),
+            background: value(properties, "highlight")
+                .map(highlight)
+                .or(shading.map(Some))
+                .flatten(),
+            size: value(properties, "sz").and_then(|size| size.parse().ok()),
+            font: child(properties, "rFonts")
+                .and_then(|fonts| attribute(fonts, "ascii").or_else(|| attribute(fonts, "hAnsi")))
+                .map(str::to_string),
+            script: value(properties, "vertAlign").map(str::to_string),
+        }
+    }
+    fn inherit(&mut self, other: &Self) {
+        inherit(&mut self.bold, &other.bold);
+        inherit(&mut self.italic, &other.italic);
+        inherit(&mut self.underline, &other.underline);
+        inherit(&mut self.strike, &other.strike);
+        inherit(&mut self.color, &other.color);
+        inherit(&mut self.background, &other.background);
+        inherit(&mut self.size, &other.size);
+        inherit(&mut self.font, &other.font);
+        inherit(&mut self.script, &other.script);
+    }
+    /// the formats of the run, its size relative to `base_size`
+    fn inlines(&self, base_size: f64) -> Vec<Inline> {
+        let mut inlines = vec![];
+        let on = |toggle: Option<bool>| toggle == Some(true);
+        if on(self.bold) {
+            inlines.push(Inline::Bold);
+        }
+        if on(self.italic) {
+            inlines.push(Inline::Italic);
+        }
+        if on(self.underline) {
+            inlines.push(Inline::Underline);
+        }
+        if on(self.strike) {
+            inlines.push(Inline::Strike);
+        }
+        match self.font.as_deref().map(str::to_ascii_lowercase) {
+            Some(font) if is_monospace(&font) => inlines.push(Inline::Code),
+            Some(font) if is_serif(&font) => inlines.push(Inline::Font(Font::Serif)),
+            _ => {}
+        }
+        // black is the default text color
+        if let Some(color) = self.color.as_deref().and_then(hex_color)
+            && color != Color::rgb(0, 0, 0)
+        {
+            inlines.push(Inline::Color(color));
+        }
+        if let Some(background) = self.background {
+            inlines.push(Inline::BgColor(background));
+        }
+        if let Some(size) = self.size {
+            let ratio = size / base_size;
+            if ratio < 0.875 {
+                inlines.push(Inline::Size(Size::Small));
+            } else if ratio >= 2.0 {
+                inlines.push(Inline::Size(Size::Huge));
+            } else if ratio >= 1.25 {
+                inlines.push(Inline::Size(Size::Large));
+            }
+        }
+        match self.script.as_deref() {
+            Some("superscript") => inlines.push(Inline::Sup),
+            Some("subscript") => inlines.push(Inline::Sub),
+            _ => {}
+        }
+        inlines
+    }
+}
+
+fn hex_color(hex: &str) -> Option<Color> {
+    (hex.len() == 6)
+        .then(|| Color::parse(&format!("#{hex}")))
+        .flatten()
+}
+
+/// the colors of Word's highlighter, `None` for `none`
+fn highlight(name: &str) -> Option<Color> {
+    let rgb = match name {
+        "yellow" => (255, 255, 0),
+        "green" => (0, 255, 0),
+        "cyan" => (0, 255, 255),
+        "magenta" => (255, 0, 255),
+        "blue" => (0, 0, 255),
+        "red" => (255, 0, 0),
+        "darkBlue" => (0, 0, 128),
+        "darkCyan" => (0, 128, 128),
+        "darkGreen" => (0, 128, 0),
+        "darkMagenta" => (128, 0, 128),
+        "darkRed" => (128, 0, 0),
+        "darkYellow" => (128, 128, 0),
+        "darkGray" => (128, 128, 128),
+        "lightGray" => (192, 192, 192),
+        "black" => (0, 0, 0),
+        "white" => (255, 255, 255),
+        _ => return None,
+    };
+    Some(Color::rgb(rgb.0, rgb.1, rgb.2))
+}
+
+fn is_monospace(font: &str) -> bool {
+    font.contains("mono")
+        || [
+            "courier",
+            "consolas",
+            "menlo",
+            "monaco",
+            "lucida console",
+            "source code",
+        ]
+        .iter()
+        .any(|family| font.starts_with(family))
+}
+
+fn is_serif(font: &str) -> bool {
+    !font.contains("sans")
+        && [
+            "times",
+            "georgia",
+            "cambria",
+            "garamond",
+            "palatino",
+            "book antiqua",
+            "baskerville",
+            "serif",
+        ]
+        .iter()
+        .any(|family| font.contains(family))
+}
+
+struct Style {
+    /// lowercase, like `heading 1`
+    name: String,
+    based_on: Option<String>,
+    paragraph: ParagraphProperties,
+    run: RunProperties,
+}
+
+#[derive(Default)]
+struct Styles {
+    styles: HashMap<String, Style>,
+    /// the style of paragraphs without one, `Normal`
+    default_paragraph: Option<String>,
+    /// the run properties of the document defaults
+    defaults: RunProperties,
+}
+
+impl Styles {
+    fn parse(document: &Document) -> Self {
+        let root = document.root_element();
+        let mut styles = Self {
+            defaults: child(root, "docDefaults")
+                .and_then(|defaults| child(defaults, "rPrDefault"))
+                .and_then(|defaults| child(defaults, "rPr"))
+                .map(RunProperties::parse)
+                .unwrap_or_default(),
+            ..Self::default()
+        };
+        for node in root.children().filter(|node| is(*node, "style")) {
+            let Some(id) = attribute(node, "styleId") else {
+                continue;
+            };
+            if attribute(node, "type") == Some("paragraph")
+                && matches!(attribute(node, "default"), Some("1" | "true"))
+            {
+                styles.default_paragraph = Some(id.to_string());
+            }
+            let style = Style {
+                name: value(node, "name").unwrap_or(id).to_ascii_lowercase(),
+                based_on: value(node, "basedOn").map(str::to_string),
+                paragraph: child(node, "pPr")
+                    .map(ParagraphProperties::parse)
+                    .unwrap_or_default(),
+                run: child(node, "rPr")
+                    .map(RunProperties::parse)
+                    .unwrap_or_default(),
+            };
+            styles.styles.insert(id.to_string(), style);
+        }
+        styles
+    }
+    /// the style and the ones it is based on, the style first
+    fn chain(&self, id: Option<&str>) -> Vec<&Style> {
+        let mut chain = vec![];
+        let mut id = id;
+        // a few levels are enough and stop cycles
+        while let Some(style) = id.and_then(|id| self.styles.get(id))
+            && chain.len() < 16
+        {
+            chain.push(style);
+            id = style.based_on.as_deref();
+        }
+        chain
+    }
+    /// the run properties of a style chain over the document defaults
+    fn run(&self, chain: &[&Style]) -> RunProperties {
+        let mut run = self.defaults.clone();
+        for style in chain.iter().rev() {
+            run.inherit(&style.run);
+        }
+        run
+    }
+}
+
+#[derive(Default)]
+struct Numbering {
+    /// the list of each level of the abstract numberings
+    abstracts: HashMap<String, HashMap<u8, Option<ListKind>>>,
+    /// the abstract numbering of each numbering and its overridden levels
+    numberings: HashMap<String, (String, HashMap<u8, Option<ListKind>>)>,
+}
+
+impl Numbering {
+    fn parse(document: &Document) -> Self {
+        let root = document.root_element();
+        let levels = |node: Node| {
+            node.descendants()
+                .filter(|level| is(*level, "lvl"))
+                .filter_map(|level| {
+                    let index = attribute(level, "ilvl")?.parse().ok()?;
+                    Some((index, list_kind(level)))
+                })
+                .collect::<HashMap<_, _>>()
+        };
+        let mut numbering = Self::default();
+        for node in root.children() {
+            if is(node, "abstractNum")
+                && let Some(id) = attribute(node, "abstractNumId")
+            {
+                numbering.abstracts.insert(id.to_string(), levels(node));
+            } else if is(node, "num")
+                && let (Some(id), Some(abstract_id)) =
+                    (attribute(node, "numId"), value(node, "abstractNumId"))
+            {
+                numbering
+                    .numberings
+                    .insert(id.to_string(), (abstract_id.to_string(), levels(node)));
+            }
+        }
+        numbering
+    }
+    fn kind(&self, id: &str, level: u8) -> Option<ListKind> {
+        let (abstract_id, overrides) = self.numberings.get(id)?;
+        match overrides.get(&level) {
+            Some(kind) => *kind,
+            None => *self.abstracts.get(abstract_id)?.get(&level)?,
+        }
+    }
+}
+
+/// bullets drawn as a ticked or empty box are checklists, `none` levels are no list
+fn list_kind(level: Node) -> Option<ListKind> {
+    match value(level, "numFmt").unwrap_or("decimal") {
+        "none" => None,
+        "bullet" => Some(match value(level, "lvlText").unwrap_or_default() {
+            "☑" | "☒" | "✓" | "✔" => ListKind::Checked,
+            "☐" | "□" | "❑" | "❒" => ListKind::Unchecked,
+            _ => ListKind::Bullet,
+        }),
+        _ => Some(ListKind::Ordered),
+    }
+}
+
+/// the line formats and base size of the paragraph being read
+struct Paragraph {
+    blocks: Vec<Block>,
+    /// the text size of the paragraph style in half-points
+    size: f64,
+    /// code blocks hold plain text
+    code: bool,
+}
+
+struct Importer<'a, 'p> {
+    import: &'a DocxImport,
+    package: Package<'p>,
+    /// the path of the main document
+    part: String,
+    relationships: HashMap<String, Relationship>,
+    styles: Styles,
+    numbering: Numbering,
+    delta: Delta,
+    warnings: Vec<DocxWarning>,
+}
+
+impl Importer<'_, '_> {
+    /// the paragraphs of the body, of a table cell or of a content control
+    fn blocks(&mut self, parent: Node) {
+        for node in parent.children().filter(Node::is_element) {
+            match node.tag_name().name() {
+                "p" => self.paragraph(node),
+                "tbl" => {
+                    self.warn(DocxWarning::Unsupported("table".into()));
+                    for row in node.children().filter(|row| is(*row, "tr")) {
+                        for cell in row.children().filter(|cell| is(*cell, "tc")) {
+                            self.blocks(cell);
+                        }
+                    }
+                }
+                "sdt" => {
+                    if let Some(content) = child(node, "sdtContent") {
+                        self.blocks(content);
+                    }
+                }
+                "customXml" | "ins" | "moveTo" => self.blocks(node),
+                "AlternateContent" => {
+                    if let Some(content) = alternate(node) {
+                        self.blocks(content);
+                    }
+                }
+                "altChunk" => self.warn(DocxWarning::Unsupported("embedded document".into())),
+                "sectPr" | "tcPr" | "bookmarkStart" | "bookmarkEnd" | "proofErr" | "permStart"
+                | "permEnd" | "commentRangeStart" | "commentRangeEnd" | "del" | "moveFrom" => {}
+                name => self.warn(DocxWarning::Unsupported(format!("<{name}>"))),
+            }
+        }
+    }
+
+    fn paragraph(&mut self, node: Node) {
+        let direct = child(node, "pPr")
+            .map(ParagraphProperties::parse)
+            .unwrap_or_default();
+        let style = direct
+            .style
+            .as_deref()
+            .or(self.styles.default_paragraph.as_deref());
+        let chain = self.styles.chain(style);
+        let mut properties = ParagraphProperties::default();
+        for style in chain.iter().rev() {
+            properties.inherit(&style.paragraph);
+        }
+        properties.inherit(&direct);
+        // the indent of a style is part of its look, like the margin of quotes
+        properties.indent = direct.indent;
+        let blocks = self.line_formats(&properties, &chain);
+        let paragraph = Paragraph {
+            code: blocks
+                .iter()
+                .any(|block| matches!(block, Block::CodeBlock(_))),
+            size: self.styles.run(&chain).size.unwrap_or(DEFAULT_SIZE),
+            blocks: self.accepted_blocks(blocks),
+        };
+        self.inline(node, &paragraph, None);
+        self.delta.insert_newline(&paragraph.blocks);
+    }
+
+    /// a list keeps its level as indent, headers come before quotes and code blocks
+    fn line_formats(&self, properties: &ParagraphProperties, chain: &[&Style]) -> Vec<Block> {
+        let names = || chain.iter().map(|style| style.name.as_str());
+        let mut blocks = vec![];
+        let level = properties.level.unwrap_or(0);
+        let list = properties
+            .numbering
+            .as_deref()
+            .filter(|id| *id != "0")
+            .and_then(|id| self.numbering.kind(id, level));
+        let header = names()
+            .find_map(|name| match name {
+                "title" => Some(1),
+                "subtitle" => Some(2),
+                _ => name.strip_prefix("heading ")?.parse().ok(),
+            })
+            // outline level 9 is Word's body text
+            .or(properties
+                .outline
+                .filter(|level| *level != 9)
+                .and_then(|level| level.checked_add(1)));
+        if let Some(kind) = list {
+            blocks.push(Block::List(kind));
+            blocks.extend(Block::indent(level));
+        } else if names().any(|name| name.contains("code") || PREFORMATTED.contains(&name)) {
+            blocks.push(Block::CodeBlock(None));
+        } else {
+            if let Some(header) = header.and_then(Block::header) {
+                blocks.push(header);
+            } else if names().any(|name| name.contains("quote")) {
+                blocks.push(Block::Blockquote);
+            }
+            let indent = properties.indent.unwrap_or(0.0) / INDENT;
+            blocks.extend(Block::indent(indent.round().clamp(0.0, 8.0) as u8));
+        }
+        match properties.align.as_deref() {
+            Some("center") => blocks.push(Block::Align(Align::Center)),
+            Some("right" | "end") => blocks.push(Block::Align(Align::Right)),
+            Some("both" | "distribute") => blocks.push(Block::Align(Align::Justify)),
+            _ => {}
+        }
+        if properties.rtl == Some(true) {
+            blocks.push(Block::Rtl);
+        }
+        blocks
+    }
+
+    /// the runs of a paragraph or of an element wrapping some of them
+    fn inline(&mut self, parent: Node, paragraph: &Paragraph, link: Option<&str>) {
+        for node in parent.children().filter(Node::is_element) {
+            match node.tag_name().name() {
+                "r" => self.run(node, paragraph, link),
+                "hyperlink" => {
+                    let url = self.hyperlink(node);
+                    self.inline(node, paragraph, url.as_deref().or(link));
+                }
+                "sdt" => {
+                    if let Some(content) = child(node, "sdtContent") {
+                        self.inline(content, paragraph, link);
+                    }
+                }
+                "ins" | "moveTo" | "smartTag" | "customXml" | "fldSimple" | "dir" | "bdo" => {
+                    self.inline(node, paragraph, link)
+                }
+                "AlternateContent" => {
+                    if let Some(content) = alternate(node) {
+                        self.inline(content, paragraph, link);
+                    }
+                }
+                "oMath" | "oMathPara" => self.warn(DocxWarning::Unsupported("equation".into())),
+                "pPr" | "del" | "moveFrom" | "bookmarkStart" | "bookmarkEnd" | "proofErr"
+                | "permStart" | "permEnd" | "commentRangeStart" | "commentRangeEnd" => {}
+                name => self.warn(DocxWarning::Unsupported(format!("<{name}>"))),
+            }
+        }
+    }
+
+    /// the url of an external hyperlink, links to bookmarks are not kept
+    fn hyperlink(&mut self, node: Node) -> Option<String> {
+        if let Some(relationship) = attribute(node, "id").and_then(|id| self.relationships.get(id))
+        {
+            return Some(relationship.target.clone());
+        }
+        if attribute(node, "anchor").is_some() {
+            self.warn(DocxWarning::Unsupported("internal link".into()));
+        }
+        None
+    }
+
+    fn run(&mut self, node: Node, paragraph: &Paragraph, link: Option<&str>) {
+        let mut properties = RunProperties::default();
+        let direct = child(node, "rPr")
+            .map(RunProperties::parse)
+            .unwrap_or_default();
+        // the look of hyperlinks is not a format of their text
+        let chain = self
+            .styles
+            .chain(direct.style.as_deref())
+            .into_iter()
+            .filter(|style| !style.name.contains("hyperlink"));
+        for style in chain.collect::<Vec<_>>().iter().rev() {
+            properties.inherit(&style.run);
+        }
+        properties.inherit(&direct);
+        let inlines = if paragraph.code {
+            vec![]
+        } else {
+            let mut inlines = properties.inlines(paragraph.size);
+            inlines.extend(link.map(|url| Inline::Link(url.to_string())));
+            self.accepted_inlines(inlines)
+        };
+        self.run_content(node, paragraph, &inlines);
+    }
+
+    fn run_content(&mut self, parent: Node, paragraph: &Paragraph, inlines: &[Inline]) {
+        for node in parent.children().filter(Node::is_element) {
+            match node.tag_name().name() {
+                "t" => {
+                    let text = node.text().unwrap_or_default();
+                    if !text.is_empty() {
+                        self.delta.insert_text(text, inlines);
+                    }
+                }
+                "tab" | "ptab" => {
+                    self.delta.insert_text("\t", inlines);
+                }
+                "noBreakHyphen" => {
+                    self.delta.insert_text("-", inlines);
+                }
+                // a line break starts a line with the formats of the paragraph
+                "br" | "cr" => {
+                    if !matches!(attribute(node, "type"), Some("page" | "column")) {
+                        self.delta.insert_newline(&paragraph.blocks);
+                    }
+                }
+                "drawing" => self.drawing(node),
+                "pict" | "object" => self.picture(node),
+                "AlternateContent" => {
+                    if let Some(content) = alternate(node) {
+                        self.run_content(content, paragraph, inlines);
+                    }
+                }
+                "footnoteReference" => self.warn(DocxWarning::Unsupported("footnote".into())),
+                "endnoteReference" => self.warn(DocxWarning::Unsupported("endnote".into())),
+                "commentReference" => self.warn(DocxWarning::Unsupported("comment".into())),
+                "sym" => self.warn(DocxWarning::Unsupported("symbol".into())),
+                "rPr"
+                | "softHyphen"
+                | "fldChar"
+                | "instrText"
+                | "delText"
+                | "delInstrText"
+                | "lastRenderedPageBreak"
+                | "annotationRef"
+                | "footnoteRef"
+                | "endnoteRef"
+                | "separator"
+                | "continuationSeparator" => {}
+                name => self.warn(DocxWarning::Unsupported(format!("<{name}>"))),
+            }
+        }
+    }
+
+    /// a DrawingML picture, shapes and charts are not kept
+    fn drawing(&mut self, node: Node) {
+        let Some(blip) = node.descendants().find(|blip| is(*blip, "blip")) else {
+            let shape = if node.descendants().any(|text| is(text, "txbxContent")) {
+                "text box"
+            } else {
+                "shape"
+            };
+            return self.warn(DocxWarning::Unsupported(shape.into()));
+        };
+        let properties = node
+            .descendants()
+            .find(|properties| is(*properties, "docPr"));
+        let alt = properties.and_then(|properties| {
+            attribute(properties, "descr").or(attribute(properties, "title"))
+        });
+        let width = node
+            .descendants()
+            .find(|extent| is(*extent, "extent"))
+            .and_then(|extent| attribute(extent, "cx"))
+            .and_then(|cx| cx.parse::<f64>().ok())
+            .map(|cx| (cx / EMU).round());
+        if let Some(id) = attribute(blip, "embed").or(attribute(blip, "link")) {
+            self.image(id, alt, width);
+        }
+    }
+
+    /// a VML picture of older documents, other VML shapes and OLE objects are not kept
+    fn picture(&mut self, node: Node) {
+        let data = node.descendants().find(|data| is(*data, "imagedata"));
+        match data.and_then(|data| attribute(data, "id")) {
+            Some(id) => self.image(id, data.and_then(|data| attribute(data, "title")), None),
+            None if is(node, "object") => {
+                self.warn(DocxWarning::Unsupported("embedded object".into()))
+            }
+            None => self.warn(DocxWarning::Unsupported("shape".into())),
+        }
+    }
+
+    fn image(&mut self, id: &str, alt: Option<&str>, width: Option<f64>) {
+        let Some(relationship) = self.relationships.get(id) else {
+            return self.warn(DocxWarning::Image(id.into()));
+        };
+        let src = if relationship.external {
+            Some(relationship.target.clone())
+        } else {
+            let path = resolve(&self.part, &relationship.target);
+            let Ok(Some(bytes)) = self.package.bytes(&path) else {
+                return self.warn(DocxWarning::Image(path));
+            };
+            let asset = DocxAsset {
+                path: &path,
+                content_type: content_type(&path),
+                bytes: &bytes,
+            };
+            let src = match &self.import.assets {
+                Some(assets) => assets(&asset),
+                None => data_url(&asset),
+            };
+            if src.is_none() {
+                return self.warn(DocxWarning::Image(path));
+            }
+            src
+        };
+        let Some(src) = src else { return };
+        let schema = &self.import.schema;
+        if !schema.accepts_embed("image", &Value::String(src.clone())) {
+            return;
+        }
+        let mut attributes = vec![];
+        attributes.extend(
+            alt.filter(|alt| !alt.is_empty())
+                .map(|alt| EmbedAttr::Alt(alt.to_string())),
+        );
+        attributes.extend(width.map(|width| EmbedAttr::Width(width.to_string())));
+        attributes.retain(|attribute| {
+            let (name, value) = attribute.as_kv();
+            schema.accepts_embed_attr(&name, &value)
+        });
+        self.delta.insert_embed(Embeds::Image(src), &attributes);
+    }
+
+    fn accepted_inlines(&self, mut inlines: Vec<Inline>) -> Vec<Inline> {
+        inlines.retain(|inline| {
+            let (name, value) = inline.as_kv();
+            self.import.schema.accepts_inline(&name, &value)
+        });
+        inlines
+    }
+
+    fn accepted_blocks(&self, mut blocks: Vec<Block>) -> Vec<Block> {
+        blocks.retain(|block| {
+            let (name, value) = block.as_kv();
+            self.import.schema.accepts_block(&name, &value)
+        });
+        blocks
+    }
+
+    fn warn(&mut self, warning: DocxWarning) {
+        if !self.warnings.contains(&warning) {
+            self.warnings.push(warning);
+        }
+    }
+}
+
+/// styles of Word and LibreOffice for code, besides the ones named after it
+const PREFORMATTED: [&str; 3] = ["html preformatted", "preformatted text", "macro text"];
+
+/// the fallback written for consumers that do not know the extensions of the choices, else
+/// the first choice
+fn alternate<'a, 'i>(node: Node<'a, 'i>) -> Option<Node<'a, 'i>> {
+    child(node, "Fallback").or_else(|| child(node, "Choice"))
+}
+
+fn content_type(path: &str) -> &'static str {
+    let extension = path.rsplit('.').next().unwrap_or_default();
+    match extension.to_ascii_lowercase().as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "bmp" => "image/bmp",
+        "tif" | "tiff" => "image/tiff",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "emf" => "image/x-emf",
+        "wmf" => "image/x-wmf",
+        _ => "application/octet-stream",
+    }
+}
+
+/// browsers show every image type but tiff and the windows metafiles
+fn data_url(asset: &DocxAsset) -> Option<String> {
+    matches!(
+        asset.content_type,
+        "image/png" | "image/jpeg" | "image/gif" | "image/bmp" | "image/webp" | "image/svg+xml"
+    )
+    .then(|| {
+        format!(
+            "data:{};base64,{}",
+            asset.content_type,
+            STANDARD.encode(asset.bytes)
+        )
+    })
+}
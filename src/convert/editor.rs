@@ -0,0 +1,286 @@
+use std::fmt::Write;
+
+use super::{LineFormat, escape_html, line_text, split_block_embeds};
+use crate::{
+    AttributeMap, Attributor, Color, Delta, Embed, Font, Formats, Inline, Insert, Op, QuillOptions,
+    SanitizePolicy, Size,
+};
+
+impl Delta {
+    /// renders this document the way Quill 2 renders it inside `.ql-editor`: `ql-align-*`,
+    /// `ql-direction-rtl`, `ql-indent-*`, `ql-size-*` and `ql-font-*` classes, flat `<ol>` lists
+    /// with `data-list` items, `ql-code-block-container` divs...
+    ///
+    /// uses Quill's default attributors and url policy, see [`Delta::to_editor_html_with`].
+    ///
+    /// put the result in the editor container on the server, Quill reads it back when
+    /// [`QuillEditor::new_with_options`](crate::QuillEditor::new_with_options) mounts and the
+    /// content keeps its layout.
+    ///
+    /// ```
+    /// # use quill_wr::*;
+    /// let doc = delta!["a", "\n" { list: "bullet", indent: 1 }];
+    /// assert_eq!(
+    ///     doc.to_editor_html(),
+    ///     "<ol><li data-list=\"bullet\" class=\"ql-indent-1\">\
+    ///      <span class=\"ql-ui\" contenteditable=\"false\"></span>a</li></ol>"
+    /// );
+    /// ```
+    pub fn to_editor_html(&self) -> String {
+        self.to_editor_html_with(&QuillOptions::default())
+    }
+    /// [`Delta::to_editor_html`] with the size and font attributors and the url policy of the
+    /// editor that will mount over the markup
+    pub fn to_editor_html_with(&self, options: &QuillOptions) -> String {
+        let default_policy = SanitizePolicy::default();
+        let document = self.sanitized(options.sanitize_policy().unwrap_or(&default_policy));
+        let mut html = String::new();
+        // the container the previous line opened, Quill merges consecutive lines into it
+        let mut container: Option<&str> = None;
+        for line in document.lines() {
+            let (videos, content) = split_block_embeds(&line.content);
+            for op in videos {
+                close_container(&mut html, &mut container);
+                if let Op::Insert {
+                    insert: Insert::Embed(embed),
+                    attributes,
+                } = op
+                {
+                    write_embed(embed, attributes.as_ref(), &mut html);
+                }
+            }
+            let format = LineFormat::new(&line.blocks());
+            let mut inner = String::new();
+            if format.code.is_some() {
+                inner.push_str(&escape_html(&line_text(&content)));
+            } else {
+                write_inlines(&content, options, &mut inner);
+            }
+            if inner.is_empty() {
+                inner.push_str("<br>");
+            }
+            let classes = line_classes(&format);
+            if let Some(language) = &format.code {
+                open_container(
+                    &mut html,
+                    &mut container,
+                    "<div class=\"ql-code-block-container\" spellcheck=\"false\">",
+                );
+                let language = language
+                    .as_ref()
+                    .map(|language| format!(" data-language=\"{}\"", escape_html(language)))
+                    .unwrap_or_default();
+                let _ = write!(
+                    html,
+                    "<div class=\"ql-code-block\"{language}{classes}>{inner}</div>"
+                );
+            } else if let Some(kind) = format.list {
+                open_container(&mut html, &mut container, "<ol>");
+                let _ = write!(
+                    html,
+                    "<li data-list=\"{}\"{classes}><span class=\"ql-ui\" contenteditable=\"false\">\
+                     </span>{inner}</li>",
+                    kind.as_str()
+                );
+            } else {
+                close_container(&mut html, &mut container);
+                let tag = match (format.header, format.blockquote) {
+                    (Some(level), _) => format!("h{level}"),
+                    (None, true) => "blockquote".into(),
+                    (None, false) => "p".into(),
+                };
+                let _ = write!(html, "<{tag}{classes}>{inner}</{tag}>");
+            }
+        }
+        close_container(&mut html, &mut container);
+        if html.is_empty() {
+            html.push_str("<p><br></p>");
+        }
+        html
+    }
+}
+
+fn open_container<'a>(html: &mut String, container: &mut Option<&'a str>, open: &'a str) {
+    if *container != Some(open) {
+        close_container(html, container);
+        html.push_str(open);
+        *container = Some(open);
+    }
+}
+
+fn close_container(html: &mut String, container: &mut Option<&str>) {
+    match container.take() {
+        Some("<ol>") => html.push_str("</ol>"),
+        Some(_) => html.push_str("</div>"),
+        None => {}
+    }
+}
+
+/// ` class="ql-align-center ql-indent-1"`, in the order Quill adds the block attributors
+fn line_classes(format: &LineFormat) -> String {
+    let mut classes = vec![];
+    if let Some(align) = format.align {
+        classes.push(format!("ql-align-{}", align.as_str()));
+    }
+    if format.rtl {
+        classes.push("ql-direction-rtl".into());
+    }
+    if format.indent > 0 {
+        classes.push(format!("ql-indent-{}", format.indent));
+    }
+    if classes.is_empty() {
+        String::new()
+    } else {
+        format!(" class=\"{}\"", classes.join(" "))
+    }
+}
+
+fn write_inlines(content: &Delta, options: &QuillOptions, html: &mut String) {
+    for op in content.ops() {
+        let Op::Insert { insert, attributes } = op else {
+            continue;
+        };
+        let mut inner = String::new();
+        match insert {
+            Insert::Text(text) => inner.push_str(&escape_html(text)),
+            Insert::Embed(embed) => write_embed(embed, attributes.as_ref(), &mut inner),
+        }
+        match attributes {
+            Some(attributes) => write_formatted(attributes, options, &inner, html),
+            None => html.push_str(&inner),
+        }
+    }
+}
+
+/// wraps `inner` following Quill's `Inline.order`, outermost first: `code`, `sub`/`sup`,
+/// `strong`, `em`, `s`, `u`, `a`, then a `span` holding the class and style attributors
+fn write_formatted(
+    attributes: &AttributeMap,
+    options: &QuillOptions,
+    inner: &str,
+    html: &mut String,
+) {
+    let formats = Formats::from_kvs(attributes.clone()).inlines;
+    let mut tags = vec![];
+    let mut link = None;
+    for format in &formats {
+        match format {
+            Inline::Code => tags.push((0, "code")),
+            Inline::Sub => tags.push((1, "sub")),
+            Inline::Sup => tags.push((1, "sup")),
+            Inline::Bold => tags.push((2, "strong")),
+            Inline::Italic => tags.push((3, "em")),
+            Inline::Strike => tags.push((4, "s")),
+            Inline::Underline => tags.push((5, "u")),
+            Inline::Link(url) => link = Some(url),
+            _ => {}
+        }
+    }
+    tags.sort();
+
+    // Quill only takes whitelisted sizes and fonts and the browser drops invalid styles,
+    // anything else would be gone once the editor mounts
+    let whitelisted = |value: &str, whitelist: &[String]| whitelist.iter().any(|v| v == value);
+    let sizes: Vec<String> = options.sizes().iter().map(Size::to_string).collect();
+    let fonts: Vec<String> = options.fonts().iter().map(Font::to_string).collect();
+    let (size, font) = (options.size_attributor(), options.font_attributor());
+    let mut classes = vec![];
+    let mut styles = vec![];
+    for (name, value) in attributes {
+        let Some(value) = value.as_str() else {
+            continue;
+        };
+        let valid = match name.as_str() {
+            "background" | "color" => Color::parse(value).is_some(),
+            "size" => whitelisted(value, &sizes),
+            "font" => whitelisted(value, &fonts),
+            _ => false,
+        };
+        if !valid {
+            continue;
+        }
+        match (name.as_str(), size, font) {
+            ("background", ..) => styles.push(format!("background-color: {value};")),
+            ("color", ..) => styles.push(format!("color: {value};")),
+            ("font", _, Attributor::Class) => classes.push(format!("ql-font-{value}")),
+            ("font", _, Attributor::Style) => styles.push(format!("font-family: {value};")),
+            ("size", Attributor::Class, _) => classes.push(format!("ql-size-{value}")),
+            ("size", Attributor::Style, _) => styles.push(format!("font-size: {value};")),
+            _ => {}
+        }
+    }
+
+    for (_, tag) in &tags {
+        let _ = write!(html, "<{tag}>");
+    }
+    if let Some(url) = link {
+        let _ = write!(
+            html,
+            "<a href=\"{}\" rel=\"noopener noreferrer\" target=\"_blank\">",
+            escape_html(url)
+        );
+    }
+    let span = !classes.is_empty() || !styles.is_empty();
+    if span {
+        html.push_str("<span");
+        if !classes.is_empty() {
+            let _ = write!(html, " class=\"{}\"", escape_html(&classes.join(" ")));
+        }
+        if !styles.is_empty() {
+            let _ = write!(html, " style=\"{}\"", escape_html(&styles.join(" ")));
+        }
+        html.push('>');
+    }
+    html.push_str(inner);
+    if span {
+        html.push_str("</span>");
+    }
+    if link.is_some() {
+        html.push_str("</a>");
+    }
+    for (_, tag) in tags.iter().rev() {
+        let _ = write!(html, "</{tag}>");
+    }
+}
+
+/// the markup of Quill's image, video and formula blots
+fn write_embed(embed: &Embed, attributes: Option<&AttributeMap>, html: &mut String) {
+    let attribute = |name: &str| {
+        attributes
+            .and_then(|attributes| attributes.get(name))
+            .and_then(|value| value.as_str())
+            .map(|value| format!(" {name}=\"{}\"", escape_html(value)))
+            .unwrap_or_default()
+    };
+    let value = escape_html(embed.value.as_str().unwrap_or_default());
+    match embed.kind.as_str() {
+        "image" => {
+            let _ = write!(
+                html,
+                "<img src=\"{value}\"{}{}{}>",
+                attribute("alt"),
+                attribute("height"),
+                attribute("width")
+            );
+        }
+        "video" => {
+            let _ = write!(
+                html,
+                "<iframe class=\"ql-video\" frameborder=\"0\" allowfullscreen=\"true\" \
+                 src=\"{value}\"{}{}></iframe>",
+                attribute("height"),
+                attribute("width")
+            );
+        }
+        // Quill's embeds keep their content between two zero width guards, KaTeX replaces
+        // the raw formula once mounted
+        "formula" => {
+            let _ = write!(
+                html,
+                "<span class=\"ql-formula\" data-value=\"{value}\">\u{feff}\
+                 <span contenteditable=\"false\">{value}</span>\u{feff}</span>"
+            );
+        }
+        _ => {}
+    }
+}
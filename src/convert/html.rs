@@ -0,0 +1,300 @@
+use std::fmt::Write;
+
+use super::{LineFormat, escape_html, line_text, split_block_embeds};
+use crate::{
+    AttributeMap, Delta, Embed, Font, Formats, Inline, Insert, Line, ListKind, Op, SanitizePolicy,
+    Size,
+};
+
+impl Delta {
+    /// renders this document as semantic html, the native counterpart of Quill's
+    /// `getSemanticHTML`: `p`, `h1`-`h6`, nested `ol`/`ul`, `blockquote`, `pre`, `strong`, `em`,
+    /// `a`, `img`, `iframe`... with inline styles instead of Quill's css classes.
+    ///
+    /// urls go through [`SanitizePolicy::default`], see [`Delta::to_semantic_html_with`].
+    ///
+    /// ```
+    /// # use quill_wr::*;
+    /// let doc = delta!["Title", "\n" { header: 1 }, "a " , "b" { bold: true }, "\n"];
+    /// assert_eq!(doc.to_semantic_html(), "<h1>Title</h1><p>a <strong>b</strong></p>");
+    /// ```
+    pub fn to_semantic_html(&self) -> String {
+        self.to_semantic_html_with(&SanitizePolicy::default())
+    }
+    pub fn to_semantic_html_with(&self, policy: &SanitizePolicy) -> String {
+        let document = self.sanitized(policy);
+        let lines: Vec<Line> = document.lines().collect();
+        let mut html = String::new();
+        let mut lists = ListStack::default();
+        let mut i = 0;
+        while i < lines.len() {
+            let content = write_block_embeds(&lines[i].content, &mut html, &mut lists);
+            let format = LineFormat::new(&lines[i].blocks());
+            if format.list.is_none() {
+                lists.close_all(&mut html);
+            }
+            if format.code.is_some() {
+                // consecutive lines of the same code block share one <pre>
+                let language = format.code_language();
+                let mut code = vec![line_text(&content)];
+                while let Some(next) = lines.get(i + 1) {
+                    let next_format = LineFormat::new(&next.blocks());
+                    if next_format.code.is_none() || next_format.code_language() != language {
+                        break;
+                    }
+                    code.push(line_text(&next.content));
+                    i += 1;
+                }
+                let class = language
+                    .map(|language| format!(" class=\"language-{}\"", escape_html(language)))
+                    .unwrap_or_default();
+                let _ = write!(
+                    html,
+                    "<pre><code{class}>{}</code></pre>",
+                    escape_html(&code.join("\n"))
+                );
+                i += 1;
+                continue;
+            }
+            let mut inner = String::new();
+            write_inlines(&content, &mut inner);
+            if inner.is_empty() {
+                inner.push_str("<br>");
+            }
+            match format.list {
+                Some(kind) => {
+                    let mut attributes = block_attributes(&format, false);
+                    if matches!(kind, ListKind::Checked | ListKind::Unchecked) {
+                        let _ = write!(attributes, " data-list=\"{}\"", kind.as_str());
+                    }
+                    lists.open_item(&mut html, kind, format.indent, &attributes);
+                    html.push_str(&inner);
+                }
+                None => {
+                    let tag = match (format.header, format.blockquote) {
+                        (Some(level), _) => format!("h{level}"),
+                        (None, true) => "blockquote".into(),
+                        (None, false) => "p".into(),
+                    };
+                    let attributes = block_attributes(&format, true);
+                    let _ = write!(html, "<{tag}{attributes}>{inner}</{tag}>");
+                }
+            }
+            i += 1;
+        }
+        lists.close_all(&mut html);
+        html
+    }
+}
+
+/// open `<ol>`/`<ul>` elements, each with an unclosed `<li>`
+#[derive(Default)]
+struct ListStack {
+    open: Vec<(&'static str, u8)>,
+}
+
+impl ListStack {
+    fn open_item(&mut self, html: &mut String, kind: ListKind, indent: u8, attributes: &str) {
+        let tag = match kind {
+            ListKind::Ordered => "ol",
+            _ => "ul",
+        };
+        while self.open.last().is_some_and(|&(_, level)| level > indent) {
+            self.close_one(html);
+        }
+        match self.open.last() {
+            Some(&(open, level)) if level == indent && open == tag => html.push_str("</li>"),
+            Some(&(_, level)) if level == indent => {
+                self.close_one(html);
+                self.push(html, tag, indent);
+            }
+            // a deeper item nests inside the still open <li>
+            _ => self.push(html, tag, indent),
+        }
+        let _ = write!(html, "<li{attributes}>");
+    }
+    fn push(&mut self, html: &mut String, tag: &'static str, indent: u8) {
+        let _ = write!(html, "<{tag}>");
+        self.open.push((tag, indent));
+    }
+    fn close_one(&mut self, html: &mut String) {
+        if let Some((tag, _)) = self.open.pop() {
+            let _ = write!(html, "</li></{tag}>");
+        }
+    }
+    fn close_all(&mut self, html: &mut String) {
+        while !self.open.is_empty() {
+            self.close_one(html);
+        }
+    }
+}
+
+/// ` style="..." dir="rtl"` for a line, `indent` only applies outside of lists
+fn block_attributes(format: &LineFormat, indent: bool) -> String {
+    let mut styles = vec![];
+    if let Some(align) = format.align {
+        styles.push(format!("text-align: {}", align.as_str()));
+    }
+    if indent && format.indent > 0 {
+        styles.push(format!("padding-left: {}em", u32::from(format.indent) * 3));
+    }
+    let mut attributes = String::new();
+    if !styles.is_empty() {
+        let _ = write!(attributes, " style=\"{}\"", styles.join("; "));
+    }
+    if format.rtl {
+        attributes.push_str(" dir=\"rtl\"");
+    }
+    attributes
+}
+
+/// writes the videos (block embeds) a line starts with and returns the rest of the line
+fn write_block_embeds(content: &Delta, html: &mut String, lists: &mut ListStack) -> Delta {
+    let (videos, rest) = split_block_embeds(content);
+    if !videos.is_empty() {
+        lists.close_all(html);
+    }
+    for op in videos {
+        if let Op::Insert {
+            insert: Insert::Embed(embed),
+            attributes,
+        } = op
+        {
+            write_embed(embed, attributes.as_ref(), html);
+        }
+    }
+    rest
+}
+
+fn write_inlines(content: &Delta, html: &mut String) {
+    for op in content.ops() {
+        let Op::Insert { insert, attributes } = op else {
+            continue;
+        };
+        let formats = Formats::from_kvs(attributes.clone().unwrap_or_default()).inlines;
+        let mut inner = String::new();
+        match insert {
+            Insert::Text(text) => inner.push_str(&escape_html(text)),
+            Insert::Embed(embed) => write_embed(embed, attributes.as_ref(), &mut inner),
+        }
+        write_formatted(&formats, &inner, html);
+    }
+}
+
+/// wraps `inner` in the tags of `formats`, outermost first: `a`, styled `span`, `u`, `s`,
+/// `em`, `strong`, `sub`/`sup`, `code`
+fn write_formatted(formats: &[Inline], inner: &str, html: &mut String) {
+    let mut link = None;
+    let mut styles = vec![];
+    let mut tags = vec![];
+    for format in formats {
+        match format {
+            Inline::Link(url) => link = Some(url),
+            Inline::Color(color) => styles.push(format!("color: {color}")),
+            Inline::BgColor(color) => styles.push(format!("background-color: {color}")),
+            Inline::Font(font) => styles.push(format!("font-family: {}", font_family(font))),
+            Inline::Size(size) => styles.push(format!("font-size: {}", font_size(size))),
+            Inline::Underline => tags.push((0, "u")),
+            Inline::Strike => tags.push((1, "s")),
+            Inline::Italic => tags.push((2, "em")),
+            Inline::Bold => tags.push((3, "strong")),
+            Inline::Sub => tags.push((4, "sub")),
+            Inline::Sup => tags.push((4, "sup")),
+            Inline::Code => tags.push((5, "code")),
+            _ => {}
+        }
+    }
+    tags.sort();
+    if let Some(url) = link {
+        let _ = write!(
+            html,
+            "<a href=\"{}\" rel=\"noopener noreferrer\" target=\"_blank\">",
+            escape_html(url)
+        );
+    }
+    if !styles.is_empty() {
+        let _ = write!(html, "<span style=\"{}\">", escape_html(&styles.join("; ")));
+    }
+    for (_, tag) in &tags {
+        let _ = write!(html, "<{tag}>");
+    }
+    html.push_str(inner);
+    for (_, tag) in tags.iter().rev() {
+        let _ = write!(html, "</{tag}>");
+    }
+    if !styles.is_empty() {
+        html.push_str("</span>");
+    }
+    if link.is_some() {
+        html.push_str("</a>");
+    }
+}
+
+/// the css Quill's snow theme uses for its named fonts
+pub(crate) fn font_family(font: &Font) -> String {
+    match font {
+        Font::Serif => "Georgia, Times New Roman, serif".into(),
+        Font::Monospace => "Monaco, Courier New, monospace".into(),
+        Font::Custom(family) => css_value(family),
+    }
+}
+
+/// the css Quill's snow theme uses for its named sizes
+pub(crate) fn font_size(size: &Size) -> String {
+    match size {
+        Size::Small => "0.75em".into(),
+        Size::Large => "1.5em".into(),
+        Size::Huge => "2.5em".into(),
+        size => css_value(&size.to_string()),
+    }
+}
+
+/// `value` without the characters that could end its declaration or open a block, a function
+/// or a string in a `style` attribute
+fn css_value(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !matches!(c, ';' | '{' | '}' | '(' | ')' | '"' | '\'' | '\\'))
+        .collect()
+}
+
+fn write_embed(embed: &Embed, attributes: Option<&AttributeMap>, html: &mut String) {
+    let attribute = |name: &str| {
+        attributes
+            .and_then(|attributes| attributes.get(name))
+            .and_then(|value| value.as_str())
+            .map(|value| format!(" {name}=\"{}\"", escape_html(value)))
+            .unwrap_or_default()
+    };
+    let value = embed.value.as_str().unwrap_or_default();
+    match embed.kind.as_str() {
+        "image" => {
+            let _ = write!(
+                html,
+                "<img src=\"{}\"{}{}{}>",
+                escape_html(value),
+                attribute("alt"),
+                attribute("width"),
+                attribute("height")
+            );
+        }
+        "video" => {
+            let _ = write!(
+                html,
+                "<iframe src=\"{}\" frameborder=\"0\" allowfullscreen=\"true\"{}{}></iframe>",
+                escape_html(value),
+                attribute("width"),
+                attribute("height")
+            );
+        }
+        "formula" => {
+            let _ = write!(
+                html,
+                "<span class=\"ql-formula\" data-value=\"{0}\">{0}</span>",
+                escape_html(value)
+            );
+        }
+        // custom embeds have no known markup
+        _ => {}
+    }
+}
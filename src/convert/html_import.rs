@@ -0,0 +1,689 @@
+use ego_tree::NodeRef;
+use scraper::{Html, Node, node::Element};
+use serde_json::{Value, json};
+use std::{collections::HashMap, fmt::Display};
+
+use crate::{AttributeMap, Color, Delta, Embed, Insert, Op, SanitizePolicy, Schema};
+
+/// the elements Quill's clipboard turns into lines
+const LINE_TAGS: [&str; 34] = [
+    "address",
+    "article",
+    "blockquote",
+    "canvas",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "fieldset",
+    "figcaption",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "iframe",
+    "li",
+    "main",
+    "nav",
+    "ol",
+    "output",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "td",
+    "th",
+    "tr",
+    "ul",
+];
+
+/// the other elements the importer understands
+const INLINE_TAGS: [&str; 22] = [
+    "html", "body", "thead", "tbody", "tfoot", "a", "b", "strong", "i", "em", "u", "s", "strike",
+    "sub", "sup", "code", "span", "br", "img", "video", "font", "o:p",
+];
+
+/// elements without content, always dropped
+const IGNORED_TAGS: [&str; 8] = [
+    "head", "title", "meta", "link", "style", "script", "template", "noscript",
+];
+
+/// what [`Delta::from_html_with`] does with an element.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TagHandling {
+    /// keeps the content, the tag itself adds no format. what Quill's clipboard does with
+    /// unknown tags
+    #[default]
+    Unwrap,
+    /// drops the element and its content
+    Drop,
+    /// fails the import with [`HtmlImportError::UnknownTag`]
+    Reject,
+}
+
+/// why [`Delta::from_html_with`] failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HtmlImportError {
+    /// an element handled with [`TagHandling::Reject`]
+    UnknownTag(String),
+}
+
+impl Display for HtmlImportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownTag(tag) => write!(f, "unknown tag <{tag}>"),
+        }
+    }
+}
+
+impl std::error::Error for HtmlImportError {}
+
+/// how html is turned into a delta, see [`Delta::from_html_with`].
+///
+/// the default keeps the formats of [`Schema::default`], sanitizes urls with
+/// [`SanitizePolicy::default`] and unwraps unknown tags like Quill's clipboard.
+#[derive(Clone, Debug)]
+pub struct HtmlImport {
+    schema: Schema,
+    sanitize: SanitizePolicy,
+    unknown_tags: TagHandling,
+    tags: HashMap<String, TagHandling>,
+}
+
+impl HtmlImport {
+    pub fn builder() -> HtmlImportBuilder {
+        HtmlImportBuilder::new()
+    }
+}
+
+impl Default for HtmlImport {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+pub struct HtmlImportBuilder {
+    import: HtmlImport,
+}
+
+impl HtmlImportBuilder {
+    pub fn new() -> Self {
+        Self {
+            import: HtmlImport {
+                schema: Schema::default(),
+                sanitize: SanitizePolicy::default(),
+                unknown_tags: TagHandling::Unwrap,
+                tags: HashMap::new(),
+            },
+        }
+    }
+    /// formats, line formats and embeds the schema rejects are left out
+    pub fn schema(mut self, schema: Schema) -> Self {
+        self.import.schema = schema;
+        self
+    }
+    pub fn sanitize(mut self, policy: SanitizePolicy) -> Self {
+        self.import.sanitize = policy;
+        self
+    }
+    /// what happens to the elements the importer does not know, e.g. `<article>` children
+    /// are kept but `<marquee>` has no meaning
+    pub fn unknown_tags(mut self, handling: TagHandling) -> Self {
+        self.import.unknown_tags = handling;
+        self
+    }
+    /// overrides the handling of one tag, known or not: `.tag("a", TagHandling::Unwrap)`
+    /// strips links, `.tag("table", TagHandling::Drop)` leaves tables out
+    pub fn tag(mut self, tag: impl Into<String>, handling: TagHandling) -> Self {
+        self.import
+            .tags
+            .insert(tag.into().to_ascii_lowercase(), handling);
+        self
+    }
+    pub fn build(self) -> HtmlImport {
+        self.import
+    }
+}
+
+impl Default for HtmlImportBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Delta {
+    /// converts html to a document following the matchers of Quill's clipboard: block
+    /// elements become lines, `<b>`, `<em>`, `<a>`... and `style`/`ql-*` attributes become
+    /// formats, nested lists get an `indent`, table cells become lines.
+    ///
+    /// uses [`HtmlImport::default`], see [`Delta::from_html_with`].
+    ///
+    /// ```
+    /// # use quill_wr::*;
+    /// let doc = Delta::from_html("<h1>Title</h1><p>a <b>b</b></p>");
+    /// assert_eq!(doc, delta!["Title", "\n" { header: 1 }, "a ", "b" { bold: true }, "\n"]);
+    /// ```
+    pub fn from_html(html: &str) -> Delta {
+        Self::from_html_with(html, &HtmlImport::default()).unwrap_or_default()
+    }
+    pub fn from_html_with(html: &str, import: &HtmlImport) -> Result<Delta, HtmlImportError> {
+        let document = Html::parse_document(html);
+        let mut importer = Importer {
+            import,
+            error: None,
+        };
+        let mut delta = importer.traverse(document.tree.root());
+        if let Some(error) = importer.error {
+            return Err(error);
+        }
+        if !ends_with_newline(&delta) {
+            delta.insert("\n", AttributeMap::new());
+        }
+        delta.sanitize(&import.sanitize);
+        Ok(delta)
+    }
+}
+
+struct Importer<'a> {
+    import: &'a HtmlImport,
+    error: Option<HtmlImportError>,
+}
+
+impl Importer<'_> {
+    /// walks the tree with a stack of the open elements rather than recursing, deeply nested
+    /// html would overflow the call stack. the content of an element is pushed into one delta
+    /// that its own formats are then applied to
+    fn traverse(&mut self, root: NodeRef<'_, Node>) -> Delta {
+        let mut open = vec![(root, root.children(), Delta::new())];
+        while let Some((_, children, _)) = open.last_mut() {
+            let delta = match children.next() {
+                Some(child) => match self.enter(child) {
+                    Some(delta) => delta,
+                    None => {
+                        open.push((child, child.children(), Delta::new()));
+                        continue;
+                    }
+                },
+                None => {
+                    let Some((node, _, delta)) = open.pop() else {
+                        break;
+                    };
+                    self.leave(node, delta)
+                }
+            };
+            match open.last_mut() {
+                Some((_, _, content)) => {
+                    for op in delta.into_ops() {
+                        content.push(op);
+                    }
+                }
+                None => return delta,
+            }
+        }
+        Delta::new()
+    }
+    /// the delta of a node without content to walk, `None` for the ones to open
+    fn enter(&mut self, node: NodeRef<'_, Node>) -> Option<Delta> {
+        match node.value() {
+            Node::Text(text) => {
+                let delta = match_text(node, text);
+                Some(match_newline(node, delta))
+            }
+            Node::Element(element) => match self.handling(element) {
+                Some(TagHandling::Drop) => Some(Delta::new()),
+                Some(TagHandling::Reject) => {
+                    self.error
+                        .get_or_insert_with(|| HtmlImportError::UnknownTag(element.name().into()));
+                    Some(Delta::new())
+                }
+                _ => None,
+            },
+            Node::Document | Node::Fragment => None,
+            _ => Some(Delta::new()),
+        }
+    }
+    /// applies the formats of an opened node to its content
+    fn leave(&self, node: NodeRef<'_, Node>, delta: Delta) -> Delta {
+        let Node::Element(element) = node.value() else {
+            return delta;
+        };
+        let handling = self.handling(element);
+        let mut delta = match_newline(node, delta);
+        if handling.is_none() {
+            delta = self.match_blot(node, element, delta);
+        }
+        delta = self.match_attributor(element, delta);
+        delta = self.match_styles(element, delta);
+        if handling.is_none() && element.name() == "li" {
+            delta = self.match_indent(node, delta);
+        }
+        delta
+    }
+    /// `None` for the known tags, matched in full
+    fn handling(&self, element: &Element) -> Option<TagHandling> {
+        let tag = element.name();
+        if let Some(handling) = self.import.tags.get(tag) {
+            return Some(*handling);
+        }
+        if IGNORED_TAGS.contains(&tag) {
+            Some(TagHandling::Drop)
+        } else if LINE_TAGS.contains(&tag) || INLINE_TAGS.contains(&tag) {
+            None
+        } else {
+            Some(self.import.unknown_tags)
+        }
+    }
+
+    /// the format or embed of the element itself
+    fn match_blot(&self, node: NodeRef<'_, Node>, element: &Element, delta: Delta) -> Delta {
+        let has_class = |class: &str| element.classes().any(|c| c == class);
+        match element.name() {
+            tag @ ("h1" | "h2" | "h3" | "h4" | "h5" | "h6") => {
+                self.apply(delta, "header", json!(tag[1..].parse::<u8>().unwrap_or(1)))
+            }
+            "blockquote" => self.apply(delta, "blockquote", json!(true)),
+            "li" => match element.attr("data-list") {
+                Some(list) => self.apply(delta, "list", json!(list)),
+                None => delta,
+            },
+            "ol" | "ul" => {
+                let list = match element.attr("data-checked") {
+                    Some("true") => "checked",
+                    Some(_) => "unchecked",
+                    None if element.name() == "ol" => "ordered",
+                    None => "bullet",
+                };
+                self.apply(delta, "list", json!(list))
+            }
+            "pre" => self.apply(delta, "code-block", code_language(node, element)),
+            "div" if has_class("ql-code-block") => {
+                self.apply(delta, "code-block", code_language(node, element))
+            }
+            "a" => match element.attr("href") {
+                Some(href) => self.apply(delta, "link", json!(href)),
+                None => delta,
+            },
+            "b" | "strong" => self.apply(delta, "bold", json!(true)),
+            "i" | "em" => self.apply(delta, "italic", json!(true)),
+            "u" => self.apply(delta, "underline", json!(true)),
+            "s" | "strike" => self.apply(delta, "strike", json!(true)),
+            "sub" => self.apply(delta, "script", json!("sub")),
+            "sup" => self.apply(delta, "script", json!("super")),
+            "code" if !is_pre(node) => self.apply(delta, "code", json!(true)),
+            "img" => self.embed(element, "image", element.attr("src")),
+            "iframe" | "video" => self.embed(element, "video", element.attr("src")),
+            "span" if has_class("ql-formula") => {
+                self.embed(element, "formula", element.attr("data-value"))
+            }
+            "br" if !ends_with_newline(&delta) => {
+                let mut delta = delta;
+                delta.insert("\n", AttributeMap::new());
+                delta
+            }
+            "o:p" => delta
+                .ops()
+                .iter()
+                .filter_map(|op| op.as_insert()?.as_text())
+                .map(str::trim)
+                .filter(|text| !text.is_empty())
+                .fold(Delta::new(), |mut delta, text| {
+                    delta.insert(text, AttributeMap::new());
+                    delta
+                }),
+            _ => delta,
+        }
+    }
+
+    fn embed(&self, element: &Element, kind: &str, value: Option<&str>) -> Delta {
+        let schema = &self.import.schema;
+        let mut delta = Delta::new();
+        let Some(value) = value.map(Value::from) else {
+            return delta;
+        };
+        if !schema.accepts_embed(kind, &value) {
+            return delta;
+        }
+        let mut attributes = AttributeMap::new();
+        for name in ["alt", "height", "width"] {
+            if let Some(attribute) = element.attr(name).map(Value::from)
+                && schema.accepts_embed_attr(name, &attribute)
+            {
+                attributes.insert(name.into(), attribute);
+            }
+        }
+        delta.insert(Embed::new(kind, value), attributes);
+        delta
+    }
+
+    /// Quill's `align`/`dir` attributes, `ql-*` classes and style attributors
+    fn match_attributor(&self, element: &Element, delta: Delta) -> Delta {
+        let mut formats = vec![];
+        if let Some(align) = element.attr("align") {
+            formats.push(("align", json!(align.to_ascii_lowercase())));
+        }
+        if let Some(direction) = element.attr("dir") {
+            formats.push(("direction", json!(direction.to_ascii_lowercase())));
+        }
+        for class in element.classes() {
+            let Some((name, value)) = class
+                .strip_prefix("ql-")
+                .and_then(|class| class.split_once('-'))
+            else {
+                continue;
+            };
+            match name {
+                "align" | "direction" | "size" | "font" | "color" => {
+                    formats.push((name, json!(value)))
+                }
+                "bg" => formats.push(("background", json!(value))),
+                "indent" => {
+                    if let Ok(indent) = value.parse::<u8>() {
+                        formats.push(("indent", json!(indent)));
+                    }
+                }
+                _ => {}
+            }
+        }
+        for (property, value) in styles(element) {
+            let format = match property.as_str() {
+                "text-align" => ("align", json!(value)),
+                "direction" => ("direction", json!(value)),
+                "color" => ("color", json!(css_color(&value))),
+                "background-color" => ("background", json!(css_color(&value))),
+                "font-family" => ("font", json!(value.replace(['"', '\''], ""))),
+                "font-size" => ("size", json!(value)),
+                _ => continue,
+            };
+            formats.push(format);
+        }
+        // the last declaration wins, like in Quill
+        let mut applied: Vec<&str> = vec![];
+        formats
+            .into_iter()
+            .rev()
+            .fold(delta, |delta, (name, value)| {
+                if applied.contains(&name) {
+                    return delta;
+                }
+                applied.push(name);
+                self.apply(delta, name, value)
+            })
+    }
+
+    /// the bold, italic, underline, strike and indent inline styles of pasted content
+    fn match_styles(&self, element: &Element, delta: Delta) -> Delta {
+        let mut delta = delta;
+        let mut text_indent = false;
+        for (property, value) in styles(element) {
+            match property.as_str() {
+                "font-style" if value == "italic" => {
+                    delta = self.apply(delta, "italic", json!(true));
+                }
+                "text-decoration" | "text-decoration-line" => {
+                    if value.contains("underline") {
+                        delta = self.apply(delta, "underline", json!(true));
+                    }
+                    if value.contains("line-through") {
+                        delta = self.apply(delta, "strike", json!(true));
+                    }
+                }
+                "font-weight"
+                    if value.starts_with("bold")
+                        || value.parse::<u32>().is_ok_and(|weight| weight >= 700) =>
+                {
+                    delta = self.apply(delta, "bold", json!(true));
+                }
+                "text-indent" => text_indent = leading_number(&value) > 0.0,
+                _ => {}
+            }
+        }
+        if text_indent {
+            let mut indented = Delta::new();
+            indented.insert("\t", AttributeMap::new());
+            for op in delta.into_ops() {
+                indented.push(op);
+            }
+            return indented;
+        }
+        delta
+    }
+
+    /// nested list items get the `indent` of their depth
+    fn match_indent(&self, node: NodeRef<'_, Node>, delta: Delta) -> Delta {
+        if !ends_with_newline(&delta) {
+            return delta;
+        }
+        let depth = node
+            .ancestors()
+            .filter(|ancestor| {
+                ancestor
+                    .value()
+                    .as_element()
+                    .is_some_and(|element| matches!(element.name(), "ol" | "ul"))
+            })
+            .count();
+        if depth <= 1 {
+            return delta;
+        }
+        self.apply(delta, "indent", json!(depth - 1))
+    }
+
+    /// Quill's `applyFormat`: sets the format on the ops not having it yet, line formats on
+    /// the newlines only and inline formats on the rest. formats the schema rejects are skipped
+    fn apply(&self, delta: Delta, name: &str, value: Value) -> Delta {
+        let schema = &self.import.schema;
+        let block = schema.accepts_block(name, &value);
+        if !block && !schema.accepts_inline(name, &value) {
+            return delta;
+        }
+        let mut applied = Delta::new();
+        for op in delta.into_ops() {
+            let Op::Insert { insert, attributes } = op else {
+                continue;
+            };
+            let attributes = attributes.unwrap_or_default();
+            let with_format = |mut attributes: AttributeMap| {
+                attributes
+                    .entry(name.to_string())
+                    .or_insert_with(|| value.clone());
+                attributes
+            };
+            match insert {
+                Insert::Text(text) => {
+                    for segment in split_newlines(&text) {
+                        let attributes = if (segment == "\n") == block {
+                            with_format(attributes.clone())
+                        } else {
+                            attributes.clone()
+                        };
+                        applied.insert(segment, attributes);
+                    }
+                }
+                Insert::Embed(embed) if !block => {
+                    applied.insert(embed, with_format(attributes));
+                }
+                Insert::Embed(embed) => {
+                    applied.insert(embed, attributes);
+                }
+            }
+        }
+        applied
+    }
+}
+
+/// Quill's `matchText`: collapses whitespace outside of `<pre>`
+fn match_text(node: NodeRef<'_, Node>, text: &str) -> Delta {
+    let mut delta = Delta::new();
+    if is_pre(node) {
+        if !text.is_empty() {
+            delta.insert(text, AttributeMap::new());
+        }
+        return delta;
+    }
+    if text.trim().is_empty() && text.contains('\n') && !is_between_inline_elements(node) {
+        return delta;
+    }
+    let mut collapsed = String::with_capacity(text.len());
+    for c in text.chars() {
+        let c = if c.is_whitespace() && c != '\u{a0}' {
+            ' '
+        } else {
+            c
+        };
+        if !(c == ' ' && collapsed.ends_with(' ')) {
+            collapsed.push(c);
+        }
+    }
+    let parent_is_line = |sibling: Option<NodeRef<'_, Node>>| match sibling {
+        None => node.parent().is_some_and(is_line),
+        Some(sibling) => sibling.value().is_element() && is_line(sibling),
+    };
+    let mut text = collapsed.as_str();
+    if parent_is_line(node.prev_sibling()) {
+        text = text.strip_prefix(' ').unwrap_or(text);
+    }
+    if parent_is_line(node.next_sibling()) {
+        text = text.strip_suffix(' ').unwrap_or(text);
+    }
+    let text = text.replace('\u{a0}', " ");
+    if !text.is_empty() {
+        delta.insert(text, AttributeMap::new());
+    }
+    delta
+}
+
+/// Quill's `matchNewline`: ends the content of lines, and of inline content followed by a line
+fn match_newline(node: NodeRef<'_, Node>, delta: Delta) -> Delta {
+    if ends_with_newline(&delta) {
+        return delta;
+    }
+    let paragraph = node
+        .value()
+        .as_element()
+        .is_some_and(|element| element.name() == "p");
+    let mut ends_line = is_line(node) && (node.has_children() || paragraph);
+    if !ends_line && !delta.ops().is_empty() {
+        let mut next = node.next_sibling();
+        while let Some(sibling) = next {
+            if is_line(sibling) {
+                ends_line = true;
+                break;
+            }
+            next = sibling.first_child();
+        }
+    }
+    let mut delta = delta;
+    if ends_line {
+        delta.insert("\n", AttributeMap::new());
+    }
+    delta
+}
+
+fn is_line(node: NodeRef<'_, Node>) -> bool {
+    node.value()
+        .as_element()
+        .is_some_and(|element| LINE_TAGS.contains(&element.name()))
+}
+
+/// inside a `<pre>` or a Quill 2 code block line, where whitespace is kept
+fn is_pre(node: NodeRef<'_, Node>) -> bool {
+    node.ancestors().any(|ancestor| {
+        ancestor.value().as_element().is_some_and(|element| {
+            element.name() == "pre" || element.classes().any(|class| class == "ql-code-block")
+        })
+    })
+}
+
+fn is_between_inline_elements(node: NodeRef<'_, Node>) -> bool {
+    let previous = node
+        .prev_siblings()
+        .find(|sibling| sibling.value().is_element());
+    let next = node
+        .next_siblings()
+        .find(|sibling| sibling.value().is_element());
+    match (previous, next) {
+        (Some(previous), Some(next)) => !is_line(previous) && !is_line(next),
+        _ => false,
+    }
+}
+
+fn ends_with_newline(delta: &Delta) -> bool {
+    matches!(
+        delta.ops().last(),
+        Some(Op::Insert { insert: Insert::Text(text), .. }) if text.ends_with('\n')
+    )
+}
+
+/// `"a\nb\n"` → `["a", "\n", "b", "\n"]`
+fn split_newlines(text: &str) -> Vec<&str> {
+    let mut segments = vec![];
+    for line in text.split_inclusive('\n') {
+        match line.strip_suffix('\n') {
+            Some(content) => {
+                if !content.is_empty() {
+                    segments.push(content);
+                }
+                segments.push("\n");
+            }
+            None => segments.push(line),
+        }
+    }
+    segments
+}
+
+/// the `data-language` of a Quill code block or the `language-*` class of `<pre>` and
+/// `<pre><code>`, `true` when there is none
+fn code_language(node: NodeRef<'_, Node>, element: &Element) -> Value {
+    if let Some(language) = element.attr("data-language") {
+        return json!(language);
+    }
+    let code = node
+        .children()
+        .filter_map(|child| child.value().as_element())
+        .find(|child| child.name() == "code");
+    [Some(element), code]
+        .into_iter()
+        .flatten()
+        .flat_map(|element| element.classes())
+        .find_map(|class| class.strip_prefix("language-"))
+        .map_or(json!(true), |language| json!(language))
+}
+
+/// the declarations of the `style` attribute, lowercase property names
+fn styles(element: &Element) -> Vec<(String, String)> {
+    element
+        .attr("style")
+        .unwrap_or_default()
+        .split(';')
+        .filter_map(|declaration| declaration.split_once(':'))
+        .map(|(property, value)| {
+            (
+                property.trim().to_ascii_lowercase(),
+                value.trim().to_string(),
+            )
+        })
+        .collect()
+}
+
+/// `rgb(...)` colors become hex like Quill's color attributors do
+fn css_color(value: &str) -> String {
+    if value.starts_with("rgb")
+        && let Some(color) = Color::parse(value)
+    {
+        return color.to_string();
+    }
+    value.to_string()
+}
+
+fn leading_number(value: &str) -> f64 {
+    let end = value
+        .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | '-' | '+')))
+        .unwrap_or(value.len());
+    value[..end].parse().unwrap_or(0.0)
+}
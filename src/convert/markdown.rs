@@ -0,0 +1,441 @@
+use std::fmt::Write;
+
+use super::{LineFormat, escape_html, font_family, font_size, line_text, split_block_embeds};
+use crate::{AttributeMap, Delta, Embeds, Formats, Inline, Insert, ListKind, SanitizePolicy};
+
+/// what [`Delta::to_markdown_with`] does with formats markdown has no syntax for: underline,
+/// colors, fonts, sizes, sub/superscript, alignment, videos and image sizes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UnsupportedFormats {
+    /// keeps the text, loses the format
+    #[default]
+    Drop,
+    /// writes inline html (`<u>`, `<span style="...">`, `<img width="...">`...), rendered by
+    /// GitHub and most markdown renderers
+    Html,
+}
+
+/// how a delta is written as markdown, see [`Delta::to_markdown_with`].
+#[derive(Clone, Debug, Default)]
+pub struct MarkdownExport {
+    unsupported: UnsupportedFormats,
+    sanitize: SanitizePolicy,
+}
+
+impl MarkdownExport {
+    pub fn builder() -> MarkdownExportBuilder {
+        MarkdownExportBuilder::new()
+    }
+}
+
+pub struct MarkdownExportBuilder {
+    export: MarkdownExport,
+}
+
+impl MarkdownExportBuilder {
+    pub fn new() -> Self {
+        Self {
+            export: MarkdownExport::default(),
+        }
+    }
+    pub fn unsupported_formats(mut self, unsupported: UnsupportedFormats) -> Self {
+        self.export.unsupported = unsupported;
+        self
+    }
+    pub fn sanitize(mut self, policy: SanitizePolicy) -> Self {
+        self.export.sanitize = policy;
+        self
+    }
+    pub fn build(self) -> MarkdownExport {
+        self.export
+    }
+}
+
+impl Default for MarkdownExportBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Delta {
+    /// writes this document as CommonMark with the GFM extensions: `#` headings, `**bold**`,
+    /// `*italic*`, `~~strike~~`, `` `code` ``, fenced code blocks with their language, nested
+    /// and `- [x]` task lists, `>` blockquotes, links, images and `$formulas$`.
+    ///
+    /// formats markdown cannot express are dropped, see [`Delta::to_markdown_with`].
+    ///
+    /// ```
+    /// # use quill_wr::*;
+    /// let doc = delta!["Title", "\n" { header: 1 }, "a ", "b" { bold: true }, "\n"];
+    /// assert_eq!(doc.to_markdown(), "# Title\n\na **b**\n");
+    /// ```
+    pub fn to_markdown(&self) -> String {
+        self.to_markdown_with(&MarkdownExport::default())
+    }
+    pub fn to_markdown_with(&self, export: &MarkdownExport) -> String {
+        let document = self.sanitized(&export.sanitize);
+        let html = export.unsupported == UnsupportedFormats::Html;
+        let mut chunks: Vec<Chunk> = vec![];
+        let mut lists: Vec<ListLevel> = vec![];
+        for line in document.lines() {
+            let (videos, content) = split_block_embeds(&line.content);
+            for op in videos {
+                if let Some(Insert::Embed(embed)) = op.as_insert() {
+                    let embed = write_embed(&Embeds::from(embed.clone()), op.attributes(), html);
+                    chunks.push(Chunk::Paragraph(embed));
+                }
+            }
+            let format = LineFormat::new(&line.blocks());
+            if format.code.is_some() {
+                let language = format.code_language().unwrap_or_default();
+                let text = line_text(&content);
+                match chunks.last_mut() {
+                    Some(Chunk::Code(open, lines)) if open == language => lines.push(text),
+                    _ => chunks.push(Chunk::Code(language.into(), vec![text])),
+                }
+                continue;
+            }
+            let inline = escape_line_start(write_inlines(&content, html));
+            if let Some(kind) = format.list {
+                if !matches!(chunks.last(), Some(Chunk::List(_))) {
+                    lists.clear();
+                    chunks.push(Chunk::List(vec![]));
+                }
+                let item = list_item(&mut lists, kind, format.indent, &inline);
+                if let Some(Chunk::List(items)) = chunks.last_mut() {
+                    items.push(item);
+                }
+            } else if format.blockquote {
+                match chunks.last_mut() {
+                    Some(Chunk::Quote(lines)) => lines.push(inline),
+                    _ => chunks.push(Chunk::Quote(vec![inline])),
+                }
+            } else if !inline.is_empty() {
+                let block = match format.header {
+                    Some(level) => format!("{} {inline}", "#".repeat(level.into())),
+                    None => inline,
+                };
+                chunks.push(Chunk::Paragraph(if html {
+                    wrap_aligned(block, &format)
+                } else {
+                    block
+                }));
+            }
+        }
+        let mut markdown = chunks
+            .iter()
+            .map(Chunk::to_markdown)
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        if !markdown.is_empty() {
+            markdown.push('\n');
+        }
+        markdown
+    }
+}
+
+/// consecutive lines rendered as one markdown block
+enum Chunk {
+    Paragraph(String),
+    List(Vec<String>),
+    Quote(Vec<String>),
+    /// language, raw lines
+    Code(String, Vec<String>),
+}
+
+impl Chunk {
+    fn to_markdown(&self) -> String {
+        match self {
+            Self::Paragraph(paragraph) => paragraph.clone(),
+            Self::List(items) => items.join("\n"),
+            // a `>` line between quote lines keeps them apart
+            Self::Quote(lines) => lines
+                .iter()
+                .map(|line| match line.is_empty() {
+                    true => ">".to_string(),
+                    false => format!("> {line}"),
+                })
+                .collect::<Vec<_>>()
+                .join("\n>\n"),
+            Self::Code(language, lines) => {
+                let code = lines.join("\n");
+                let fence = "`".repeat(longest_run(&code, '`').max(2) + 1);
+                format!("{fence}{language}\n{code}\n{fence}")
+            }
+        }
+    }
+}
+
+/// an open list level while writing nested lists
+struct ListLevel {
+    ordered: bool,
+    number: usize,
+    /// the width of the item marker, nested items are indented by it
+    width: usize,
+}
+
+fn list_item(lists: &mut Vec<ListLevel>, kind: ListKind, indent: u8, content: &str) -> String {
+    // a level can only nest one deeper than the previous item
+    let level = usize::from(indent).min(lists.len());
+    lists.truncate(level + 1);
+    let ordered = kind == ListKind::Ordered;
+    match lists.get_mut(level) {
+        Some(list) if list.ordered == ordered => list.number += 1,
+        _ => {
+            lists.truncate(level);
+            lists.push(ListLevel {
+                ordered,
+                number: 1,
+                width: 0,
+            });
+        }
+    }
+    let padding: usize = lists[..level].iter().map(|list| list.width).sum();
+    let list = &mut lists[level];
+    let marker = match kind {
+        ListKind::Ordered => format!("{}.", list.number),
+        ListKind::Bullet => "-".into(),
+        ListKind::Checked => "- [x]".into(),
+        ListKind::Unchecked => "- [ ]".into(),
+    };
+    list.width = if ordered { marker.len() + 1 } else { 2 };
+    let item = format!("{}{marker} {content}", " ".repeat(padding));
+    item.trim_end().to_string()
+}
+
+/// `<div align="center">` around a paragraph, blank lines keep its markdown alive
+fn wrap_aligned(block: String, format: &LineFormat) -> String {
+    let mut attributes = String::new();
+    if let Some(align) = format.align {
+        let _ = write!(attributes, " align=\"{}\"", align.as_str());
+    }
+    if format.rtl {
+        attributes.push_str(" dir=\"rtl\"");
+    }
+    if attributes.is_empty() {
+        block
+    } else {
+        format!("<div{attributes}>\n\n{block}\n\n</div>")
+    }
+}
+
+/// an inline format markdown writes around the text it applies to
+#[derive(Clone, Debug, PartialEq)]
+enum Mark {
+    Link(String),
+    /// opening and closing html tags
+    Html(String, &'static str),
+    Strike,
+    Bold,
+    Italic,
+}
+
+impl Mark {
+    fn open(&self) -> &str {
+        match self {
+            Self::Link(_) => "[",
+            Self::Html(open, _) => open,
+            Self::Strike => "~~",
+            Self::Bold => "**",
+            Self::Italic => "*",
+        }
+    }
+    fn close(&self) -> String {
+        match self {
+            Self::Link(url) => format!("]({})", link_destination(url)),
+            Self::Html(_, close) => close.to_string(),
+            mark => mark.open().to_string(),
+        }
+    }
+}
+
+/// the marks of a run, outermost first
+fn marks(formats: &[Inline], html: bool) -> Vec<Mark> {
+    let mut link = None;
+    let mut styles = vec![];
+    let mut tags = vec![];
+    let mut marks = vec![];
+    for format in formats {
+        match format {
+            Inline::Link(url) => link = Some(Mark::Link(url.clone())),
+            Inline::Strike => marks.push(Mark::Strike),
+            Inline::Bold => marks.push(Mark::Bold),
+            Inline::Italic => marks.push(Mark::Italic),
+            Inline::Underline => tags.push(("<u>", "</u>")),
+            Inline::Sub => tags.push(("<sub>", "</sub>")),
+            Inline::Sup => tags.push(("<sup>", "</sup>")),
+            Inline::Color(color) => styles.push(format!("color: {color}")),
+            Inline::BgColor(color) => styles.push(format!("background-color: {color}")),
+            Inline::Font(font) => styles.push(format!("font-family: {}", font_family(font))),
+            Inline::Size(size) => styles.push(format!("font-size: {}", font_size(size))),
+            _ => {}
+        }
+    }
+    marks.sort_by_key(|mark| match mark {
+        Mark::Strike => 0,
+        Mark::Bold => 1,
+        _ => 2,
+    });
+    let mut html_marks = vec![];
+    if html {
+        if !styles.is_empty() {
+            let style = escape_html(&styles.join("; "));
+            html_marks.push(Mark::Html(format!("<span style=\"{style}\">"), "</span>"));
+        }
+        tags.sort();
+        for (open, close) in tags {
+            html_marks.push(Mark::Html(open.into(), close));
+        }
+    }
+    link.into_iter().chain(html_marks).chain(marks).collect()
+}
+
+/// the markdown of a line's content, formats that change between runs are closed and
+/// reopened so that every delimiter run hugs non whitespace text
+fn write_inlines(content: &Delta, html: bool) -> String {
+    let mut markdown = String::new();
+    let mut open: Vec<Mark> = vec![];
+    let mut whitespace = String::new();
+    for op in content.ops() {
+        let Some(insert) = op.as_insert() else {
+            continue;
+        };
+        let formats = Formats::from_kvs(op.attributes().cloned().unwrap_or_default()).inlines;
+        let core = match insert {
+            Insert::Text(text) => {
+                let trimmed = text.trim_start_matches([' ', '\t']);
+                whitespace.push_str(&text[..text.len() - trimmed.len()]);
+                let core = trimmed.trim_end_matches([' ', '\t']);
+                if core.is_empty() {
+                    continue;
+                }
+                let code = formats.contains(&Inline::Code);
+                let written = if code {
+                    code_span(core)
+                } else {
+                    escape_markdown(core)
+                };
+                (written, &trimmed[core.len()..])
+            }
+            Insert::Embed(embed) => (
+                write_embed(&Embeds::from(embed.clone()), op.attributes(), html),
+                "",
+            ),
+        };
+        let wanted = marks(&formats, html);
+        let kept = open
+            .iter()
+            .zip(&wanted)
+            .take_while(|(open, wanted)| open == wanted)
+            .count();
+        for mark in open.drain(kept..).rev() {
+            markdown.push_str(&mark.close());
+        }
+        markdown.push_str(&whitespace);
+        whitespace.clear();
+        for mark in &wanted[kept..] {
+            markdown.push_str(mark.open());
+        }
+        open = wanted;
+        markdown.push_str(&core.0);
+        whitespace.push_str(core.1);
+    }
+    for mark in open.iter().rev() {
+        markdown.push_str(&mark.close());
+    }
+    markdown
+}
+
+fn write_embed(embed: &Embeds, attributes: Option<&AttributeMap>, html: bool) -> String {
+    let attribute = |name: &str| {
+        attributes
+            .and_then(|attributes| attributes.get(name))
+            .and_then(|value| value.as_str())
+    };
+    let sized = attribute("width").is_some() || attribute("height").is_some();
+    match embed {
+        Embeds::Image(src) if html && sized => {
+            let mut img = format!("<img src=\"{}\"", escape_html(src));
+            for name in ["alt", "width", "height"] {
+                if let Some(value) = attribute(name) {
+                    let _ = write!(img, " {name}=\"{}\"", escape_html(value));
+                }
+            }
+            img + ">"
+        }
+        Embeds::Image(src) => format!(
+            "![{}]({})",
+            escape_markdown(attribute("alt").unwrap_or_default()),
+            link_destination(src)
+        ),
+        Embeds::Video(src) if html => {
+            format!(
+                "<iframe src=\"{}\" frameborder=\"0\" allowfullscreen=\"true\"></iframe>",
+                escape_html(src)
+            )
+        }
+        Embeds::Video(src) => format!("[video]({})", link_destination(src)),
+        Embeds::Formula(formula) => format!("${formula}$"),
+        Embeds::Custom(..) => String::new(),
+    }
+}
+
+/// backslash escapes the characters that could start markdown syntax inside a line
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let entity = c == '&'
+            && chars
+                .peek()
+                .is_some_and(|c| c.is_alphanumeric() || *c == '#');
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '~' | '$'
+        ) || entity
+        {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// escapes what would turn a paragraph into a heading, a list or a quote
+fn escape_line_start(line: String) -> String {
+    if line.starts_with(['#', '-', '+', '=']) {
+        return format!("\\{line}");
+    }
+    let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    if digits > 0 && line[digits..].starts_with(['.', ')']) {
+        return format!("{}\\{}", &line[..digits], &line[digits..]);
+    }
+    line
+}
+
+/// a code span with a fence longer than any backtick run of `code`
+fn code_span(code: &str) -> String {
+    let fence = "`".repeat(longest_run(code, '`') + 1);
+    let padding = if code.starts_with('`') || code.ends_with('`') {
+        " "
+    } else {
+        ""
+    };
+    format!("{fence}{padding}{code}{padding}{fence}")
+}
+
+fn longest_run(text: &str, c: char) -> usize {
+    text.split(|other| other != c)
+        .map(str::len)
+        .max()
+        .unwrap_or_default()
+}
+
+/// `<url>` when the url has characters ending a bare destination
+fn link_destination(url: &str) -> String {
+    if url.is_empty() || url.contains([' ', '(', ')', '<', '>']) {
+        format!("<{}>", url.replace('<', "%3C").replace('>', "%3E"))
+    } else {
+        url.to_string()
+    }
+}
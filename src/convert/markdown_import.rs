@@ -0,0 +1,343 @@
+use pulldown_cmark::{CodeBlockKind, Event, Options, Parser, Tag, TagEnd};
+use serde_json::{Value, json};
+
+use crate::{AttributeMap, Delta, Embed, HtmlImport, Insert, SanitizePolicy, Schema};
+
+/// how markdown is turned into a delta, see [`Delta::from_markdown_with`].
+///
+/// the default keeps the formats of [`Schema::default`] and sanitizes urls with
+/// [`SanitizePolicy::default`].
+#[derive(Clone, Debug, Default)]
+pub struct MarkdownImport {
+    schema: Schema,
+    sanitize: SanitizePolicy,
+}
+
+impl MarkdownImport {
+    pub fn builder() -> MarkdownImportBuilder {
+        MarkdownImportBuilder::new()
+    }
+}
+
+pub struct MarkdownImportBuilder {
+    import: MarkdownImport,
+}
+
+impl MarkdownImportBuilder {
+    pub fn new() -> Self {
+        Self {
+            import: MarkdownImport::default(),
+        }
+    }
+    /// formats, line formats and embeds the schema rejects are left out
+    pub fn schema(mut self, schema: Schema) -> Self {
+        self.import.schema = schema;
+        self
+    }
+    pub fn sanitize(mut self, policy: SanitizePolicy) -> Self {
+        self.import.sanitize = policy;
+        self
+    }
+    pub fn build(self) -> MarkdownImport {
+        self.import
+    }
+}
+
+impl Default for MarkdownImportBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Delta {
+    /// converts CommonMark with the GFM extensions to a document: headings, emphasis,
+    /// `~~strike~~`, code spans and fenced code blocks with their language, nested lists get an
+    /// `indent`, task lists become checked and unchecked lists, quotes, links, images and
+    /// `$formulas$`.
+    ///
+    /// paragraphs and list items become lines, soft breaks become spaces. html blocks go
+    /// through [`Delta::from_html_with`], inline `<u>`, `<sub>`, `<sup>` and `<br>` are kept.
+    ///
+    /// uses [`MarkdownImport::default`], see [`Delta::from_markdown_with`].
+    ///
+    /// ```
+    /// # use quill_wr::*;
+    /// let doc = Delta::from_markdown("# Title\n\n- [x] a **b**\n");
+    /// assert_eq!(
+    ///     doc,
+    ///     delta!["Title", "\n" { header: 1 }, "a ", "b" { bold: true }, "\n" { list: "checked" }]
+    /// );
+    /// ```
+    pub fn from_markdown(markdown: &str) -> Delta {
+        Self::from_markdown_with(markdown, &MarkdownImport::default())
+    }
+    pub fn from_markdown_with(markdown: &str, import: &MarkdownImport) -> Delta {
+        let options = Options::ENABLE_STRIKETHROUGH
+            | Options::ENABLE_TASKLISTS
+            | Options::ENABLE_TABLES
+            | Options::ENABLE_MATH;
+        let mut importer = Importer {
+            import,
+            html_import: HtmlImport::builder()
+                .schema(import.schema.clone())
+                .sanitize(import.sanitize.clone())
+                .build(),
+            delta: Delta::new(),
+            formats: vec![],
+            lists: vec![],
+            item: None,
+            header: None,
+            blockquote: 0,
+            code: None,
+            image: None,
+            html: String::new(),
+            open_line: false,
+        };
+        for event in Parser::new_ext(markdown, options) {
+            importer.event(event);
+        }
+        if importer.open_line || importer.delta.ops().is_empty() {
+            importer.end_line();
+        }
+        let mut delta = importer.delta;
+        delta.sanitize(&import.sanitize);
+        delta
+    }
+}
+
+struct Importer<'a> {
+    import: &'a MarkdownImport,
+    html_import: HtmlImport,
+    delta: Delta,
+    /// the inline formats of the open elements, innermost last
+    formats: Vec<(&'static str, Value)>,
+    /// whether each open list is ordered
+    lists: Vec<bool>,
+    /// the list format of the item whose first line is not written yet
+    item: Option<&'static str>,
+    header: Option<u8>,
+    blockquote: usize,
+    code: Option<Value>,
+    /// the url and alt text of the image being read
+    image: Option<(String, String)>,
+    /// the html block being read
+    html: String,
+    /// whether the current line has content
+    open_line: bool,
+}
+
+impl Importer<'_> {
+    fn event(&mut self, event: Event<'_>) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) if self.code.is_some() => {
+                for segment in text.split_inclusive('\n') {
+                    self.text(segment.trim_end_matches('\n'));
+                    if segment.ends_with('\n') {
+                        self.end_line();
+                    }
+                }
+            }
+            Event::Text(text) => self.text(&text),
+            Event::Code(code) => {
+                self.formats.push(("code", json!(true)));
+                self.text(&code);
+                self.formats.pop();
+            }
+            Event::InlineMath(formula) | Event::DisplayMath(formula) => {
+                self.embed("formula", json!(formula.as_ref()), AttributeMap::new());
+            }
+            Event::Html(html) => self.html.push_str(&html),
+            Event::InlineHtml(tag) => self.inline_html(&tag),
+            Event::SoftBreak => self.text(" "),
+            Event::HardBreak => self.end_line(),
+            Event::TaskListMarker(checked) if self.item.is_some() => {
+                self.item = Some(if checked { "checked" } else { "unchecked" });
+            }
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, tag: Tag<'_>) {
+        match tag {
+            Tag::Heading { level, .. } => self.header = Some(level as u8),
+            Tag::BlockQuote(_) => self.blockquote += 1,
+            Tag::CodeBlock(kind) => {
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => info
+                        .split(|c: char| c.is_whitespace() || c == ',')
+                        .next()
+                        .filter(|language| !language.is_empty())
+                        .map(str::to_string),
+                    CodeBlockKind::Indented => None,
+                };
+                self.code = Some(language.map_or(json!(true), |language| json!(language)));
+            }
+            Tag::List(start) => {
+                // a nested list ends the line of its parent item
+                self.finish_line();
+                self.lists.push(start.is_some());
+            }
+            Tag::Item => {
+                let ordered = self.lists.last().copied().unwrap_or_default();
+                self.item = Some(if ordered { "ordered" } else { "bullet" });
+            }
+            Tag::Emphasis => self.formats.push(("italic", json!(true))),
+            Tag::Strong => self.formats.push(("bold", json!(true))),
+            Tag::Strikethrough => self.formats.push(("strike", json!(true))),
+            Tag::Superscript => self.formats.push(("script", json!("super"))),
+            Tag::Subscript => self.formats.push(("script", json!("sub"))),
+            Tag::Link { dest_url, .. } => self.formats.push(("link", json!(dest_url.as_ref()))),
+            Tag::Image { dest_url, .. } => self.image = Some((dest_url.to_string(), String::new())),
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph | TagEnd::TableCell => self.end_line(),
+            TagEnd::Heading(_) => {
+                self.end_line();
+                self.header = None;
+            }
+            TagEnd::BlockQuote(_) => self.blockquote = self.blockquote.saturating_sub(1),
+            TagEnd::CodeBlock => {
+                self.finish_line();
+                self.code = None;
+            }
+            TagEnd::HtmlBlock => self.html_block(),
+            TagEnd::List(_) => {
+                self.finish_line();
+                self.lists.pop();
+            }
+            TagEnd::Item => self.finish_line(),
+            TagEnd::Emphasis
+            | TagEnd::Strong
+            | TagEnd::Strikethrough
+            | TagEnd::Superscript
+            | TagEnd::Subscript
+            | TagEnd::Link => {
+                self.formats.pop();
+            }
+            TagEnd::Image => {
+                if let Some((url, alt)) = self.image.take() {
+                    let mut attributes = AttributeMap::new();
+                    if !alt.is_empty() {
+                        attributes.insert("alt".into(), json!(alt));
+                    }
+                    self.embed("image", json!(url), attributes);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// the inline tags markdown has no syntax for, other html is dropped and its text kept
+    fn inline_html(&mut self, html: &str) {
+        let tag = html.trim().to_ascii_lowercase();
+        let (name, value) = match tag.trim_start_matches('<').trim_start_matches('/') {
+            "u>" => ("underline", json!(true)),
+            "sub>" => ("script", json!("sub")),
+            "sup>" => ("script", json!("super")),
+            "br>" | "br/>" | "br />" => return self.end_line(),
+            _ => return,
+        };
+        if !tag.starts_with("</") {
+            self.formats.push((name, value));
+        } else if let Some(index) = self.formats.iter().rposition(|(open, _)| *open == name) {
+            self.formats.remove(index);
+        }
+    }
+
+    fn html_block(&mut self) {
+        let html = std::mem::take(&mut self.html);
+        let imported = Delta::from_html_with(&html, &self.html_import).unwrap_or_default();
+        // `<div align="center">` alone has no content
+        let blank = imported.ops().iter().all(|op| {
+            op.as_insert()
+                .and_then(Insert::as_text)
+                .is_some_and(|text| text.chars().all(|c| c == '\n'))
+        });
+        if !blank {
+            self.finish_line();
+            for op in imported.into_ops() {
+                self.delta.push(op);
+            }
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if let Some((_, alt)) = &mut self.image {
+            alt.push_str(text);
+            return;
+        }
+        if !text.is_empty() {
+            let attributes = self.inline_attributes();
+            self.delta.insert(text, attributes);
+            self.open_line = true;
+        }
+    }
+
+    fn embed(&mut self, kind: &str, value: Value, attributes: AttributeMap) {
+        let schema = &self.import.schema;
+        if !schema.accepts_embed(kind, &value) {
+            return;
+        }
+        let mut all = self.inline_attributes();
+        all.extend(
+            attributes
+                .into_iter()
+                .filter(|(name, value)| schema.accepts_embed_attr(name, value)),
+        );
+        self.delta.insert(Embed::new(kind, value), all);
+        self.open_line = true;
+    }
+
+    fn inline_attributes(&self) -> AttributeMap {
+        let schema = &self.import.schema;
+        self.formats
+            .iter()
+            .filter(|(name, value)| schema.accepts_inline(name, value))
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect()
+    }
+
+    /// ends the line when it has content or is the first line of a list item
+    fn finish_line(&mut self) {
+        if self.open_line || self.item.is_some() {
+            self.end_line();
+        }
+    }
+
+    /// writes the newline with the formats of the innermost block: code, then lists, headers
+    /// and quotes. paragraphs following the first line of an item are indented below it
+    fn end_line(&mut self) {
+        let mut formats = vec![];
+        if let Some(language) = &self.code {
+            formats.push(("code-block", language.clone()));
+        } else if let Some(kind) = self.item.take() {
+            formats.push(("list", json!(kind)));
+            if self.lists.len() > 1 {
+                formats.push(("indent", json!(self.lists.len() - 1)));
+            }
+        } else {
+            if let Some(level) = self.header {
+                formats.push(("header", json!(level)));
+            } else if self.blockquote > 0 {
+                formats.push(("blockquote", json!(true)));
+            }
+            if !self.lists.is_empty() {
+                formats.push(("indent", json!(self.lists.len())));
+            }
+        }
+        let schema = &self.import.schema;
+        let attributes = formats
+            .into_iter()
+            .filter(|(name, value)| schema.accepts_block(name, value))
+            .map(|(name, value)| (name.to_string(), value))
+            .collect();
+        self.delta.insert("\n", attributes);
+        self.open_line = false;
+    }
+}
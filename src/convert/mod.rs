@@ -0,0 +1,218 @@
+mod docx;
+pub use docx::*;
+mod docx_import;
+pub use docx_import::*;
+mod editor;
+mod html;
+pub(crate) use html::{font_family, font_size};
+mod html_import;
+pub use html_import::*;
+mod markdown;
+pub use markdown::*;
+mod markdown_import;
+pub use markdown_import::*;
+mod odt;
+pub use odt::*;
+mod text;
+pub use text::*;
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use imagesize::ImageType;
+
+use crate::{Align, Block, Delta, Font, Insert, Line, ListKind, Op, Size};
+
+/// the line formats of one line, flattened for the converters
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct LineFormat {
+    pub header: Option<u8>,
+    pub list: Option<ListKind>,
+    pub blockquote: bool,
+    /// `Some(None)` for a code block without language
+    pub code: Option<Option<String>>,
+    pub indent: u8,
+    pub align: Option<Align>,
+    pub rtl: bool,
+}
+
+impl LineFormat {
+    pub fn new(blocks: &[Block]) -> Self {
+        let mut format = Self::default();
+        for block in blocks {
+            match block {
+                Block::Header(level) => format.header = Some(level.get()),
+                Block::List(kind) => format.list = Some(*kind),
+                Block::Blockquote => format.blockquote = true,
+                Block::CodeBlock(language) => format.code = Some(language.clone()),
+                Block::Indent(level) => format.indent = level.get(),
+                Block::Align(align) => format.align = Some(*align),
+                Block::Rtl => format.rtl = true,
+                Block::Custom(..) => {}
+            }
+        }
+        format
+    }
+    /// the language of a code block, `None` for "plain" or no language
+    pub fn code_language(&self) -> Option<&str> {
+        self.code
+            .as_ref()?
+            .as_deref()
+            .filter(|language| *language != "plain")
+    }
+}
+
+/// escapes text for html element content
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// escapes text for xml content and attributes, dropping the characters xml cannot hold
+pub(crate) fn escape_xml(text: &str) -> String {
+    let valid = text
+        .chars()
+        .filter(|c| {
+            matches!(c, '\t' | '\n' | '\r' | '\u{20}'..='\u{d7ff}' | '\u{e000}'..='\u{fffd}')
+                || *c >= '\u{10000}'
+        })
+        .collect::<String>();
+    escape_html(&valid)
+}
+
+/// Quill's named sizes relative to the 11pt of the document, pixel sizes at 96 dpi kept
+/// within the 1pt to 1638pt word allows
+pub(crate) fn half_points(size: &Size) -> Option<u32> {
+    match size {
+        Size::Small => Some(16),
+        Size::Large => Some(33),
+        Size::Huge => Some(55),
+        Size::Px(pixels) if pixels.is_finite() && *pixels > 0.0 => {
+            Some((pixels * 1.5).round().clamp(2.0, 3276.0) as u32)
+        }
+        Size::Px(_) => None,
+        Size::Custom(_) => None,
+    }
+}
+
+/// the family written for a font, the first of a css list
+pub(crate) fn font_name(font: &Font) -> String {
+    match font {
+        Font::Serif => "Georgia".into(),
+        Font::Monospace => "Courier New".into(),
+        Font::Custom(family) => family
+            .split(',')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .trim_matches(['"', '\''])
+            .to_string(),
+    }
+}
+
+/// returns the bytes of an image from its url, see [`DocxExportBuilder::images`]
+pub(crate) type ImageLoader = Box<dyn Fn(&str) -> Option<Vec<u8>>>;
+
+/// an image embedded in a document package
+pub(crate) struct ImageData {
+    pub bytes: Vec<u8>,
+    pub extension: &'static str,
+    pub mime: &'static str,
+    /// intrinsic size in pixels
+    pub width: f64,
+    pub height: f64,
+}
+
+impl ImageData {
+    /// the bytes of `src` from the loader or from a base64 `data:` url, `None` when there are
+    /// none or they are not a png, jpeg, gif, bmp or tiff image
+    pub fn load(src: &str, loader: Option<&ImageLoader>) -> Option<Self> {
+        let bytes = loader
+            .and_then(|loader| loader(src))
+            .or_else(|| data_url(src))?;
+        let (extension, mime) = match imagesize::image_type(&bytes).ok()? {
+            ImageType::Png => ("png", "image/png"),
+            ImageType::Jpeg => ("jpeg", "image/jpeg"),
+            ImageType::Gif => ("gif", "image/gif"),
+            ImageType::Bmp => ("bmp", "image/bmp"),
+            ImageType::Tiff => ("tiff", "image/tiff"),
+            _ => return None,
+        };
+        let size = imagesize::blob_size(&bytes).ok()?;
+        Some(Self {
+            bytes,
+            extension,
+            mime,
+            width: size.width as f64,
+            height: size.height as f64,
+        })
+    }
+    /// the displayed size in pixels: the `width` and `height` attributes of the embed, the
+    /// missing one following the aspect ratio, at most `max_width` wide
+    pub fn display_size(
+        &self,
+        width: Option<&str>,
+        height: Option<&str>,
+        max_width: f64,
+    ) -> (f64, f64) {
+        let ratio = self.height / self.width.max(1.0);
+        let (width, height) = match (width.and_then(pixels), height.and_then(pixels)) {
+            (Some(width), Some(height)) => (width, height),
+            (Some(width), None) => (width, width * ratio),
+            (None, Some(height)) => (height / ratio.max(f64::EPSILON), height),
+            (None, None) => (self.width, self.height),
+        };
+        if width > max_width {
+            (max_width, height * (max_width / width))
+        } else {
+            (width, height)
+        }
+    }
+}
+
+fn data_url(src: &str) -> Option<Vec<u8>> {
+    let (meta, data) = src.strip_prefix("data:")?.split_once(',')?;
+    meta.ends_with(";base64")
+        .then(|| STANDARD.decode(data.trim()).ok())
+        .flatten()
+}
+
+/// `"120"` or `"120px"`, other units have no pixel size
+fn pixels(value: &str) -> Option<f64> {
+    let value = value.trim();
+    value
+        .strip_suffix("px")
+        .unwrap_or(value)
+        .trim()
+        .parse()
+        .ok()
+        .filter(|pixels: &f64| pixels.is_finite() && *pixels > 0.0)
+}
+
+/// splits the videos (block embeds) a line starts with from the rest of the line
+pub(crate) fn split_block_embeds(content: &Delta) -> (&[Op], Delta) {
+    let is_video = |op: &&Op| {
+        op.as_insert()
+            .and_then(Insert::as_embed)
+            .is_some_and(|embed| embed.kind == "video")
+    };
+    let videos = content.ops().iter().take_while(is_video).count();
+    let (videos, rest) = content.ops().split_at(videos);
+    (videos, Delta::from_ops(rest.to_vec()))
+}
+
+/// the text of a line's content, embeds are skipped
+pub(crate) fn line_text(content: &Delta) -> String {
+    Line {
+        content: content.clone(),
+        ..Default::default()
+    }
+    .text()
+}
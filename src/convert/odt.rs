@@ -0,0 +1,728 @@
+use std::{
+    collections::HashMap,
+    fmt::Write as _,
+    io::{self, Cursor, Write},
+};
+
+use zip::{CompressionMethod, ZipWriter, write::SimpleFileOptions};
+
+use super::{
+    ImageData, ImageLoader, LineFormat, escape_xml, font_name, half_points, split_block_embeds,
+};
+use crate::{
+    Align, AttributeMap, Delta, Embed, Embeds, Formats, Inline, Insert, Line, ListKind,
+    SanitizePolicy,
+};
+
+const MIME_TYPE: &str = "application/vnd.oasis.opendocument.text";
+const NAMESPACES: &str = "xmlns:office=\"urn:oasis:names:tc:opendocument:xmlns:office:1.0\" \
+     xmlns:style=\"urn:oasis:names:tc:opendocument:xmlns:style:1.0\" \
+     xmlns:text=\"urn:oasis:names:tc:opendocument:xmlns:text:1.0\" \
+     xmlns:draw=\"urn:oasis:names:tc:opendocument:xmlns:drawing:1.0\" \
+     xmlns:fo=\"urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0\" \
+     xmlns:xlink=\"http://www.w3.org/1999/xlink\" \
+     xmlns:svg=\"urn:oasis:names:tc:opendocument:xmlns:svg-compatible:1.0\" \
+     office:version=\"1.2\"";
+
+/// the width between the one inch margins of a letter page, in pixels
+const TEXT_WIDTH: f64 = 624.0;
+/// levels of the list styles, the most ODF allows
+const LIST_LEVELS: usize = 10;
+
+/// how a delta is written as an OpenDocument text, see [`Delta::to_odt_with`].
+#[derive(Default)]
+pub struct OdtExport {
+    images: Option<ImageLoader>,
+    sanitize: SanitizePolicy,
+}
+
+impl OdtExport {
+    pub fn builder() -> OdtExportBuilder {
+        OdtExportBuilder::new()
+    }
+}
+
+pub struct OdtExportBuilder {
+    export: OdtExport,
+}
+
+impl OdtExportBuilder {
+    pub fn new() -> Self {
+        Self {
+            export: OdtExport::default(),
+        }
+    }
+    /// the bytes of the image at a url, fetched by the caller. `data:` urls are decoded
+    /// without it, the images without bytes are written as their alt text
+    pub fn images(mut self, loader: impl Fn(&str) -> Option<Vec<u8>> + 'static) -> Self {
+        self.export.images = Some(Box::new(loader));
+        self
+    }
+    pub fn sanitize(mut self, policy: SanitizePolicy) -> Self {
+        self.export.sanitize = policy;
+        self
+    }
+    pub fn build(self) -> OdtExport {
+        self.export
+    }
+}
+
+impl Default for OdtExportBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Delta {
+    /// writes this document as an OpenDocument text (`.odt`): headers use the `Heading 1`..
+    /// `Heading 6` styles, quotes and code blocks the `Quotations` and `Preformatted Text`
+    /// styles, lists are nested lists of the `Bullet`, `Numbering`, `Checked` and `Unchecked`
+    /// list styles, links are hyperlinks and images are embedded in `Pictures/`.
+    ///
+    /// inline formats and the alignment and indent of lines become automatic styles of
+    /// `content.xml`, the named styles are in `styles.xml`. only the `data:` images are
+    /// embedded, see [`Delta::to_odt_with`] to provide the bytes of the others.
+    ///
+    /// ```
+    /// # use quill_wr::*;
+    /// let doc = delta!["Title", "\n" { header: 1 }, "a ", "b" { bold: true }, "\n"];
+    /// let odt = doc.to_odt().unwrap();
+    /// assert_eq!(&odt[30..38], b"mimetype");
+    /// ```
+    pub fn to_odt(&self) -> io::Result<Vec<u8>> {
+        self.to_odt_with(&OdtExport::default())
+    }
+    pub fn to_odt_with(&self, export: &OdtExport) -> io::Result<Vec<u8>> {
+        let document = self.sanitized(&export.sanitize);
+        let mut writer = OdtWriter {
+            export,
+            body: String::new(),
+            text_styles: vec![],
+            paragraph_styles: vec![],
+            images: HashMap::new(),
+            pictures: vec![],
+            frames: 0,
+            lists: vec![],
+            ordered_lists: 0,
+            ordered_list: None,
+        };
+        for line in document.lines() {
+            writer.line(&line);
+        }
+        writer.close_lists();
+        if writer.body.is_empty() {
+            writer
+                .body
+                .push_str("<text:p text:style-name=\"Standard\"/>");
+        }
+        writer.package()
+    }
+}
+
+struct OdtWriter<'a> {
+    export: &'a OdtExport,
+    body: String,
+    /// the properties of the automatic text styles `T1`, `T2`...
+    text_styles: Vec<String>,
+    /// the parent and properties of the automatic paragraph styles `P1`, `P2`...
+    paragraph_styles: Vec<(&'static str, String)>,
+    /// paths and sizes by image url, `None` for the images without bytes
+    images: HashMap<String, Option<(String, ImageData)>>,
+    /// paths, bytes and media types of the images in the package
+    pictures: Vec<(String, Vec<u8>, &'static str)>,
+    frames: usize,
+    /// the kind of each open list, outermost first
+    lists: Vec<ListKind>,
+    ordered_lists: usize,
+    /// the number of the last top level ordered list, until a line that is not an item
+    ordered_list: Option<usize>,
+}
+
+impl OdtWriter<'_> {
+    fn line(&mut self, line: &Line) {
+        let (videos, content) = split_block_embeds(&line.content);
+        for op in videos {
+            if let Some(Insert::Embed(embed)) = op.as_insert() {
+                self.end_list();
+                let url = embed.value.as_str().unwrap_or_default();
+                let link = hyperlink(url, &text_content(url, &mut true));
+                let _ = write!(
+                    self.body,
+                    "<text:p text:style-name=\"Standard\">{link}</text:p>"
+                );
+            }
+        }
+        // the newline after a video holds no paragraph of its own
+        if !videos.is_empty() && content.ops().is_empty() {
+            return;
+        }
+        let format = LineFormat::new(&line.blocks());
+        let (parent, level) = match (&format.code, format.list, format.header) {
+            (Some(_), ..) => ("Preformatted_20_Text", None),
+            (None, Some(_), _) => ("Standard", None),
+            (None, None, Some(level)) => {
+                (HEADINGS[usize::from(level.clamp(1, 6)) - 1], Some(level))
+            }
+            (None, None, None) if format.blockquote => ("Quotations", None),
+            (None, None, None) => ("Standard", None),
+        };
+        match format.list.filter(|_| format.code.is_none()) {
+            Some(kind) => self.list_item(kind, usize::from(format.indent.min(8))),
+            None => self.end_list(),
+        }
+        let mut properties = String::new();
+        if format.list.is_none() && format.code.is_none() && format.indent > 0 {
+            let _ = write!(
+                properties,
+                " fo:margin-left=\"{}in\"",
+                f64::from(format.indent) * 0.5
+            );
+        }
+        if let Some(align) = format.align {
+            let align = match align {
+                Align::Center => "center",
+                Align::Right => "right",
+                Align::Justify => "justify",
+            };
+            let _ = write!(properties, " fo:text-align=\"{align}\"");
+        }
+        if format.rtl {
+            properties.push_str(" style:writing-mode=\"rl-tb\"");
+        }
+        let style = if properties.is_empty() {
+            parent.to_string()
+        } else {
+            self.paragraph_style(parent, properties)
+        };
+        let spans = self.spans(&content);
+        match level {
+            Some(level) => {
+                let _ = write!(
+                    self.body,
+                    "<text:h text:style-name=\"{style}\" text:outline-level=\"{level}\">{spans}\
+                     </text:h>"
+                );
+            }
+            None => {
+                let _ = write!(
+                    self.body,
+                    "<text:p text:style-name=\"{style}\">{spans}</text:p>"
+                );
+            }
+        }
+    }
+
+    /// opens the list item of a line, nesting a list per indent level. each top level list
+    /// starts at 1 unless it follows ordered items only interrupted by other kinds of items,
+    /// like Quill numbers them
+    fn list_item(&mut self, kind: ListKind, level: usize) {
+        while self.lists.len() > level + 1 {
+            self.lists.pop();
+            self.body.push_str("</text:list-item></text:list>");
+        }
+        if self.lists.len() == level + 1 {
+            self.body.push_str("</text:list-item>");
+            if self.lists[level] != kind {
+                self.lists.pop();
+                self.body.push_str("</text:list>");
+            }
+        }
+        while self.lists.len() <= level {
+            let style = match kind {
+                ListKind::Ordered => "Numbering",
+                ListKind::Bullet => "Bullet",
+                ListKind::Checked => "Checked",
+                ListKind::Unchecked => "Unchecked",
+            };
+            let _ = write!(self.body, "<text:list text:style-name=\"{style}\"");
+            if self.lists.is_empty() && kind == ListKind::Ordered {
+                self.ordered_lists += 1;
+                let _ = write!(self.body, " xml:id=\"list{}\"", self.ordered_lists);
+                if let Some(previous) = self.ordered_list {
+                    let _ = write!(self.body, " text:continue-list=\"list{previous}\"");
+                }
+                self.ordered_list = Some(self.ordered_lists);
+            }
+            self.body.push('>');
+            self.lists.push(kind);
+            // the levels skipped over hold nothing but the next list
+            if self.lists.len() <= level {
+                self.body.push_str("<text:list-item>");
+            }
+        }
+        self.body.push_str("<text:list-item>");
+    }
+
+    /// a line that is not a list item ends the open lists and the ordered numbering
+    fn end_list(&mut self) {
+        self.close_lists();
+        self.ordered_list = None;
+    }
+
+    fn close_lists(&mut self) {
+        for _ in self.lists.drain(..) {
+            self.body.push_str("</text:list-item></text:list>");
+        }
+    }
+
+    fn paragraph_style(&mut self, parent: &'static str, properties: String) -> String {
+        let style = (parent, properties);
+        let index = match self
+            .paragraph_styles
+            .iter()
+            .position(|known| *known == style)
+        {
+            Some(index) => index,
+            None => {
+                self.paragraph_styles.push(style);
+                self.paragraph_styles.len() - 1
+            }
+        };
+        format!("P{}", index + 1)
+    }
+
+    fn text_style(&mut self, properties: String) -> String {
+        let index = match self
+            .text_styles
+            .iter()
+            .position(|known| *known == properties)
+        {
+            Some(index) => index,
+            None => {
+                self.text_styles.push(properties);
+                self.text_styles.len() - 1
+            }
+        };
+        format!("T{}", index + 1)
+    }
+
+    /// the spans of a line, consecutive spans with the same link share one hyperlink
+    fn spans(&mut self, content: &Delta) -> String {
+        let mut xml = String::new();
+        let mut link: Option<(String, String)> = None;
+        // the line starts as if after a space, its leading spaces have to be kept too
+        let mut space = true;
+        for op in content.ops() {
+            let Some(insert) = op.as_insert() else {
+                continue;
+            };
+            let attributes = op.attributes().cloned().unwrap_or_default();
+            let formats = Formats::from_kvs(attributes.clone()).inlines;
+            // videos inside a line link to their url
+            let video = insert
+                .as_embed()
+                .filter(|embed| embed.kind == "video")
+                .and_then(|embed| embed.value.as_str());
+            let url = formats
+                .iter()
+                .find_map(|format| match format {
+                    Inline::Link(url) => Some(url.clone()),
+                    _ => None,
+                })
+                .or_else(|| video.map(str::to_string));
+            let properties = text_properties(&formats);
+            let span = match insert {
+                Insert::Text(text) => self.span(&text_content(text, &mut space), properties),
+                Insert::Embed(embed) => self.embed(embed, &attributes, properties, &mut space),
+            };
+            if link.as_ref().map(|(open, _)| open) != url.as_ref()
+                && let Some((open, spans)) = link.take()
+            {
+                xml.push_str(&hyperlink(&open, &spans));
+            }
+            match url {
+                Some(url) => link.get_or_insert_with(|| (url, String::new())).1 += &span,
+                None => xml.push_str(&span),
+            }
+        }
+        if let Some((open, spans)) = link {
+            xml.push_str(&hyperlink(&open, &spans));
+        }
+        xml
+    }
+
+    fn span(&mut self, content: &str, properties: String) -> String {
+        if content.is_empty() || properties.is_empty() {
+            return content.to_string();
+        }
+        let style = self.text_style(properties);
+        format!("<text:span text:style-name=\"{style}\">{content}</text:span>")
+    }
+
+    fn embed(
+        &mut self,
+        embed: &Embed,
+        attributes: &AttributeMap,
+        properties: String,
+        space: &mut bool,
+    ) -> String {
+        let attribute = |name: &str| attributes.get(name).and_then(|value| value.as_str());
+        let text = match Embeds::from(embed.clone()) {
+            Embeds::Image(src) => {
+                let frame = self.image(
+                    &src,
+                    attribute("width"),
+                    attribute("height"),
+                    attribute("alt"),
+                );
+                if let Some(frame) = frame {
+                    *space = false;
+                    return frame;
+                }
+                attribute("alt").unwrap_or_default().to_string()
+            }
+            Embeds::Video(url) | Embeds::Formula(url) => url,
+            Embeds::Custom(..) => String::new(),
+        };
+        self.span(&text_content(&text, space), properties)
+    }
+
+    /// a picture anchored as a character, `None` when the image has no bytes
+    fn image(
+        &mut self,
+        src: &str,
+        width: Option<&str>,
+        height: Option<&str>,
+        alt: Option<&str>,
+    ) -> Option<String> {
+        if !self.images.contains_key(src) {
+            let image = ImageData::load(src, self.export.images.as_ref()).map(|mut image| {
+                let path = format!(
+                    "Pictures/image{}.{}",
+                    self.pictures.len() + 1,
+                    image.extension
+                );
+                let bytes = std::mem::take(&mut image.bytes);
+                self.pictures.push((path.clone(), bytes, image.mime));
+                (path, image)
+            });
+            self.images.insert(src.to_string(), image);
+        }
+        let (path, image) = self.images.get(src)?.as_ref()?;
+        let (width, height) = image.display_size(width, height, TEXT_WIDTH);
+        self.frames += 1;
+        let description = match alt.filter(|alt| !alt.is_empty()) {
+            Some(alt) => format!("<svg:desc>{}</svg:desc>", escape_xml(alt)),
+            None => String::new(),
+        };
+        Some(format!(
+            "<draw:frame draw:name=\"Image{}\" text:anchor-type=\"as-char\" \
+             svg:width=\"{}in\" svg:height=\"{}in\" draw:z-index=\"0\">\
+             <draw:image xlink:href=\"{path}\" xlink:type=\"simple\" xlink:show=\"embed\" \
+             xlink:actuate=\"onLoad\"/>{description}</draw:frame>",
+            self.frames,
+            inches(width),
+            inches(height)
+        ))
+    }
+
+    fn package(self) -> io::Result<Vec<u8>> {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        // the media type comes first and uncompressed, to be read at a fixed offset
+        zip.start_file(
+            "mimetype",
+            SimpleFileOptions::default().compression_method(CompressionMethod::Stored),
+        )?;
+        zip.write_all(MIME_TYPE.as_bytes())?;
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        let mut part = |path: &str, bytes: &[u8]| -> io::Result<()> {
+            zip.start_file(path, options)?;
+            zip.write_all(bytes)
+        };
+
+        let pictures = self
+            .pictures
+            .iter()
+            .map(|(path, _, mime)| {
+                format!("<manifest:file-entry manifest:full-path=\"{path}\" manifest:media-type=\"{mime}\"/>")
+            })
+            .collect::<String>();
+        part(
+            "META-INF/manifest.xml",
+            format!(
+                "{XML_DECLARATION}<manifest:manifest \
+                 xmlns:manifest=\"urn:oasis:names:tc:opendocument:xmlns:manifest:1.0\" \
+                 manifest:version=\"1.2\">\
+                 <manifest:file-entry manifest:full-path=\"/\" manifest:version=\"1.2\" \
+                 manifest:media-type=\"{MIME_TYPE}\"/>\
+                 <manifest:file-entry manifest:full-path=\"content.xml\" \
+                 manifest:media-type=\"text/xml\"/>\
+                 <manifest:file-entry manifest:full-path=\"styles.xml\" \
+                 manifest:media-type=\"text/xml\"/>{pictures}</manifest:manifest>"
+            )
+            .as_bytes(),
+        )?;
+
+        let mut automatic = String::new();
+        for (index, (parent, properties)) in self.paragraph_styles.iter().enumerate() {
+            let _ = write!(
+                automatic,
+                "<style:style style:name=\"P{}\" style:family=\"paragraph\" \
+                 style:parent-style-name=\"{parent}\">\
+                 <style:paragraph-properties{properties}/></style:style>",
+                index + 1
+            );
+        }
+        for (index, properties) in self.text_styles.iter().enumerate() {
+            let _ = write!(
+                automatic,
+                "<style:style style:name=\"T{}\" style:family=\"text\">\
+                 <style:text-properties{properties}/></style:style>",
+                index + 1
+            );
+        }
+        part(
+            "content.xml",
+            format!(
+                "{XML_DECLARATION}<office:document-content {NAMESPACES}>\
+                 <office:automatic-styles>{automatic}</office:automatic-styles>\
+                 <office:body><office:text>{}</office:text></office:body>\
+                 </office:document-content>",
+                self.body
+            )
+            .as_bytes(),
+        )?;
+        part("styles.xml", styles().as_bytes())?;
+        for (path, bytes, _) in &self.pictures {
+            part(path, bytes)?;
+        }
+        Ok(zip.finish()?.into_inner())
+    }
+}
+
+const XML_DECLARATION: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n";
+
+const HEADINGS: [&str; 6] = [
+    "Heading_20_1",
+    "Heading_20_2",
+    "Heading_20_3",
+    "Heading_20_4",
+    "Heading_20_5",
+    "Heading_20_6",
+];
+
+/// the named paragraph, text and list styles the document refers to and a letter page,
+/// 11pt Calibri by default like the Word export
+fn styles() -> String {
+    let mut styles = format!(
+        "{XML_DECLARATION}<office:document-styles {NAMESPACES}><office:styles>\
+         <style:default-style style:family=\"paragraph\">\
+         <style:paragraph-properties fo:margin-top=\"0in\" fo:margin-bottom=\"0.111in\" \
+         fo:line-height=\"108%\"/>\
+         <style:text-properties fo:font-family=\"Calibri\" fo:font-size=\"11pt\"/>\
+         </style:default-style>\
+         <style:style style:name=\"Standard\" style:family=\"paragraph\" style:class=\"text\"/>\
+         <style:style style:name=\"Heading\" style:family=\"paragraph\" \
+         style:parent-style-name=\"Standard\" style:next-style-name=\"Standard\" \
+         style:class=\"text\"><style:paragraph-properties fo:margin-top=\"0.167in\" \
+         fo:margin-bottom=\"0.056in\" fo:keep-with-next=\"always\"/>\
+         <style:text-properties fo:font-weight=\"bold\" style:font-weight-complex=\"bold\"/>\
+         </style:style>"
+    );
+    for (level, size) in (1..=6).zip([20, 16, 14, 12, 11, 11]) {
+        let _ = write!(
+            styles,
+            "<style:style style:name=\"{}\" style:display-name=\"Heading {level}\" \
+             style:family=\"paragraph\" style:parent-style-name=\"Heading\" \
+             style:next-style-name=\"Standard\" style:default-outline-level=\"{level}\" \
+             style:class=\"text\"><style:text-properties fo:font-size=\"{size}pt\"/>\
+             </style:style>",
+            HEADINGS[level - 1]
+        );
+    }
+    styles.push_str(
+        "<style:style style:name=\"Quotations\" style:family=\"paragraph\" \
+         style:parent-style-name=\"Standard\" style:class=\"html\">\
+         <style:paragraph-properties fo:margin-left=\"0.25in\" fo:padding-left=\"0.111in\" \
+         fo:border-left=\"0.02in solid #cccccc\" fo:border-right=\"none\" fo:border-top=\"none\" \
+         fo:border-bottom=\"none\"/>\
+         <style:text-properties fo:font-style=\"italic\" fo:color=\"#555555\"/></style:style>\
+         <style:style style:name=\"Preformatted_20_Text\" style:display-name=\"Preformatted Text\" \
+         style:family=\"paragraph\" style:parent-style-name=\"Standard\" style:class=\"html\">\
+         <style:paragraph-properties fo:margin-top=\"0in\" fo:margin-bottom=\"0in\" \
+         fo:line-height=\"100%\" fo:background-color=\"#f0f0f0\"/>\
+         <style:text-properties fo:font-family=\"'Courier New'\" fo:font-size=\"10pt\"/>\
+         </style:style>\
+         <style:style style:name=\"Internet_20_link\" style:display-name=\"Internet link\" \
+         style:family=\"text\"><style:text-properties fo:color=\"#0563c1\" \
+         style:text-underline-style=\"solid\" style:text-underline-width=\"auto\" \
+         style:text-underline-color=\"font-color\"/></style:style>\
+         <style:style style:name=\"Visited_20_Internet_20_Link\" \
+         style:display-name=\"Visited Internet Link\" style:family=\"text\">\
+         <style:text-properties fo:color=\"#954f72\" style:text-underline-style=\"solid\" \
+         style:text-underline-width=\"auto\" style:text-underline-color=\"font-color\"/>\
+         </style:style>",
+    );
+    let bullets = [
+        ("Bullet", "\u{2022}"),
+        ("Checked", "\u{2611}"),
+        ("Unchecked", "\u{2610}"),
+    ];
+    for (name, bullet) in bullets {
+        let _ = write!(styles, "<text:list-style style:name=\"{name}\">");
+        for level in 1..=LIST_LEVELS {
+            let _ = write!(
+                styles,
+                "<text:list-level-style-bullet text:level=\"{level}\" \
+                 text:bullet-char=\"{bullet}\">{}</text:list-level-style-bullet>",
+                list_level_properties(level)
+            );
+        }
+        styles.push_str("</text:list-style>");
+    }
+    styles.push_str("<text:list-style style:name=\"Numbering\">");
+    for level in 1..=LIST_LEVELS {
+        // the counter styles Quill uses for nested ordered lists
+        let format = ["1", "a", "i"][(level - 1) % 3];
+        let _ = write!(
+            styles,
+            "<text:list-level-style-number text:level=\"{level}\" style:num-suffix=\".\" \
+             style:num-format=\"{format}\">{}</text:list-level-style-number>",
+            list_level_properties(level)
+        );
+    }
+    styles.push_str(
+        "</text:list-style></office:styles>\
+         <office:automatic-styles><style:page-layout style:name=\"Letter\">\
+         <style:page-layout-properties fo:page-width=\"8.5in\" fo:page-height=\"11in\" \
+         style:print-orientation=\"portrait\" fo:margin-top=\"1in\" fo:margin-bottom=\"1in\" \
+         fo:margin-left=\"1in\" fo:margin-right=\"1in\"/></style:page-layout>\
+         </office:automatic-styles>\
+         <office:master-styles><style:master-page style:name=\"Standard\" \
+         style:page-layout-name=\"Letter\"/></office:master-styles>\
+         </office:document-styles>",
+    );
+    styles
+}
+
+/// the label of a level hangs in the half inch before its text, like the Word export
+fn list_level_properties(level: usize) -> String {
+    let margin = level as f64 * 0.5;
+    format!(
+        "<style:list-level-properties text:list-level-position-and-space-mode=\"label-alignment\">\
+         <style:list-level-label-alignment text:label-followed-by=\"listtab\" \
+         text:list-tab-stop-position=\"{margin}in\" fo:text-indent=\"-0.25in\" \
+         fo:margin-left=\"{margin}in\"/></style:list-level-properties>"
+    )
+}
+
+/// the attributes of `<style:text-properties>` for the inline formats, links take the look
+/// of the `Internet link` style
+fn text_properties(formats: &[Inline]) -> String {
+    let mut properties = String::new();
+    let font = formats.iter().find_map(|format| match format {
+        Inline::Code => Some("Courier New".to_string()),
+        Inline::Font(font) => Some(font_name(font)),
+        _ => None,
+    });
+    if let Some(font) = font {
+        let font = escape_xml(&font.replace('\'', ""));
+        let _ = write!(properties, " fo:font-family=\"'{font}'\"");
+    }
+    let has = |format: Inline| formats.contains(&format);
+    if has(Inline::Bold) {
+        properties.push_str(" fo:font-weight=\"bold\" style:font-weight-complex=\"bold\"");
+    }
+    if has(Inline::Italic) {
+        properties.push_str(" fo:font-style=\"italic\" style:font-style-complex=\"italic\"");
+    }
+    if has(Inline::Underline) {
+        properties.push_str(
+            " style:text-underline-style=\"solid\" style:text-underline-width=\"auto\" \
+             style:text-underline-color=\"font-color\"",
+        );
+    }
+    if has(Inline::Strike) {
+        properties.push_str(
+            " style:text-line-through-style=\"solid\" style:text-line-through-type=\"single\"",
+        );
+    }
+    for format in formats {
+        match format {
+            Inline::Color(color) => {
+                let _ = write!(
+                    properties,
+                    " fo:color=\"#{:02x}{:02x}{:02x}\"",
+                    color.r, color.g, color.b
+                );
+            }
+            Inline::BgColor(color) => {
+                let _ = write!(
+                    properties,
+                    " fo:background-color=\"#{:02x}{:02x}{:02x}\"",
+                    color.r, color.g, color.b
+                );
+            }
+            Inline::Size(size) => {
+                if let Some(half_points) = half_points(size) {
+                    let _ = write!(
+                        properties,
+                        " fo:font-size=\"{}pt\"",
+                        f64::from(half_points) / 2.0
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+    if has(Inline::Sub) {
+        properties.push_str(" style:text-position=\"sub 58%\"");
+    } else if has(Inline::Sup) {
+        properties.push_str(" style:text-position=\"super 58%\"");
+    }
+    properties
+}
+
+fn hyperlink(url: &str, spans: &str) -> String {
+    format!(
+        "<text:a xlink:type=\"simple\" xlink:href=\"{}\" text:style-name=\"Internet_20_link\" \
+         text:visited-style-name=\"Visited_20_Internet_20_Link\">{spans}</text:a>",
+        escape_xml(url)
+    )
+}
+
+/// escaped text with tabs and the spaces ODF would collapse written as elements: the spaces
+/// following a space, a tab or the start of the line. `space` tells whether the text follows
+/// one of them and is updated for the next text
+fn text_content(text: &str, space: &mut bool) -> String {
+    let mut xml = String::new();
+    // the text not written yet and the spaces following it
+    let mut run = String::new();
+    let mut spaces = 0;
+    let flush = |xml: &mut String, run: &mut String, spaces: &mut usize| {
+        xml.push_str(&escape_xml(run));
+        run.clear();
+        match *spaces {
+            0 => {}
+            1 => xml.push_str("<text:s/>"),
+            count => {
+                let _ = write!(xml, "<text:s text:c=\"{count}\"/>");
+            }
+        }
+        *spaces = 0;
+    };
+    for c in text.chars() {
+        match c {
+            ' ' if *space => spaces += 1,
+            '\t' => {
+                flush(&mut xml, &mut run, &mut spaces);
+                xml.push_str("<text:tab/>");
+                *space = true;
+            }
+            c => {
+                if spaces > 0 {
+                    flush(&mut xml, &mut run, &mut spaces);
+                }
+                run.push(c);
+                *space = c == ' ';
+            }
+        }
+    }
+    flush(&mut xml, &mut run, &mut spaces);
+    xml
+}
+
+/// css pixels at 96 dpi
+fn inches(pixels: f64) -> f64 {
+    (pixels / 96.0 * 10_000.0).round() / 10_000.0
+}
@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+
+use super::{LineFormat, split_block_embeds};
+use crate::{AttributeMap, Delta, Embed, Embeds, Insert, ListKind};
+
+/// one indent level, the width of a `1. ` marker
+const INDENT: &str = "   ";
+
+type PlaceholderFn = Box<dyn Fn(&Embed, &AttributeMap) -> String>;
+
+/// how a delta is written as plain text, see [`Delta::to_plain_text_with`].
+pub struct PlainTextExport {
+    bullet: String,
+    checked: String,
+    unchecked: String,
+    placeholders: HashMap<String, PlaceholderFn>,
+}
+
+impl PlainTextExport {
+    pub fn builder() -> PlainTextExportBuilder {
+        PlainTextExportBuilder::new()
+    }
+    fn placeholder(&self, embed: &Embed, attributes: Option<&AttributeMap>) -> String {
+        let attributes = attributes.cloned().unwrap_or_default();
+        match self.placeholders.get(&embed.kind) {
+            Some(placeholder) => placeholder(embed, &attributes),
+            None => default_placeholder(embed, &attributes),
+        }
+    }
+}
+
+impl Default for PlainTextExport {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+pub struct PlainTextExportBuilder {
+    export: PlainTextExport,
+}
+
+impl PlainTextExportBuilder {
+    pub fn new() -> Self {
+        Self {
+            export: PlainTextExport {
+                bullet: "•".into(),
+                checked: "☑".into(),
+                unchecked: "☐".into(),
+                placeholders: HashMap::new(),
+            },
+        }
+    }
+    /// the marker of bullet items, `•` by default
+    pub fn bullet(mut self, bullet: impl Into<String>) -> Self {
+        self.export.bullet = bullet.into();
+        self
+    }
+    /// the markers of checked and unchecked items, `☑` and `☐` by default, `[x]` and `[ ]`
+    /// keep to ascii
+    pub fn checkboxes(mut self, checked: impl Into<String>, unchecked: impl Into<String>) -> Self {
+        self.export.checked = checked.into();
+        self.export.unchecked = unchecked.into();
+        self
+    }
+    /// the text written in place of the `kind` embeds, from the embed and its attributes.
+    ///
+    /// by default images are `[image: alt]`, videos `[video: url]`, formulas their source and
+    /// other embeds `[kind]`. an empty placeholder leaves the embed out.
+    pub fn embed(
+        mut self,
+        kind: impl Into<String>,
+        placeholder: impl Fn(&Embed, &AttributeMap) -> String + 'static,
+    ) -> Self {
+        self.export
+            .placeholders
+            .insert(kind.into(), Box::new(placeholder));
+        self
+    }
+    pub fn build(self) -> PlainTextExport {
+        self.export
+    }
+}
+
+impl Default for PlainTextExportBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Delta {
+    /// writes this document as plain text, one line per line, keeping its structure: list
+    /// items get their number, bullet or checkbox, nested levels and indented lines are
+    /// indented, quotes start with `> ` and embeds become placeholders like `[image: alt]`.
+    ///
+    /// ordered items are numbered the way Quill displays them: `1.`, `a.`, `i.` by level, an
+    /// item resets the numbering of the deeper levels and any other line restarts the list.
+    ///
+    /// ```
+    /// # use quill_wr::*;
+    /// let doc = delta![
+    ///     "one", "\n" { list: "ordered" },
+    ///     "sub", "\n" { list: "ordered", indent: 1 },
+    ///     "two", "\n" { list: "ordered" },
+    ///     "done", "\n" { list: "checked" },
+    /// ];
+    /// assert_eq!(doc.to_plain_text(), "1. one\n   a. sub\n2. two\n☑ done\n");
+    /// ```
+    pub fn to_plain_text(&self) -> String {
+        self.to_plain_text_with(&PlainTextExport::default())
+    }
+    pub fn to_plain_text_with(&self, export: &PlainTextExport) -> String {
+        let mut text = String::new();
+        // the last number of each open ordered level
+        let mut counters: Vec<usize> = vec![];
+        for line in self.lines() {
+            let (videos, content) = split_block_embeds(&line.content);
+            for op in videos {
+                if let Some(Insert::Embed(embed)) = op.as_insert() {
+                    counters.clear();
+                    write_line(&mut text, "", &export.placeholder(embed, op.attributes()));
+                }
+            }
+            // the newline after a video holds no line of its own
+            if !videos.is_empty() && content.ops().is_empty() {
+                continue;
+            }
+            let format = LineFormat::new(&line.blocks());
+            let mut inline = String::new();
+            for op in content.ops() {
+                match op.as_insert() {
+                    Some(Insert::Text(run)) => inline.push_str(run),
+                    Some(Insert::Embed(embed)) => {
+                        inline.push_str(&export.placeholder(embed, op.attributes()))
+                    }
+                    None => {}
+                }
+            }
+            if format.code.is_some() {
+                counters.clear();
+                write_line(&mut text, "", &inline);
+                continue;
+            }
+            let level = usize::from(format.indent);
+            let indent = INDENT.repeat(level);
+            let Some(kind) = format.list else {
+                counters.clear();
+                let quote = if format.blockquote { "> " } else { "" };
+                write_line(&mut text, &format!("{indent}{quote}"), &inline);
+                continue;
+            };
+            counters.resize(level + 1, 0);
+            let marker = match kind {
+                ListKind::Ordered => {
+                    counters[level] += 1;
+                    list_number(counters[level], level)
+                }
+                ListKind::Bullet => export.bullet.clone(),
+                ListKind::Checked => export.checked.clone(),
+                ListKind::Unchecked => export.unchecked.clone(),
+            };
+            write_line(&mut text, &format!("{indent}{marker} "), &inline);
+        }
+        text
+    }
+}
+
+/// the prefix of an empty line loses its trailing space
+fn write_line(text: &mut String, prefix: &str, inline: &str) {
+    if inline.is_empty() {
+        text.push_str(prefix.trim_end_matches(' '));
+    } else {
+        text.push_str(prefix);
+        text.push_str(inline);
+    }
+    text.push('\n');
+}
+
+fn default_placeholder(embed: &Embed, attributes: &AttributeMap) -> String {
+    match Embeds::from(embed.clone()) {
+        Embeds::Image(_) => match attributes.get("alt").and_then(|alt| alt.as_str()) {
+            Some(alt) if !alt.is_empty() => format!("[image: {alt}]"),
+            _ => "[image]".into(),
+        },
+        Embeds::Video(url) => format!("[video: {url}]"),
+        Embeds::Formula(formula) => formula,
+        Embeds::Custom(kind, _) => format!("[{kind}]"),
+    }
+}
+
+/// the marker of an ordered item, the counter styles of Quill's nested lists: decimal, then
+/// lower-alpha, then lower-roman, repeating
+fn list_number(number: usize, level: usize) -> String {
+    let number = match level % 3 {
+        0 => number.to_string(),
+        1 => lower_alpha(number),
+        _ => lower_roman(number),
+    };
+    format!("{number}.")
+}
+
+/// `a`..`z`, then `aa`, `ab`...
+fn lower_alpha(mut number: usize) -> String {
+    let mut letters = vec![];
+    while number > 0 {
+        number -= 1;
+        letters.push(char::from(b'a' + (number % 26) as u8));
+        number /= 26;
+    }
+    letters.iter().rev().collect()
+}
+
+/// css falls back to decimal past 3999
+fn lower_roman(mut number: usize) -> String {
+    if number >= 4000 {
+        return number.to_string();
+    }
+    const NUMERALS: [(usize, &str); 13] = [
+        (1000, "m"),
+        (900, "cm"),
+        (500, "d"),
+        (400, "cd"),
+        (100, "c"),
+        (90, "xc"),
+        (50, "l"),
+        (40, "xl"),
+        (10, "x"),
+        (9, "ix"),
+        (5, "v"),
+        (4, "iv"),
+        (1, "i"),
+    ];
+    let mut roman = String::new();
+    for (value, numeral) in NUMERALS {
+        while number >= value {
+            roman.push_str(numeral);
+            number -= value;
+        }
+    }
+    roman
+}
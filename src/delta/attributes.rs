@@ -0,0 +1,100 @@
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use crate::{Block, EmbedAttr, Inline};
+
+/// format name -> format value, exactly as it appears under `"attributes"` in Quill's json.
+///
+/// a `null` value means "remove this format" when the map belongs to a retain.
+pub type AttributeMap = BTreeMap<String, Value>;
+
+pub fn inlines_to_attributes(attrs: &[Inline]) -> AttributeMap {
+    attrs.iter().map(|a| a.as_kv()).collect()
+}
+
+pub fn blocks_to_attributes(attrs: &[Block]) -> AttributeMap {
+    attrs.iter().map(|a| a.as_kv()).collect()
+}
+
+pub fn embed_attrs_to_attributes(attrs: &[EmbedAttr]) -> AttributeMap {
+    attrs.iter().map(|a| a.as_kv()).collect()
+}
+
+/// merges `b` on top of `a`, like quill-delta's `AttributeMap.compose`.
+///
+/// `null` values in `b` are kept only when `keep_null` is set (i.e. the result is still a retain),
+/// otherwise they just remove the format. returns `None` when nothing is left.
+pub fn compose_attributes(
+    a: Option<&AttributeMap>,
+    b: Option<&AttributeMap>,
+    keep_null: bool,
+) -> Option<AttributeMap> {
+    let mut attributes: AttributeMap = b
+        .into_iter()
+        .flatten()
+        .filter(|(_, v)| keep_null || !v.is_null())
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    for (k, v) in a.into_iter().flatten() {
+        if !b.is_some_and(|b| b.contains_key(k)) {
+            attributes.insert(k.clone(), v.clone());
+        }
+    }
+    (!attributes.is_empty()).then_some(attributes)
+}
+
+/// transforms `b` against `a` that happened concurrently, like quill-delta's `AttributeMap.transform`.
+///
+/// with `priority` the formats already set by `a` win and are dropped from `b`.
+pub fn transform_attributes(
+    a: Option<&AttributeMap>,
+    b: Option<&AttributeMap>,
+    priority: bool,
+) -> Option<AttributeMap> {
+    let (Some(a), b) = (a, b) else {
+        return b.cloned();
+    };
+    let b = b?;
+    if !priority {
+        return Some(b.clone());
+    }
+    let attributes: AttributeMap = b
+        .iter()
+        .filter(|(k, _)| !a.contains_key(*k))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    (!attributes.is_empty()).then_some(attributes)
+}
+
+/// the formats needed to undo `attributes` applied on top of `base`, like quill-delta's
+/// `AttributeMap.invert`. formats `base` did not have come back as `null`.
+pub fn invert_attributes(
+    attributes: Option<&AttributeMap>,
+    base: Option<&AttributeMap>,
+) -> AttributeMap {
+    let empty = AttributeMap::new();
+    let (attributes, base) = (attributes.unwrap_or(&empty), base.unwrap_or(&empty));
+    let mut inverted: AttributeMap = base
+        .iter()
+        .filter(|(k, v)| attributes.get(*k).is_some_and(|a| a != *v))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    for k in attributes.keys() {
+        if !base.contains_key(k) {
+            inverted.insert(k.clone(), Value::Null);
+        }
+    }
+    inverted
+}
+
+/// the retain attributes turning `a` into `b`, like quill-delta's `AttributeMap.diff`.
+/// formats missing from `b` come back as `null`.
+pub fn diff_attributes(a: Option<&AttributeMap>, b: Option<&AttributeMap>) -> AttributeMap {
+    let empty = AttributeMap::new();
+    let (a, b) = (a.unwrap_or(&empty), b.unwrap_or(&empty));
+    a.keys()
+        .chain(b.keys())
+        .filter(|k| a.get(*k) != b.get(*k))
+        .map(|k| (k.clone(), b.get(k).cloned().unwrap_or(Value::Null)))
+        .collect()
+}
@@ -0,0 +1,75 @@
+use super::{Delta, Op, OpIterator, compose_attributes};
+use crate::Utf16Index;
+
+impl Delta {
+    /// returns a delta equivalent to applying `self` and then `other`.
+    ///
+    /// follows quill-delta's `compose` exactly, including how attributes merge and
+    /// how a `null` attribute removes a format.
+    pub fn compose(&self, other: &Delta) -> Delta {
+        let mut this_iter = OpIterator::new(self.ops());
+        let mut other_iter = OpIterator::new(other.ops());
+        let mut ops = vec![];
+
+        // fast path: a leading plain retain in `other` keeps our leading inserts untouched
+        if let Some(Op::Retain {
+            retain,
+            attributes: None,
+        }) = other_iter.peek()
+        {
+            let mut first_left = *retain;
+            while this_iter.peek_is_insert() && this_iter.peek_length() <= first_left {
+                first_left -= this_iter.peek_length();
+                ops.push(this_iter.next(Utf16Index::MAX));
+            }
+            if *retain > first_left {
+                other_iter.next(*retain - first_left);
+            }
+        }
+
+        let mut delta = Delta::from_ops(ops);
+        while this_iter.has_next() || other_iter.has_next() {
+            if other_iter.peek_is_insert() {
+                delta.push(other_iter.next(Utf16Index::MAX));
+            } else if this_iter.peek_is_delete() {
+                delta.push(this_iter.next(Utf16Index::MAX));
+            } else {
+                let length = this_iter.peek_length().min(other_iter.peek_length());
+                let this_op = this_iter.next(length);
+                let other_op = other_iter.next(length);
+                match (this_op, other_op) {
+                    (this_op, Op::Retain { attributes, .. }) => {
+                        let keep_null = this_op.is_retain();
+                        let attributes = compose_attributes(
+                            this_op.attributes(),
+                            attributes.as_ref(),
+                            keep_null,
+                        );
+                        let new_op = match this_op {
+                            Op::Insert { insert, .. } => Op::Insert { insert, attributes },
+                            _ => Op::Retain {
+                                retain: length,
+                                attributes,
+                            },
+                        };
+                        delta.push(new_op.clone());
+                        // the rest of `other` is just retain, copy the rest of `self` as is
+                        if !other_iter.has_next() && delta.ops().last() == Some(&new_op) {
+                            let rest = Delta::from_ops(this_iter.rest());
+                            let mut delta = delta.concat(&rest);
+                            delta.chop();
+                            return delta;
+                        }
+                    }
+                    (Op::Retain { .. }, other_op @ Op::Delete { .. }) => {
+                        delta.push(other_op);
+                    }
+                    // `other` deletes something `self` inserted, both cancel out
+                    _ => {}
+                }
+            }
+        }
+        delta.chop();
+        delta
+    }
+}
@@ -0,0 +1,102 @@
+use similar::{ChangeTag, TextDiff};
+
+use super::{Delta, Insert, Op, OpIterator, diff_attributes};
+use crate::Utf16Index;
+
+/// embeds take part in the text diff as this single placeholder, like quill-delta does
+const EMBED_TEXT: &str = "\0";
+
+/// the unit two documents are compared in by [`Delta::diff_with`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DiffGranularity {
+    /// smallest possible change, like quill-delta's `diff`
+    #[default]
+    Char,
+    /// whole words and whitespace runs are replaced, which reads better when comparing revisions
+    Word,
+}
+
+impl Delta {
+    /// character level diff, see [`Delta::diff_with`]
+    pub fn diff(&self, other: &Delta) -> Option<Delta> {
+        self.diff_with(other, DiffGranularity::Char)
+    }
+
+    /// returns the change turning the document `self` into the document `other`,
+    /// covering both text and attribute changes.
+    ///
+    /// returns `None` when either delta is not a document (contains a retain or delete).
+    pub fn diff_with(&self, other: &Delta, granularity: DiffGranularity) -> Option<Delta> {
+        if self.ops() == other.ops() {
+            return Some(Delta::new());
+        }
+        let (old, new) = (self.diff_text()?, other.diff_text()?);
+        let text_diff = match granularity {
+            DiffGranularity::Char => TextDiff::from_chars(&old, &new),
+            DiffGranularity::Word => TextDiff::from_words(&old, &new),
+        };
+
+        let mut delta = Delta::new();
+        let mut this_iter = OpIterator::new(self.ops());
+        let mut other_iter = OpIterator::new(other.ops());
+        for change in text_diff.iter_all_changes() {
+            let mut length = Utf16Index::len_of(change.value());
+            while length > Utf16Index::ZERO {
+                let op_length = match change.tag() {
+                    ChangeTag::Insert => {
+                        let op_length = other_iter.peek_length().min(length);
+                        delta.push(other_iter.next(op_length));
+                        op_length
+                    }
+                    ChangeTag::Delete => {
+                        let op_length = this_iter.peek_length().min(length);
+                        this_iter.next(op_length);
+                        delta.delete(op_length);
+                        op_length
+                    }
+                    ChangeTag::Equal => {
+                        let op_length = this_iter
+                            .peek_length()
+                            .min(other_iter.peek_length())
+                            .min(length);
+                        let this_op = this_iter.next(op_length);
+                        let other_op = other_iter.next(op_length);
+                        match (&this_op, &other_op) {
+                            (Op::Insert { insert: a, .. }, Op::Insert { insert: b, .. })
+                                if a == b =>
+                            {
+                                delta.retain(
+                                    op_length,
+                                    diff_attributes(this_op.attributes(), other_op.attributes()),
+                                );
+                            }
+                            // two different embeds
+                            _ => {
+                                delta.push(other_op).delete(op_length);
+                            }
+                        }
+                        op_length
+                    }
+                };
+                length -= op_length;
+            }
+        }
+        delta.chop();
+        Some(delta)
+    }
+
+    /// the document text with every embed replaced by [`EMBED_TEXT`], `None` for non documents
+    fn diff_text(&self) -> Option<String> {
+        self.ops()
+            .iter()
+            .map(|op| match op {
+                Op::Insert {
+                    insert: Insert::Text(text),
+                    ..
+                } => Some(text.as_str()),
+                Op::Insert { .. } => Some(EMBED_TEXT),
+                _ => None,
+            })
+            .collect()
+    }
+}
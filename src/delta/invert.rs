@@ -0,0 +1,43 @@
+use super::{AttributeMap, Delta, Op, invert_attributes};
+use crate::Utf16Index;
+
+impl Delta {
+    /// returns the change that undoes `self` once it has been applied to `base`.
+    ///
+    /// deleted content is restored with the attributes it had in `base`, and formats set by a
+    /// retain go back to their previous values, so `base.compose(self).compose(inverted) == base`.
+    pub fn invert(&self, base: &Delta) -> Delta {
+        let mut inverted = Delta::new();
+        let mut base_index = Utf16Index::ZERO;
+        for op in self.ops() {
+            match op {
+                Op::Insert { .. } => {
+                    inverted.delete(op.len());
+                }
+                Op::Retain {
+                    retain,
+                    attributes: None,
+                } => {
+                    inverted.retain(*retain, AttributeMap::new());
+                    base_index = base_index.saturating_add(*retain);
+                }
+                Op::Delete { delete: length } | Op::Retain { retain: length, .. } => {
+                    let slice = base.slice(base_index, base_index.saturating_add(*length));
+                    for base_op in slice.into_ops() {
+                        if op.is_delete() {
+                            inverted.push(base_op);
+                        } else {
+                            inverted.retain(
+                                base_op.len(),
+                                invert_attributes(op.attributes(), base_op.attributes()),
+                            );
+                        }
+                    }
+                    base_index = base_index.saturating_add(*length);
+                }
+            }
+        }
+        inverted.chop();
+        inverted
+    }
+}
@@ -0,0 +1,98 @@
+use super::{Insert, Op};
+use crate::{Utf16Index, utf16_slice};
+
+/// walks a list of ops while letting the caller consume them in arbitrary sized chunks,
+/// the same cursor quill-delta uses internally for compose/transform.
+///
+/// once exhausted it keeps yielding an infinite retain ([`Utf16Index::MAX`]).
+#[derive(Debug, Clone)]
+pub struct OpIterator<'a> {
+    ops: &'a [Op],
+    index: usize,
+    /// offset inside `ops[index]`
+    offset: Utf16Index,
+}
+
+impl<'a> OpIterator<'a> {
+    pub fn new(ops: &'a [Op]) -> Self {
+        Self {
+            ops,
+            index: 0,
+            offset: Utf16Index::ZERO,
+        }
+    }
+    pub fn has_next(&self) -> bool {
+        self.peek_length() < Utf16Index::MAX
+    }
+    pub fn peek(&self) -> Option<&'a Op> {
+        self.ops.get(self.index)
+    }
+    /// remaining length of the current op, [`Utf16Index::MAX`] when exhausted
+    pub fn peek_length(&self) -> Utf16Index {
+        self.peek()
+            .map(|op| op.len() - self.offset)
+            .unwrap_or(Utf16Index::MAX)
+    }
+    pub fn peek_is_insert(&self) -> bool {
+        self.peek().is_some_and(Op::is_insert)
+    }
+    pub fn peek_is_delete(&self) -> bool {
+        self.peek().is_some_and(Op::is_delete)
+    }
+    /// an exhausted iterator counts as a retain
+    pub fn peek_is_retain(&self) -> bool {
+        !self.peek_is_insert() && !self.peek_is_delete()
+    }
+
+    /// consumes at most `length` of the current op, pass [`Utf16Index::MAX`] to take all of it.
+    pub fn next(&mut self, length: Utf16Index) -> Op {
+        let Some(op) = self.ops.get(self.index) else {
+            return Op::Retain {
+                retain: Utf16Index::MAX,
+                attributes: None,
+            };
+        };
+        let offset = self.offset;
+        let op_length = op.len();
+        let length = if length >= op_length - offset {
+            self.index += 1;
+            self.offset = Utf16Index::ZERO;
+            op_length - offset
+        } else {
+            self.offset += length;
+            length
+        };
+        match op {
+            Op::Delete { .. } => Op::Delete { delete: length },
+            Op::Retain { attributes, .. } => Op::Retain {
+                retain: length,
+                attributes: attributes.clone(),
+            },
+            Op::Insert { insert, attributes } => Op::Insert {
+                insert: match insert {
+                    Insert::Text(text) => {
+                        Insert::Text(utf16_slice(text, offset, offset + length).to_string())
+                    }
+                    embed => embed.clone(),
+                },
+                attributes: attributes.clone(),
+            },
+        }
+    }
+
+    /// everything not consumed yet, without advancing the iterator.
+    pub fn rest(&mut self) -> Vec<Op> {
+        if !self.has_next() {
+            return vec![];
+        }
+        if self.offset == Utf16Index::ZERO {
+            return self.ops[self.index..].to_vec();
+        }
+        let (index, offset) = (self.index, self.offset);
+        let mut rest = vec![self.next(Utf16Index::MAX)];
+        rest.extend_from_slice(&self.ops[self.index..]);
+        self.index = index;
+        self.offset = offset;
+        rest
+    }
+}
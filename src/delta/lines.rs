@@ -0,0 +1,129 @@
+use super::{AttributeMap, Delta, Insert, Op, OpIterator};
+use crate::{Block, Formats, Utf16Index, utf16_to_byte};
+
+/// one line of a document, as yielded by [`Delta::lines`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Line {
+    /// the inline runs of the line, without its trailing `"\n"`
+    pub content: Delta,
+    /// block formats (header, list, align, ...) Quill stores on the trailing `"\n"`
+    pub attributes: AttributeMap,
+    /// offset of the line start inside the document
+    pub index: Utf16Index,
+    /// `false` for trailing text without a `"\n"`, which only deltas that are not documents have
+    pub newline: bool,
+}
+
+impl Line {
+    /// the line text, embeds are skipped
+    pub fn text(&self) -> String {
+        self.content
+            .ops()
+            .iter()
+            .filter_map(|op| match op {
+                Op::Insert {
+                    insert: Insert::Text(text),
+                    ..
+                } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+    /// the typed line formats, see [`Block`]
+    pub fn blocks(&self) -> Vec<Block> {
+        Formats::from_kvs(self.attributes.clone()).blocks
+    }
+    /// length including the trailing newline, when there is one
+    pub fn length(&self) -> Utf16Index {
+        self.content.length() + Utf16Index(self.newline.into())
+    }
+}
+
+/// iterator over the lines of a document, see [`Delta::lines`].
+#[derive(Debug, Clone)]
+pub struct Lines<'a> {
+    iter: OpIterator<'a>,
+    index: Utf16Index,
+    done: bool,
+}
+
+impl Iterator for Lines<'_> {
+    type Item = Line;
+
+    fn next(&mut self) -> Option<Line> {
+        if self.done {
+            return None;
+        }
+        let start = self.index;
+        let mut content = Delta::new();
+        while let Some(op) = self.iter.peek() {
+            // like quill-delta, stop at the first op that is not an insert
+            let Op::Insert { insert, .. } = op else {
+                self.done = true;
+                return None;
+            };
+            let offset = op.len() - self.iter.peek_length();
+            let newline = match insert {
+                Insert::Text(text) => {
+                    let rest = &text[utf16_to_byte(text, offset)..];
+                    rest.find('\n')
+                        .map(|byte| Utf16Index::len_of(&rest[..byte]))
+                }
+                Insert::Embed(_) => None,
+            };
+            match newline {
+                Some(Utf16Index::ZERO) => {
+                    let newline = self.iter.next(Utf16Index(1));
+                    self.index += Utf16Index(1);
+                    return Some(Line {
+                        content,
+                        attributes: newline.attributes().cloned().unwrap_or_default(),
+                        index: start,
+                        newline: true,
+                    });
+                }
+                Some(length) => {
+                    content.push(self.iter.next(length));
+                    self.index += length;
+                }
+                None => {
+                    let op = self.iter.next(Utf16Index::MAX);
+                    self.index += op.len();
+                    content.push(op);
+                }
+            }
+        }
+        // trailing text without a newline still forms a line, without block formats
+        self.done = true;
+        (content.length() > Utf16Index::ZERO).then(|| Line {
+            content,
+            attributes: AttributeMap::new(),
+            index: start,
+            newline: false,
+        })
+    }
+}
+
+impl Delta {
+    /// iterates over the lines of a document, yielding each line's inline runs together with
+    /// the block attributes stored on its trailing `"\n"`.
+    ///
+    /// iteration stops at the first retain or delete, like quill-delta's `eachLine`.
+    pub fn lines(&self) -> Lines<'_> {
+        Lines {
+            iter: OpIterator::new(self.ops()),
+            index: Utf16Index::ZERO,
+            done: false,
+        }
+    }
+
+    /// calls `predicate(content, block_attributes, line_number)` for every line,
+    /// stopping as soon as it returns `false`.
+    pub fn each_line(&self, mut predicate: impl FnMut(&Delta, &AttributeMap, usize) -> bool) {
+        for (i, line) in self.lines().enumerate() {
+            if !predicate(&line.content, &line.attributes, i) {
+                return;
+            }
+        }
+    }
+}
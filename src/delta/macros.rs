@@ -0,0 +1,97 @@
+#[doc(hidden)]
+pub use serde_json::json as __json;
+
+/// builds a [`Delta`](crate::Delta) from a compact list of ops, merging them like
+/// [`Delta::push`](crate::Delta::push) does.
+///
+/// - `"text"` or `"text" { bold: true, color: "red" }` inserts text, use `insert(expr)` for
+///   text that is not a literal
+/// - `{ image: "a.png" }` or `{ image: "a.png" } { width: "120" }` inserts an embed
+/// - `retain(5)` or `retain(5) { bold: null }` retains, `null` removes a format
+/// - `delete(3)` deletes
+///
+/// attribute and embed values are anything [`json!`](serde_json::json) accepts, keys that are
+/// not identifiers (`"code-block"`) are written as string literals.
+///
+/// ```
+/// # use quill_wr::*;
+/// let doc = delta![
+///     "Gandalf" { bold: true },
+///     " the Grey",
+///     "\n" { header: 1 },
+///     { image: "gandalf.png" } { alt: "a wizard" },
+///     "\n",
+/// ];
+/// assert_eq!(doc.ops().len(), 5);
+///
+/// let change = delta![retain(7) { bold: null }, delete(4), insert("White")];
+/// assert_eq!(change.change_length(), 1);
+/// ```
+#[macro_export]
+macro_rules! delta {
+    // ops
+    (@ops $delta:ident ()) => {};
+    (@ops $delta:ident (retain($length:expr) $({ $($attrs:tt)* })? $(, $($rest:tt)*)?)) => {
+        $delta.retain($length, $crate::delta!(@attrs $($($attrs)*)?));
+        $crate::delta!(@ops $delta ($($($rest)*)?));
+    };
+    (@ops $delta:ident (delete($length:expr) $(, $($rest:tt)*)?)) => {
+        $delta.delete($length);
+        $crate::delta!(@ops $delta ($($($rest)*)?));
+    };
+    (@ops $delta:ident (insert($text:expr) $({ $($attrs:tt)* })? $(, $($rest:tt)*)?)) => {
+        $delta.insert($text, $crate::delta!(@attrs $($($attrs)*)?));
+        $crate::delta!(@ops $delta ($($($rest)*)?));
+    };
+    (@ops $delta:ident ($text:literal $({ $($attrs:tt)* })? $(, $($rest:tt)*)?)) => {
+        $delta.insert($text, $crate::delta!(@attrs $($($attrs)*)?));
+        $crate::delta!(@ops $delta ($($($rest)*)?));
+    };
+    (@ops $delta:ident ({ $kind:tt : $($value:tt)+ } $({ $($attrs:tt)* })? $(, $($rest:tt)*)?)) => {
+        $delta.insert(
+            $crate::Embed::new($crate::delta!(@key $kind), $crate::__json!($($value)+)),
+            $crate::delta!(@attrs $($($attrs)*)?),
+        );
+        $crate::delta!(@ops $delta ($($($rest)*)?));
+    };
+    (@ops $delta:ident ($($rest:tt)*)) => {
+        compile_error!(concat!("delta!: unexpected op `", stringify!($($rest)*), "`"))
+    };
+
+    // attributes, munched one token at a time so values may hold commas inside brackets
+    (@attrs $($attrs:tt)*) => {{
+        #[allow(unused_mut)]
+        let mut attributes = $crate::AttributeMap::new();
+        $crate::delta!(@attr attributes ($($attrs)*));
+        attributes
+    }};
+    (@attr $map:ident ()) => {};
+    (@attr $map:ident ($key:tt : $($rest:tt)*)) => {
+        $crate::delta!(@value $map $key [] ($($rest)*));
+    };
+    (@value $map:ident $key:tt [$($value:tt)+] ($(, $($rest:tt)*)?)) => {
+        $map.insert(
+            ::std::string::String::from($crate::delta!(@key $key)),
+            $crate::__json!($($value)+),
+        );
+        $crate::delta!(@attr $map ($($($rest)*)?));
+    };
+    (@value $map:ident $key:tt [$($value:tt)*] ($next:tt $($rest:tt)*)) => {
+        $crate::delta!(@value $map $key [$($value)* $next] ($($rest)*));
+    };
+    (@value $map:ident $key:tt [] $($rest:tt)*) => {
+        compile_error!(concat!("delta!: missing value for `", stringify!($key), "`"))
+    };
+    (@key $key:ident) => {
+        stringify!($key)
+    };
+    (@key $key:literal) => {
+        $key
+    };
+
+    ($($ops:tt)*) => {{
+        let mut delta = $crate::Delta::new();
+        $crate::delta!(@ops delta ($($ops)*));
+        delta
+    }};
+}
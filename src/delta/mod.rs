@@ -0,0 +1,22 @@
+mod attributes;
+pub use attributes::*;
+mod op;
+pub use op::*;
+mod iter;
+pub use iter::*;
+mod model;
+pub use model::*;
+mod compose;
+mod diff;
+pub use diff::*;
+mod invert;
+mod lines;
+pub use lines::*;
+mod macros;
+pub use macros::*;
+mod normalize;
+mod sanitize;
+pub use sanitize::*;
+mod schema;
+pub use schema::*;
+mod transform;
@@ -0,0 +1,210 @@
+use serde::{Deserialize, Serialize};
+
+use super::{
+    AttributeMap, Embed, Insert, Op, OpIterator, blocks_to_attributes, embed_attrs_to_attributes,
+    inlines_to_attributes,
+};
+use crate::{Block, EmbedAttr, Embeds, Inline, Utf16Index};
+
+/// native, pure-rust counterpart of quill-delta's `Delta`.
+///
+/// (de)serializes to the same `{"ops": [...]}` json Quill emits, so stored documents and
+/// change sets can be handled without a browser. see [`DeltaEditor`](crate::DeltaEditor) for
+/// the js backed handle and the conversions between the two.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Delta {
+    ops: Vec<Op>,
+}
+
+impl Delta {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// takes the ops as they are, without merging them
+    pub fn from_ops(ops: Vec<Op>) -> Self {
+        Self { ops }
+    }
+    pub fn ops(&self) -> &[Op] {
+        &self.ops
+    }
+    pub fn into_ops(self) -> Vec<Op> {
+        self.ops
+    }
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// appends an op, merging it into the previous one when possible.
+    ///
+    /// mirrors quill-delta's `push`: consecutive deletes, and inserts/retains with equal
+    /// attributes are merged, and an insert is always placed before an adjacent delete.
+    /// merged lengths saturate at [`Utf16Index::MAX`].
+    pub fn push(&mut self, op: Op) -> &mut Self {
+        let mut index = self.ops.len();
+        if let Some(last) = self.ops.last_mut() {
+            if let (Op::Delete { delete: last }, Op::Delete { delete }) = (&mut *last, &op) {
+                *last = last.saturating_add(*delete);
+                return self;
+            }
+            // inserting before or after a delete at the same index is equivalent,
+            // always prefer the insert first
+            if last.is_delete() && op.is_insert() {
+                index -= 1;
+                if index == 0 {
+                    self.ops.insert(0, op);
+                    return self;
+                }
+            }
+            let last = &mut self.ops[index - 1];
+            if last.attributes() == op.attributes() {
+                match (last, &op) {
+                    (
+                        Op::Insert {
+                            insert: Insert::Text(last),
+                            ..
+                        },
+                        Op::Insert {
+                            insert: Insert::Text(text),
+                            ..
+                        },
+                    ) => {
+                        last.push_str(text);
+                        return self;
+                    }
+                    (Op::Retain { retain: last, .. }, Op::Retain { retain, .. }) => {
+                        *last = last.saturating_add(*retain);
+                        return self;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        self.ops.insert(index, op);
+        self
+    }
+
+    /// inserting empty text is a no-op
+    pub fn insert(&mut self, insert: impl Into<Insert>, attributes: AttributeMap) -> &mut Self {
+        let insert = insert.into();
+        if insert.is_empty() {
+            return self;
+        }
+        self.push(Op::insert(insert, attributes))
+    }
+    pub fn insert_text(&mut self, text: &str, attributes: &[Inline]) -> &mut Self {
+        self.insert(text, inlines_to_attributes(attributes))
+    }
+    /// ends the current line with the given line formats
+    pub fn insert_newline(&mut self, formats: &[Block]) -> &mut Self {
+        self.insert("\n", blocks_to_attributes(formats))
+    }
+    pub fn insert_embed(&mut self, embed: Embeds, attributes: &[EmbedAttr]) -> &mut Self {
+        self.insert(Embed::from(embed), embed_attrs_to_attributes(attributes))
+    }
+    pub fn delete(&mut self, length: impl Into<Utf16Index>) -> &mut Self {
+        let length = length.into();
+        if length == Utf16Index::ZERO {
+            return self;
+        }
+        self.push(Op::delete(length))
+    }
+    /// a `null` attribute value removes that format from the retained range
+    pub fn retain(&mut self, length: impl Into<Utf16Index>, attributes: AttributeMap) -> &mut Self {
+        let length = length.into();
+        if length == Utf16Index::ZERO {
+            return self;
+        }
+        self.push(Op::retain(length, attributes))
+    }
+    pub fn retain_with(
+        &mut self,
+        length: impl Into<Utf16Index>,
+        attributes: &[Inline],
+    ) -> &mut Self {
+        self.retain(length, inlines_to_attributes(attributes))
+    }
+
+    /// drops a trailing retain without attributes, which has no effect.
+    pub fn chop(&mut self) -> &mut Self {
+        if let Some(Op::Retain {
+            attributes: None, ..
+        }) = self.ops.last()
+        {
+            self.ops.pop();
+        }
+        self
+    }
+
+    /// total length of all ops, saturating at [`Utf16Index::MAX`]
+    pub fn length(&self) -> Utf16Index {
+        self.ops
+            .iter()
+            .map(Op::len)
+            .fold(Utf16Index::ZERO, Utf16Index::saturating_add)
+    }
+    /// length difference this change applies to a document
+    pub fn change_length(&self) -> isize {
+        self.ops
+            .iter()
+            .map(|op| match op {
+                Op::Insert { .. } => op.len().get() as isize,
+                Op::Delete { delete } => -(delete.get() as isize),
+                Op::Retain { .. } => 0,
+            })
+            .sum()
+    }
+    /// true when the delta contains only inserts, i.e. it describes a whole document
+    pub fn is_document(&self) -> bool {
+        self.ops.iter().all(Op::is_insert)
+    }
+
+    /// copies the ops covering `start..end`, pass [`Utf16Index::MAX`] as `end` for the rest.
+    pub fn slice(&self, start: impl Into<Utf16Index>, end: impl Into<Utf16Index>) -> Delta {
+        let (start, end) = (start.into(), end.into());
+        let mut ops = vec![];
+        let mut iter = OpIterator::new(&self.ops);
+        let mut index = Utf16Index::ZERO;
+        while index < end && iter.has_next() {
+            let op = if index < start {
+                iter.next(start - index)
+            } else {
+                let op = iter.next(end - index);
+                ops.push(op.clone());
+                op
+            };
+            index += op.len();
+        }
+        Delta::from_ops(ops)
+    }
+
+    /// returns a new delta holding `self` followed by `other`, merging at the seam.
+    pub fn concat(&self, other: &Delta) -> Delta {
+        let mut delta = self.clone();
+        let mut rest = other.ops.iter().cloned();
+        if let Some(first) = rest.next() {
+            delta.push(first);
+            delta.ops.extend(rest);
+        }
+        delta
+    }
+}
+
+impl From<Vec<Op>> for Delta {
+    fn from(ops: Vec<Op>) -> Self {
+        Self::from_ops(ops)
+    }
+}
+
+impl FromIterator<Op> for Delta {
+    /// pushes every op, merging where possible
+    fn from_iter<T: IntoIterator<Item = Op>>(iter: T) -> Self {
+        let mut delta = Delta::new();
+        for op in iter {
+            delta.push(op);
+        }
+        delta
+    }
+}
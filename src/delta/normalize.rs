@@ -0,0 +1,52 @@
+use super::{Delta, Insert, Op};
+
+impl Delta {
+    /// rewrites the delta into its canonical form, see [`Delta::normalized`]
+    pub fn normalize(&mut self) -> &mut Self {
+        *self = self.normalized();
+        self
+    }
+
+    /// returns the canonical form of this delta.
+    ///
+    /// empty ops and empty attribute maps are dropped, adjacent ops with equal attributes are
+    /// merged, inserts are moved before adjacent deletes and trailing plain retains are removed.
+    /// attribute keys are always sorted ([`AttributeMap`](super::AttributeMap) is ordered) and so
+    /// are the keys of nested json objects, so two equal documents serialize byte-identically.
+    pub fn normalized(&self) -> Delta {
+        let mut delta: Delta = self
+            .ops()
+            .iter()
+            .filter(|op| !op.is_empty())
+            .cloned()
+            .map(|mut op| {
+                if let Op::Insert { attributes, .. } | Op::Retain { attributes, .. } = &mut op {
+                    if attributes.as_ref().is_some_and(|a| a.is_empty()) {
+                        *attributes = None;
+                    }
+                    for value in attributes.iter_mut().flat_map(|a| a.values_mut()) {
+                        value.sort_all_objects();
+                    }
+                }
+                if let Op::Insert {
+                    insert: Insert::Embed(embed),
+                    ..
+                } = &mut op
+                {
+                    embed.value.sort_all_objects();
+                }
+                op
+            })
+            .collect();
+        while matches!(
+            delta.ops().last(),
+            Some(Op::Retain {
+                attributes: None,
+                ..
+            })
+        ) {
+            delta.chop();
+        }
+        delta
+    }
+}
@@ -0,0 +1,217 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error as _, ser::SerializeMap};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use super::AttributeMap;
+use crate::{CustomEmbed, Embeds, Utf16Index};
+
+/// a single embed insert such as `{"image": "https://..."}`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Embed {
+    /// the blot name (`image`, `video`, `formula`, ...)
+    pub kind: String,
+    pub value: Value,
+}
+
+impl Embed {
+    pub fn new(kind: impl Into<String>, value: impl Into<Value>) -> Self {
+        Self {
+            kind: kind.into(),
+            value: value.into(),
+        }
+    }
+    /// `None` when this is not a `T` embed or its value does not deserialize into `T`
+    pub fn to_custom<T: CustomEmbed>(&self) -> Option<T> {
+        if self.kind != T::NAME {
+            return None;
+        }
+        serde_json::from_value(self.value.clone()).ok()
+    }
+}
+
+impl From<Embeds> for Embed {
+    fn from(embed: Embeds) -> Self {
+        Self::new(embed.get_type_name(), embed.get_value())
+    }
+}
+
+impl From<Embed> for Embeds {
+    /// embeds other than a string `image`, `video` or `formula` are kept as [`Embeds::Custom`]
+    fn from(embed: Embed) -> Self {
+        match (embed.kind.as_str(), embed.value) {
+            ("image", Value::String(v)) => Embeds::Image(v),
+            ("video", Value::String(v)) => Embeds::Video(v),
+            ("formula", Value::String(v)) => Embeds::Formula(v),
+            (_, value) => Embeds::Custom(embed.kind, value),
+        }
+    }
+}
+
+impl Serialize for Embed {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry(&self.kind, &self.value)?;
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Embed {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let map = BTreeMap::<String, Value>::deserialize(deserializer)?;
+        if map.len() != 1 {
+            return Err(D::Error::custom("an embed must have exactly one key"));
+        }
+        let (kind, value) = map.into_iter().next().unwrap();
+        Ok(Self { kind, value })
+    }
+}
+
+/// the value carried by an insert op: either a run of text or a single embed.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Insert {
+    Text(String),
+    Embed(Embed),
+}
+
+impl Insert {
+    /// embeds always count as 1
+    pub fn len(&self) -> Utf16Index {
+        match self {
+            Self::Text(text) => Utf16Index::len_of(text),
+            Self::Embed(_) => Utf16Index(1),
+        }
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == Utf16Index::ZERO
+    }
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            Self::Text(text) => Some(text),
+            Self::Embed(_) => None,
+        }
+    }
+    pub fn as_embed(&self) -> Option<&Embed> {
+        match self {
+            Self::Embed(embed) => Some(embed),
+            Self::Text(_) => None,
+        }
+    }
+}
+
+impl From<&str> for Insert {
+    fn from(text: &str) -> Self {
+        Self::Text(text.to_string())
+    }
+}
+impl From<String> for Insert {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+impl From<Embed> for Insert {
+    fn from(embed: Embed) -> Self {
+        Self::Embed(embed)
+    }
+}
+impl From<Embeds> for Insert {
+    fn from(embed: Embeds) -> Self {
+        Self::Embed(embed.into())
+    }
+}
+
+/// one operation of a [`Delta`](super::Delta), serialized the same way quill-delta does:
+/// `{"insert": ..., "attributes": {...}}`, `{"retain": n, "attributes": {...}}`, `{"delete": n}`.
+///
+/// quill-delta's retain of an embed, `{"retain": {"table": ...}}`, has no equivalent: only
+/// embeds registered with `Delta.registerEmbed` produce it, such ops fail to deserialize.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Op {
+    Insert {
+        insert: Insert,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        attributes: Option<AttributeMap>,
+    },
+    Retain {
+        #[serde(deserialize_with = "length")]
+        retain: Utf16Index,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        attributes: Option<AttributeMap>,
+    },
+    Delete {
+        #[serde(deserialize_with = "length")]
+        delete: Utf16Index,
+    },
+}
+
+/// [`Utf16Index::MAX`] stands for the end of a delta, it can't be the length of an op
+fn length<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Utf16Index, D::Error> {
+    let length = Utf16Index::deserialize(deserializer)?;
+    if length == Utf16Index::MAX {
+        return Err(D::Error::custom(
+            "a length cannot be the end of delta marker",
+        ));
+    }
+    Ok(length)
+}
+
+fn non_empty(attributes: AttributeMap) -> Option<AttributeMap> {
+    (!attributes.is_empty()).then_some(attributes)
+}
+
+impl Op {
+    /// empty attribute maps are dropped
+    pub fn insert(insert: impl Into<Insert>, attributes: AttributeMap) -> Self {
+        Self::Insert {
+            insert: insert.into(),
+            attributes: non_empty(attributes),
+        }
+    }
+    /// empty attribute maps are dropped
+    pub fn retain(length: impl Into<Utf16Index>, attributes: AttributeMap) -> Self {
+        Self::Retain {
+            retain: length.into(),
+            attributes: non_empty(attributes),
+        }
+    }
+    pub fn delete(length: impl Into<Utf16Index>) -> Self {
+        Self::Delete {
+            delete: length.into(),
+        }
+    }
+
+    pub fn len(&self) -> Utf16Index {
+        match self {
+            Self::Insert { insert, .. } => insert.len(),
+            Self::Retain { retain, .. } => *retain,
+            Self::Delete { delete } => *delete,
+        }
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == Utf16Index::ZERO
+    }
+    pub fn attributes(&self) -> Option<&AttributeMap> {
+        match self {
+            Self::Insert { attributes, .. } | Self::Retain { attributes, .. } => {
+                attributes.as_ref()
+            }
+            Self::Delete { .. } => None,
+        }
+    }
+    pub fn as_insert(&self) -> Option<&Insert> {
+        match self {
+            Self::Insert { insert, .. } => Some(insert),
+            _ => None,
+        }
+    }
+    pub fn is_insert(&self) -> bool {
+        matches!(self, Self::Insert { .. })
+    }
+    pub fn is_retain(&self) -> bool {
+        matches!(self, Self::Retain { .. })
+    }
+    pub fn is_delete(&self) -> bool {
+        matches!(self, Self::Delete { .. })
+    }
+}
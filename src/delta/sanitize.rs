@@ -0,0 +1,277 @@
+use serde_json::Value;
+use std::borrow::Cow;
+
+use super::{AttributeMap, Delta, Embed, Insert, Op};
+use crate::{Embeds, Inline};
+
+/// which urls are allowed, the rust side counterpart of Quill's `Link.sanitize`.
+///
+/// [`UrlPolicy::default`] is Quill's link policy: `http`, `https`, `mailto`, `tel` and `sms`,
+/// relative urls allowed, disallowed urls replaced by `about:blank`.
+///
+/// ```
+/// # use quill_wr::UrlPolicy;
+/// let policy = UrlPolicy::builder().domains(&["example.com"]).build();
+/// assert_eq!(policy.sanitize("https://docs.example.com/a"), "https://docs.example.com/a");
+/// assert_eq!(policy.sanitize("https://evil.com"), "about:blank");
+/// assert_eq!(policy.sanitize(" JavaScript:alert(1)"), "about:blank");
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct UrlPolicy {
+    protocols: Vec<String>,
+    allow_relative: bool,
+    domains: Option<Vec<String>>,
+    replacement: String,
+}
+
+impl UrlPolicy {
+    pub fn builder() -> UrlPolicyBuilder {
+        UrlPolicyBuilder::new()
+    }
+    /// Quill's image policy: `http`, `https` and `data`, replaced by `//:0`
+    pub fn image() -> Self {
+        Self::builder()
+            .protocols(&["http", "https", "data"])
+            .replacement("//:0")
+            .build()
+    }
+
+    pub fn is_allowed(&self, url: &str) -> bool {
+        let url: String = url
+            .trim_matches(|c: char| c <= ' ')
+            .chars()
+            .filter(|c| !matches!(c, '\t' | '\n' | '\r'))
+            .collect();
+        let protocol = scheme(&url);
+        match &protocol {
+            Some(protocol) if !self.protocols.iter().any(|p| p == protocol) => return false,
+            None if !self.allow_relative => return false,
+            _ => {}
+        }
+        let Some(domains) = &self.domains else {
+            return true;
+        };
+        let special = protocol.as_deref().is_some_and(|p| SPECIAL.contains(&p));
+        match host(&url, protocol.as_deref()) {
+            Some(host) if !host.is_empty() => domains
+                .iter()
+                .any(|domain| host == *domain || host.ends_with(&format!(".{domain}"))),
+            // a special url always has a host, one we can't find is one we can't check
+            Some(_) => false,
+            None => !special,
+        }
+    }
+    /// `url` itself when allowed, the replacement otherwise
+    pub fn sanitize<'a>(&self, url: &'a str) -> Cow<'a, str> {
+        if self.is_allowed(url) {
+            Cow::Borrowed(url)
+        } else {
+            Cow::Owned(self.replacement.clone())
+        }
+    }
+}
+
+impl Default for UrlPolicy {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+pub struct UrlPolicyBuilder {
+    policy: UrlPolicy,
+}
+
+impl UrlPolicyBuilder {
+    pub fn new() -> Self {
+        Self {
+            policy: UrlPolicy {
+                protocols: ["http", "https", "mailto", "tel", "sms"]
+                    .map(String::from)
+                    .to_vec(),
+                allow_relative: true,
+                domains: None,
+                replacement: "about:blank".into(),
+            },
+        }
+    }
+    /// lowercase, without the trailing `:`
+    pub fn protocols(mut self, protocols: &[&str]) -> Self {
+        self.policy.protocols = protocols.iter().map(|p| p.to_ascii_lowercase()).collect();
+        self
+    }
+    /// `/path`, `page.html`, `#anchor` and `//host/path` urls, resolved by the browser against
+    /// the page
+    pub fn allow_relative(mut self, allow: bool) -> Self {
+        self.policy.allow_relative = allow;
+        self
+    }
+    /// only urls with one of these hosts or their subdomains, urls without a host (`mailto:`,
+    /// `/path`) are not affected, `https:` and the like without one are rejected
+    pub fn domains(mut self, domains: &[&str]) -> Self {
+        self.policy.domains = Some(domains.iter().map(|d| d.to_ascii_lowercase()).collect());
+        self
+    }
+    pub fn replacement(mut self, replacement: impl Into<String>) -> Self {
+        self.policy.replacement = replacement.into();
+        self
+    }
+    pub fn build(self) -> UrlPolicy {
+        self.policy
+    }
+}
+
+impl Default for UrlPolicyBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// lowercase scheme, `None` for relative urls
+fn scheme(url: &str) -> Option<String> {
+    let (scheme, _) = url.split_once(':')?;
+    let mut chars = scheme.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    valid.then(|| scheme.to_ascii_lowercase())
+}
+
+/// the schemes browsers parse with a host, reading `\` as `/` and skipping any run of
+/// slashes before it
+const SPECIAL: [&str; 6] = ["http", "https", "ws", "wss", "ftp", "file"];
+
+/// lowercase host of a url the way browsers find it: after any run of `/` and `\` for
+/// [`SPECIAL`] schemes (`https:evil.com` included), after two of them for relative urls
+/// (`/\evil.com`) and after `//` for other schemes. `None` for urls without an authority
+fn host(url: &str, scheme: Option<&str>) -> Option<String> {
+    let authority = match scheme {
+        Some(scheme) if SPECIAL.contains(&scheme) => {
+            url.split_once(':')?.1.trim_start_matches(['/', '\\'])
+        }
+        Some(_) => url.split_once(':')?.1.strip_prefix("//")?,
+        None => {
+            let authority = url.trim_start_matches(['/', '\\']);
+            if url.len() - authority.len() < 2 {
+                return None;
+            }
+            authority
+        }
+    };
+    let authority = authority
+        .split(['/', '\\', '?', '#'])
+        .next()
+        .unwrap_or_default();
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    let host = match host.strip_prefix('[') {
+        Some(ipv6) => ipv6.split(']').next().unwrap_or_default(),
+        None => host.split(':').next().unwrap_or_default(),
+    };
+    Some(host.trim_end_matches('.').to_ascii_lowercase())
+}
+
+/// the url policies applied to untrusted content, see [`Delta::sanitize`].
+///
+/// the default mirrors Quill: [`UrlPolicy::default`] for links and videos, [`UrlPolicy::image`]
+/// for images.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SanitizePolicy {
+    pub link: UrlPolicy,
+    pub image: UrlPolicy,
+    pub video: UrlPolicy,
+}
+
+impl Default for SanitizePolicy {
+    fn default() -> Self {
+        Self {
+            link: UrlPolicy::default(),
+            image: UrlPolicy::image(),
+            video: UrlPolicy::default(),
+        }
+    }
+}
+
+impl SanitizePolicy {
+    /// keyed on the name Quill sees, so `Inline::Custom("link", ..)` is checked as well
+    pub fn sanitize_inline(&self, inline: Inline) -> Inline {
+        let (name, mut value) = inline.as_kv();
+        if name != "link" {
+            return inline;
+        }
+        Self::sanitize_value(&self.link, &mut value);
+        Inline::from_kv(name, value)
+    }
+    /// keyed on the blot name, so `Embeds::Custom("image", ..)` is checked as well
+    pub fn sanitize_embed(&self, embed: Embeds) -> Embeds {
+        let policy = match embed.get_type_name() {
+            "image" => &self.image,
+            "video" => &self.video,
+            _ => return embed,
+        };
+        let mut embed = Embed::from(embed);
+        Self::sanitize_value(policy, &mut embed.value);
+        embed.into()
+    }
+    /// urls that are not allowed are replaced, so are values that are not strings: the
+    /// browser turns them into one (`["javascript:x"]` gives `javascript:x`). `null` is kept,
+    /// it removes a format
+    fn sanitize_value(policy: &UrlPolicy, value: &mut Value) {
+        let allowed = match value {
+            Value::String(url) => policy.is_allowed(url),
+            Value::Null => true,
+            _ => false,
+        };
+        if !allowed {
+            *value = Value::String(policy.replacement.clone());
+        }
+    }
+    fn sanitize_attributes(&self, attributes: &mut Option<AttributeMap>) {
+        if let Some(link) = attributes.as_mut().and_then(|a| a.get_mut("link")) {
+            Self::sanitize_value(&self.link, link);
+        }
+    }
+    fn sanitize_op(&self, op: &mut Op) {
+        match op {
+            Op::Insert { insert, attributes } => {
+                self.sanitize_attributes(attributes);
+                if let Insert::Embed(embed) = insert {
+                    match embed.kind.as_str() {
+                        "image" => Self::sanitize_value(&self.image, &mut embed.value),
+                        "video" => Self::sanitize_value(&self.video, &mut embed.value),
+                        _ => {}
+                    }
+                }
+            }
+            Op::Retain { attributes, .. } => self.sanitize_attributes(attributes),
+            Op::Delete { .. } => {}
+        }
+    }
+}
+
+impl Delta {
+    /// replaces the disallowed urls of `link` formats, `image` and `video` embeds, in place.
+    ///
+    /// run it on deltas coming from untrusted sources before storing or rendering them.
+    pub fn sanitize(&mut self, policy: &SanitizePolicy) -> &mut Self {
+        let mut ops = std::mem::take(self).into_ops();
+        for op in &mut ops {
+            policy.sanitize_op(op);
+        }
+        *self = Delta::from_ops(ops);
+        self
+    }
+    pub fn sanitized(&self, policy: &SanitizePolicy) -> Delta {
+        let mut delta = self.clone();
+        delta.sanitize(policy);
+        delta
+    }
+    /// [`Delta::from_json`] followed by [`Delta::sanitize`]
+    pub fn from_json_sanitized(
+        json: &str,
+        policy: &SanitizePolicy,
+    ) -> Result<Self, serde_json::Error> {
+        let mut delta = Self::from_json(json)?;
+        delta.sanitize(policy);
+        Ok(delta)
+    }
+}
@@ -0,0 +1,402 @@
+use serde_json::{Value, json};
+use std::{collections::HashMap, fmt::Display};
+
+use super::{Delta, Insert, Op};
+use crate::{
+    Align, Block, Color, CustomEmbed, EmbedAttr, Embeds, Font, HeaderLevel, IndentLevel, Inline,
+    ListKind,
+};
+
+/// the values a format accepts.
+#[derive(Debug, Clone)]
+pub enum FormatKind {
+    Bool,
+    String,
+    Number,
+    /// one of a fixed set of values, e.g. `header` accepts `1..=6`
+    OneOf(Vec<Value>),
+    /// accepts the values the function returns `true` for
+    Validator(fn(&Value) -> bool),
+    /// a css color [`Color::parse`] understands
+    Color,
+    /// a css color of the palette, in any notation: `"red"` matches `Color::rgb(255, 0, 0)`
+    Palette(Vec<Color>),
+    Any,
+}
+
+impl PartialEq for FormatKind {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::OneOf(a), Self::OneOf(b)) => a == b,
+            (Self::Palette(a), Self::Palette(b)) => a == b,
+            // best effort, the same function may end up with several addresses
+            (Self::Validator(a), Self::Validator(b)) => std::ptr::fn_addr_eq(*a, *b),
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+impl FormatKind {
+    pub fn accepts(&self, value: &Value) -> bool {
+        match self {
+            Self::Bool => value.is_boolean(),
+            Self::String => value.is_string(),
+            Self::Number => value.is_number(),
+            Self::OneOf(values) => values.contains(value),
+            Self::Validator(validate) => validate(value),
+            Self::Color => value.as_str().and_then(Color::parse).is_some(),
+            Self::Palette(colors) => value
+                .as_str()
+                .and_then(Color::parse)
+                .is_some_and(|color| colors.contains(&color)),
+            Self::Any => true,
+        }
+    }
+    fn of_value(value: &Value) -> Self {
+        match value {
+            Value::Bool(_) => Self::Bool,
+            Value::String(_) => Self::String,
+            Value::Number(_) => Self::Number,
+            _ => Self::Any,
+        }
+    }
+}
+
+/// the formats, block formats and embeds a document may use, checked by [`Delta::validate`].
+///
+/// [`Schema::default`] accepts everything Quill's snow theme can produce,
+/// use [`Schema::builder`] to restrict it.
+#[derive(Debug, Clone)]
+pub struct Schema {
+    inline: HashMap<String, FormatKind>,
+    block: HashMap<String, FormatKind>,
+    embeds: HashMap<String, FormatKind>,
+    embed_attrs: HashMap<String, FormatKind>,
+}
+
+impl Schema {
+    pub fn builder() -> SchemaBuilder {
+        SchemaBuilder::new()
+    }
+    pub(crate) fn accepts_inline(&self, name: &str, value: &Value) -> bool {
+        self.inline
+            .get(name)
+            .is_some_and(|kind| kind.accepts(value))
+    }
+    pub(crate) fn accepts_block(&self, name: &str, value: &Value) -> bool {
+        self.block.get(name).is_some_and(|kind| kind.accepts(value))
+    }
+    pub(crate) fn accepts_embed(&self, name: &str, value: &Value) -> bool {
+        self.embeds
+            .get(name)
+            .is_some_and(|kind| kind.accepts(value))
+    }
+    pub(crate) fn accepts_embed_attr(&self, name: &str, value: &Value) -> bool {
+        self.embed_attrs
+            .get(name)
+            .is_some_and(|kind| kind.accepts(value))
+    }
+}
+
+impl Default for Schema {
+    fn default() -> Self {
+        Schema::builder()
+            .inlines(&[
+                Inline::Bold,
+                Inline::Italic,
+                Inline::Underline,
+                Inline::Strike,
+                Inline::Code,
+                Inline::Link(String::new()),
+                Inline::BgColor(Color::rgb(0, 0, 0)),
+                Inline::Color(Color::rgb(0, 0, 0)),
+                Inline::Font(Font::Custom(String::new())),
+                Inline::Sub,
+            ])
+            .inline("size", FormatKind::Any)
+            .blocks(&[
+                Block::Header(HeaderLevel::MIN),
+                Block::List(ListKind::Bullet),
+                Block::Blockquote,
+                Block::CodeBlock(None),
+                Block::Indent(IndentLevel::MIN),
+                Block::Align(Align::Center),
+                Block::Rtl,
+            ])
+            .embeds(&[
+                Embeds::Image(String::new()),
+                Embeds::Video(String::new()),
+                Embeds::Formula(String::new()),
+            ])
+            .embed_attrs(&[
+                EmbedAttr::Width(String::new()),
+                EmbedAttr::Height(String::new()),
+                EmbedAttr::Alt(String::new()),
+            ])
+            .build()
+    }
+}
+
+pub struct SchemaBuilder {
+    schema: Schema,
+}
+
+impl SchemaBuilder {
+    /// starts from an empty schema accepting no format at all
+    pub fn new() -> Self {
+        Self {
+            schema: Schema {
+                inline: HashMap::new(),
+                block: HashMap::new(),
+                embeds: HashMap::new(),
+                embed_attrs: HashMap::new(),
+            },
+        }
+    }
+    /// allows the given inline formats, their values only tell the expected value type
+    #[allow(deprecated)]
+    pub fn inlines(mut self, formats: &[Inline]) -> Self {
+        for format in formats {
+            let (name, value) = format.as_kv();
+            let kind = match format {
+                Inline::Sub | Inline::Sup => FormatKind::OneOf(vec![json!("sub"), json!("super")]),
+                Inline::Color(_) | Inline::BgColor(_) => FormatKind::Color,
+                _ => FormatKind::of_value(&value),
+            };
+            // `align` is a line format even though `Inline` carries it
+            match format {
+                Inline::Align(_) => self.schema.block.insert(name, kind),
+                _ => self.schema.inline.insert(name, kind),
+            };
+        }
+        self
+    }
+    /// restricts `color` and `background` to the colors of a palette, like the swatches of
+    /// the toolbar pickers
+    pub fn palette(mut self, colors: &[Color]) -> Self {
+        for name in ["color", "background"] {
+            self.schema
+                .inline
+                .insert(name.into(), FormatKind::Palette(colors.to_vec()));
+        }
+        self
+    }
+    pub fn inline(mut self, name: impl Into<String>, kind: FormatKind) -> Self {
+        self.schema.inline.insert(name.into(), kind);
+        self
+    }
+    /// allows the given line formats with every value Quill accepts for them, e.g. any
+    /// `Block::Header(_)` allows `header` levels `1..=6`
+    pub fn blocks(mut self, formats: &[Block]) -> Self {
+        for format in formats {
+            let (name, value) = format.as_kv();
+            let kind = match format {
+                Block::Header(_) => {
+                    FormatKind::OneOf((1..=Block::MAX_HEADER).map(Value::from).collect())
+                }
+                Block::Indent(_) => {
+                    FormatKind::OneOf((1..=Block::MAX_INDENT).map(Value::from).collect())
+                }
+                Block::List(_) => FormatKind::OneOf(
+                    [
+                        ListKind::Ordered,
+                        ListKind::Bullet,
+                        ListKind::Checked,
+                        ListKind::Unchecked,
+                    ]
+                    .iter()
+                    .map(|kind| Value::from(kind.as_str()))
+                    .collect(),
+                ),
+                Block::Align(_) => FormatKind::OneOf(
+                    [Align::Center, Align::Right, Align::Justify]
+                        .iter()
+                        .map(|align| Value::from(align.as_str()))
+                        .collect(),
+                ),
+                // quill 2 stores the language ("plain", "rust", ...), older versions `true`
+                Block::CodeBlock(_) => FormatKind::Any,
+                Block::Rtl => FormatKind::OneOf(vec![value]),
+                Block::Blockquote | Block::Custom(..) => FormatKind::of_value(&value),
+            };
+            self.schema.block.insert(name, kind);
+        }
+        self
+    }
+    /// allows a line format, stored on the trailing `"\n"` of a line
+    pub fn block(mut self, name: impl Into<String>, kind: FormatKind) -> Self {
+        self.schema.block.insert(name.into(), kind);
+        self
+    }
+    /// allows the given embeds, their values only tell the expected value type
+    pub fn embeds(mut self, embeds: &[Embeds]) -> Self {
+        for embed in embeds {
+            let kind = match embed {
+                Embeds::Custom(_, value) => FormatKind::of_value(value),
+                _ => FormatKind::String,
+            };
+            self.schema
+                .embeds
+                .insert(embed.get_type_name().to_string(), kind);
+        }
+        self
+    }
+    /// allows a [`CustomEmbed`], its value must deserialize into `T`
+    pub fn custom_embed<T: CustomEmbed>(mut self) -> Self {
+        self.schema.embeds.insert(
+            T::NAME.to_string(),
+            FormatKind::Validator(|value| serde_json::from_value::<T>(value.clone()).is_ok()),
+        );
+        self
+    }
+    pub fn embed(mut self, name: impl Into<String>, kind: FormatKind) -> Self {
+        self.schema.embeds.insert(name.into(), kind);
+        self
+    }
+    /// allows the given attributes on embeds, their values only tell the expected value type
+    pub fn embed_attrs(mut self, attrs: &[EmbedAttr]) -> Self {
+        for attr in attrs {
+            let (name, value) = attr.as_kv();
+            self.schema
+                .embed_attrs
+                .insert(name, FormatKind::of_value(&value));
+        }
+        self
+    }
+    pub fn build(self) -> Schema {
+        self.schema
+    }
+}
+
+impl Default for SchemaBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// why an op was rejected by [`Delta::validate`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationErrorKind {
+    /// documents only contain inserts
+    NotAnInsert,
+    EmptyInsert,
+    /// the last op does not end with `"\n"`, reported past the last op where it is missing
+    MissingTrailingNewline,
+    UnknownFormat(String),
+    InvalidFormatValue(String, Value),
+    /// `null` only makes sense in a change, to remove a format
+    NullFormat(String),
+    /// a line format set on an insert that is not made only of newlines
+    BlockFormatOnText(String),
+    UnknownEmbed(String),
+    InvalidEmbedValue(String, Value),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    /// index of the offending op in [`Delta::ops`], `ops().len()` for a missing trailing
+    /// newline
+    pub op_index: usize,
+    pub kind: ValidationErrorKind,
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use ValidationErrorKind::*;
+        write!(f, "op {}: ", self.op_index)?;
+        match &self.kind {
+            NotAnInsert => write!(f, "a document may only contain inserts"),
+            EmptyInsert => write!(f, "empty insert"),
+            MissingTrailingNewline => write!(f, "a document must end with a newline"),
+            UnknownFormat(name) => write!(f, "unknown format `{name}`"),
+            InvalidFormatValue(name, value) => write!(f, "invalid value {value} for `{name}`"),
+            NullFormat(name) => write!(f, "format `{name}` is null"),
+            BlockFormatOnText(name) => {
+                write!(f, "line format `{name}` must be set on a newline")
+            }
+            UnknownEmbed(name) => write!(f, "unknown embed `{name}`"),
+            InvalidEmbedValue(name, value) => {
+                write!(f, "invalid value {value} for embed `{name}`")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl Delta {
+    /// checks that this delta is a valid document for `schema`: only inserts, ending in a
+    /// newline, using known formats and embeds with well typed values.
+    ///
+    /// every problem found is reported, in op order.
+    pub fn validate(&self, schema: &Schema) -> Result<(), Vec<ValidationError>> {
+        let mut errors = vec![];
+        for (op_index, op) in self.ops().iter().enumerate() {
+            let mut error = |kind| errors.push(ValidationError { op_index, kind });
+            let Op::Insert { insert, attributes } = op else {
+                error(ValidationErrorKind::NotAnInsert);
+                continue;
+            };
+            if insert.is_empty() {
+                error(ValidationErrorKind::EmptyInsert);
+            }
+            let newlines_only = match insert {
+                Insert::Text(text) => text.chars().all(|c| c == '\n'),
+                Insert::Embed(embed) => {
+                    match schema.embeds.get(&embed.kind) {
+                        None => error(ValidationErrorKind::UnknownEmbed(embed.kind.clone())),
+                        Some(kind) if !kind.accepts(&embed.value) => {
+                            error(ValidationErrorKind::InvalidEmbedValue(
+                                embed.kind.clone(),
+                                embed.value.clone(),
+                            ))
+                        }
+                        _ => {}
+                    }
+                    false
+                }
+            };
+            for (name, value) in attributes.iter().flatten() {
+                let kind = schema
+                    .inline
+                    .get(name)
+                    .or_else(|| insert.as_embed().and_then(|_| schema.embed_attrs.get(name)));
+                let kind = match (kind, schema.block.get(name)) {
+                    (Some(kind), _) => kind,
+                    (None, Some(kind)) if newlines_only => kind,
+                    (None, Some(_)) => {
+                        error(ValidationErrorKind::BlockFormatOnText(name.clone()));
+                        continue;
+                    }
+                    (None, None) => {
+                        error(ValidationErrorKind::UnknownFormat(name.clone()));
+                        continue;
+                    }
+                };
+                if value.is_null() {
+                    error(ValidationErrorKind::NullFormat(name.clone()));
+                } else if !kind.accepts(value) {
+                    error(ValidationErrorKind::InvalidFormatValue(
+                        name.clone(),
+                        value.clone(),
+                    ));
+                }
+            }
+        }
+        let ends_with_newline = matches!(
+            self.ops().last(),
+            Some(Op::Insert { insert: Insert::Text(text), .. }) if text.ends_with('\n')
+        );
+        if !ends_with_newline {
+            errors.push(ValidationError {
+                op_index: self.ops().len(),
+                kind: ValidationErrorKind::MissingTrailingNewline,
+            });
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
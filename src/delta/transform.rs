@@ -0,0 +1,66 @@
+use super::{AttributeMap, Delta, Op, OpIterator, transform_attributes};
+use crate::Utf16Index;
+
+impl Delta {
+    /// transforms `other` against `self`, both made concurrently on the same document.
+    ///
+    /// `priority` tells whether `self` happened first: its inserts go before `other`'s at the
+    /// same index and its formats win. the result can be composed after `self`, so that
+    /// `a.compose(&a.transform(&b, true)) == b.compose(&b.transform(&a, false))`.
+    pub fn transform(&self, other: &Delta, priority: bool) -> Delta {
+        let mut this_iter = OpIterator::new(self.ops());
+        let mut other_iter = OpIterator::new(other.ops());
+        let mut delta = Delta::new();
+        while this_iter.has_next() || other_iter.has_next() {
+            if this_iter.peek_is_insert() && (priority || !other_iter.peek_is_insert()) {
+                let length = this_iter.next(Utf16Index::MAX).len();
+                delta.retain(length, AttributeMap::new());
+            } else if other_iter.peek_is_insert() {
+                delta.push(other_iter.next(Utf16Index::MAX));
+            } else {
+                let length = this_iter.peek_length().min(other_iter.peek_length());
+                let this_op = this_iter.next(length);
+                let other_op = other_iter.next(length);
+                match (this_op, other_op) {
+                    // our delete either makes their delete redundant or removes their retain
+                    (Op::Delete { .. }, _) => continue,
+                    (_, other_op @ Op::Delete { .. }) => {
+                        delta.push(other_op);
+                    }
+                    (this_op, other_op) => {
+                        let attributes = transform_attributes(
+                            this_op.attributes(),
+                            other_op.attributes(),
+                            priority,
+                        );
+                        delta.retain(length, attributes.unwrap_or_default());
+                    }
+                }
+            }
+        }
+        delta.chop();
+        delta
+    }
+
+    /// transforms a cursor `index` against this change.
+    ///
+    /// with `priority` an insert at exactly `index` does not push the cursor forward.
+    pub fn transform_position(&self, index: impl Into<Utf16Index>, priority: bool) -> Utf16Index {
+        let mut index = index.into();
+        let mut iter = OpIterator::new(self.ops());
+        let mut offset = Utf16Index::ZERO;
+        while iter.has_next() && offset <= index {
+            let length = iter.peek_length();
+            let (is_insert, is_delete) = (iter.peek_is_insert(), iter.peek_is_delete());
+            iter.next(Utf16Index::MAX);
+            if is_delete {
+                index -= length.min(index - offset);
+                continue;
+            } else if is_insert && (offset < index || !priority) {
+                index += length;
+            }
+            offset += length;
+        }
+        index
+    }
+}
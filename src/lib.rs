@@ -1,7 +1,15 @@
+mod color;
+pub use color::*;
+mod convert;
+pub use convert::*;
+mod delta;
+pub use delta::*;
 mod quil_api;
 mod styling;
+mod utf16;
 pub use quil_api::*;
 pub use styling::*;
+pub use utf16::*;
 
 #[cfg(feature = "wasm-tests")]
 mod test_utils;
@@ -0,0 +1,32 @@
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(inline_js = r#"
+export function define_blot(Base, blotName, tagName, className, create, value, formats) {
+    class Blot extends Base {
+        static create(v) {
+            const node = super.create(v);
+            if (create) create(node, v);
+            return node;
+        }
+    }
+    if (value) Blot.value = (node) => value(node);
+    if (formats) Blot.formats = (node) => formats(node);
+    Blot.blotName = blotName;
+    Blot.tagName = tagName;
+    if (className) Blot.className = className;
+    return Blot;
+}
+"#)]
+extern "C" {
+    /// subclasses `base` (one of Quill's `blots/*`) with the given static members,
+    /// the callbacks may be `undefined` to keep the inherited ones
+    pub fn define_blot(
+        base: &JsValue,
+        blot_name: &str,
+        tag_name: &str,
+        class_name: Option<String>,
+        create: &JsValue,
+        value: &JsValue,
+        formats: &JsValue,
+    ) -> JsValue;
+}
@@ -2,3 +2,5 @@ mod quill;
 pub use quill::*;
 mod delta;
 pub use delta::*;
+mod blot;
+pub use blot::*;
@@ -1,7 +1,3 @@
-use std::collections::HashMap;
-
-use gloo::utils::format::JsValueSerdeExt;
-use serde_json::json;
 use wasm_bindgen::prelude::*;
 use web_sys::{Node, js_sys::Function};
 
@@ -0,0 +1,50 @@
+use js_sys::{Array, Reflect};
+use wasm_bindgen::JsValue;
+
+use super::Quill;
+use crate::{Font, Size};
+
+/// how Quill writes a format into the DOM.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Attributor {
+    /// a `ql-size-large` / `ql-font-serif` class, every value needs a css rule
+    #[default]
+    Class,
+    /// an inline `font-size` / `font-family` style, works for any css value (`"18px"`, `"Arial"`)
+    Style,
+}
+
+impl Attributor {
+    fn path(&self, format: &str) -> String {
+        match self {
+            Self::Class => format!("attributors/class/{format}"),
+            Self::Style => format!("attributors/style/{format}"),
+        }
+    }
+}
+
+/// registers the allowed `size` values globally, must run before the editor is created.
+///
+/// [`QuillEditor::new_with_options`](crate::QuillEditor::new_with_options) calls it for the
+/// sizes declared with [`QuillOptionsBuilder::sizes`](crate::QuillOptionsBuilder::sizes).
+pub fn register_sizes(attributor: Attributor, sizes: &[Size]) {
+    register_whitelist(&attributor.path("size"), sizes.iter().map(Size::to_string));
+}
+
+/// registers the allowed `font` values globally, must run before the editor is created.
+///
+/// [`QuillEditor::new_with_options`](crate::QuillEditor::new_with_options) calls it for the
+/// fonts declared with [`QuillOptionsBuilder::fonts`](crate::QuillOptionsBuilder::fonts).
+pub fn register_fonts(attributor: Attributor, fonts: &[Font]) {
+    register_whitelist(&attributor.path("font"), fonts.iter().map(Font::to_string));
+}
+
+fn register_whitelist(path: &str, values: impl Iterator<Item = String>) {
+    let attributor = Quill::import(path);
+    let whitelist = Array::new();
+    for value in values {
+        whitelist.push(&JsValue::from(value));
+    }
+    Reflect::set(&attributor, &"whitelist".into(), &whitelist).expect("failed to set whitelist");
+    Quill::register(&attributor, true);
+}
@@ -0,0 +1,177 @@
+use serde_json::Value;
+use wasm_bindgen::{JsCast, JsValue, prelude::Closure};
+use web_sys::HtmlElement;
+
+use super::{Quill, define_blot};
+use crate::to_js_object;
+
+/// the Quill blot a custom blot extends.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlotKind {
+    /// a whole line embed, e.g. a divider or a video (`blots/block/embed`)
+    BlockEmbed,
+    /// an embed inside a line, e.g. a mention or an image (`blots/embed`)
+    Embed,
+    /// an inline format wrapping text, e.g. a highlight (`blots/inline`)
+    Inline,
+    /// a line format, e.g. a callout paragraph (`blots/block`)
+    Block,
+}
+
+impl BlotKind {
+    pub fn import_path(&self) -> &'static str {
+        match self {
+            Self::BlockEmbed => "blots/block/embed",
+            Self::Embed => "blots/embed",
+            Self::Inline => "blots/inline",
+            Self::Block => "blots/block",
+        }
+    }
+    /// used when no tag name is given
+    pub fn default_tag_name(&self) -> &'static str {
+        match self {
+            Self::BlockEmbed => "DIV",
+            Self::Embed | Self::Inline => "SPAN",
+            Self::Block => "P",
+        }
+    }
+}
+
+type CreateFn = Box<dyn Fn(&HtmlElement, &Value)>;
+type NodeFn = Box<dyn Fn(&HtmlElement) -> Value>;
+
+/// a custom blot defined from rust, see [`BlotDefinition::builder`].
+///
+/// ```no_run
+/// # use quill_wr::*;
+/// # use serde_json::{Value, json};
+/// // {"insert": {"mention": {"id": 7, "name": "Ada"}}} renders as <span class="mention">@Ada</span>
+/// BlotDefinition::builder(BlotKind::Embed, "mention")
+///     .class_name("mention")
+///     .create(|node, value| {
+///         node.set_inner_text(&format!("@{}", value["name"].as_str().unwrap_or_default()));
+///         let _ = node.set_attribute("data-id", &value["id"].to_string());
+///         let _ = node.set_attribute("data-name", value["name"].as_str().unwrap_or_default());
+///     })
+///     .value(|node| {
+///         json!({
+///             "id": node.get_attribute("data-id").and_then(|id| id.parse::<u64>().ok()),
+///             "name": node.get_attribute("data-name"),
+///         })
+///     })
+///     .build()
+///     .register();
+/// ```
+pub struct BlotDefinition {
+    kind: BlotKind,
+    blot_name: String,
+    tag_name: Option<String>,
+    class_name: Option<String>,
+    create: Option<CreateFn>,
+    value: Option<NodeFn>,
+    formats: Option<NodeFn>,
+}
+
+impl BlotDefinition {
+    /// `blot_name` is the key used in deltas (`{"insert": {blot_name: ...}}` for embeds,
+    /// `"attributes": {blot_name: ...}` for formats)
+    pub fn builder(kind: BlotKind, blot_name: impl Into<String>) -> BlotDefinitionBuilder {
+        BlotDefinitionBuilder::new(kind, blot_name)
+    }
+    pub fn kind(&self) -> BlotKind {
+        self.kind
+    }
+    pub fn blot_name(&self) -> &str {
+        &self.blot_name
+    }
+
+    /// builds the js class, the callbacks are leaked since Quill keeps the class forever
+    pub fn to_js_class(self) -> JsValue {
+        let create = self
+            .create
+            .map(|create| {
+                Closure::wrap(Box::new(move |node: JsValue, value: JsValue| {
+                    let value = serde_wasm_bindgen::from_value(value).unwrap_or(Value::Null);
+                    create(node.unchecked_ref(), &value);
+                }) as Box<dyn Fn(JsValue, JsValue)>)
+                .into_js_value()
+            })
+            .unwrap_or(JsValue::UNDEFINED);
+        let node_fn = |callback: Option<NodeFn>| {
+            callback
+                .map(|callback| {
+                    Closure::wrap(Box::new(move |node: JsValue| {
+                        to_js_object(&callback(node.unchecked_ref()))
+                    }) as Box<dyn Fn(JsValue) -> JsValue>)
+                    .into_js_value()
+                })
+                .unwrap_or(JsValue::UNDEFINED)
+        };
+        define_blot(
+            &Quill::import(self.kind.import_path()),
+            &self.blot_name,
+            self.tag_name
+                .as_deref()
+                .unwrap_or(self.kind.default_tag_name()),
+            self.class_name,
+            &create,
+            &node_fn(self.value),
+            &node_fn(self.formats),
+        )
+    }
+
+    /// registers the blot globally under `formats/<blot_name>`, overwriting any previous one.
+    /// must happen before the editors using it are created
+    pub fn register(self) {
+        Quill::register(&self.to_js_class(), true);
+    }
+}
+
+pub struct BlotDefinitionBuilder {
+    definition: BlotDefinition,
+}
+
+impl BlotDefinitionBuilder {
+    pub fn new(kind: BlotKind, blot_name: impl Into<String>) -> Self {
+        Self {
+            definition: BlotDefinition {
+                kind,
+                blot_name: blot_name.into(),
+                tag_name: None,
+                class_name: None,
+                create: None,
+                value: None,
+                formats: None,
+            },
+        }
+    }
+    /// defaults to [`BlotKind::default_tag_name`]
+    pub fn tag_name(mut self, tag_name: impl Into<String>) -> Self {
+        self.definition.tag_name = Some(tag_name.into());
+        self
+    }
+    /// tells apart blots sharing a tag name
+    pub fn class_name(mut self, class_name: impl Into<String>) -> Self {
+        self.definition.class_name = Some(class_name.into());
+        self
+    }
+    /// fills the node Quill created for `value` (tag and class name already set)
+    pub fn create(mut self, create: impl Fn(&HtmlElement, &Value) + 'static) -> Self {
+        self.definition.create = Some(Box::new(create));
+        self
+    }
+    /// reads the embed value back from the node, what ends up in the delta
+    pub fn value(mut self, value: impl Fn(&HtmlElement) -> Value + 'static) -> Self {
+        self.definition.value = Some(Box::new(value));
+        self
+    }
+    /// reads the format value back from the node, for [`BlotKind::Inline`] and
+    /// [`BlotKind::Block`] blots
+    pub fn formats(mut self, formats: impl Fn(&HtmlElement) -> Value + 'static) -> Self {
+        self.definition.formats = Some(Box::new(formats));
+        self
+    }
+    pub fn build(self) -> BlotDefinition {
+        self.definition
+    }
+}
@@ -1,10 +1,11 @@
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
 use serde_wasm_bindgen::to_value;
-use std::collections::HashMap;
 use wasm_bindgen::{JsCast, JsValue};
 
-use crate::{EmbedAttr, Embeds, Inline, embed_attrs_to_map, inlines_to_map, quil_api::api::Delta};
+use crate::{
+    Delta, Embed, EmbedAttr, Embeds, Inline, Op, Utf16Index, embed_attrs_to_map,
+    inlines_to_attributes, inlines_to_map, quil_api::api::Delta as JsDelta, to_js_object,
+};
 #[derive(Serialize, Deserialize, Debug)]
 pub enum DeltaOps {
     Insert(String, Vec<Inline>),
@@ -13,39 +14,40 @@ pub enum DeltaOps {
 }
 
 fn ops_to_jsvalue(ops: &[DeltaOps]) -> JsValue {
-    let js_ops: Vec<serde_json::Value> = ops
+    let delta: Delta = ops
         .iter()
         .map(|op| match op {
-            DeltaOps::Insert(text, inlines) => serde_json::json!({
-                "insert": text,
-                "attributes": inlines.iter().map(|i| i.as_kv()).collect::<serde_json::Map<_, _>>()
-            }),
-            DeltaOps::Retain(length, attrs) => serde_json::json!({
-                "retain": length,
-                "attributes": attrs.iter().map(|a| a.as_kv()).collect::<serde_json::Map<_, _>>()
-            }),
-            DeltaOps::Delete(length) => serde_json::json!({ "delete": length }),
+            DeltaOps::Insert(text, inlines) => {
+                Op::insert(text.as_str(), inlines_to_attributes(inlines))
+            }
+            DeltaOps::Retain(length, attrs) => Op::retain(*length, inlines_to_attributes(attrs)),
+            DeltaOps::Delete(length) => Op::delete(*length),
         })
         .collect();
-
-    serde_wasm_bindgen::to_value(&js_ops).unwrap()
+    // json compatible so attribute maps become plain objects and `null` survives
+    let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+    delta
+        .normalized()
+        .ops()
+        .serialize(&serializer)
+        .expect("Failed to convert ops to JsValue")
 }
 
 pub struct DeltaEditor {
-    delta: Delta,
+    delta: JsDelta,
 }
 
 impl DeltaEditor {
     pub fn new(ops: Vec<DeltaOps>) -> Self {
         let js_ops = ops_to_jsvalue(&ops);
         Self {
-            delta: Delta::new(js_ops),
+            delta: JsDelta::new(js_ops),
         }
     }
-    pub fn from_delta(delta: Delta) -> Self {
+    pub fn from_delta(delta: JsDelta) -> Self {
         Self { delta }
     }
-    pub fn get_inner_delta(&self) -> &Delta {
+    pub fn get_inner_delta(&self) -> &JsDelta {
         &self.delta
     }
     pub fn to_json(&self) -> Result<String, JsValue> {
@@ -55,7 +57,7 @@ impl DeltaEditor {
     // Deserialize from JSON string
     pub fn from_json(json: &str) -> Result<Self, JsValue> {
         let js_val = js_sys::JSON::parse(json)?;
-        let delta: Delta = js_val.unchecked_into();
+        let delta: JsDelta = js_val.unchecked_into();
         Ok(Self { delta })
     }
     // insert text with attributes
@@ -67,26 +69,22 @@ impl DeltaEditor {
 
     // insert embed with attributes
     pub fn insert_embed(&mut self, embed: Embeds, attributes: &[EmbedAttr]) -> &mut Self {
-        let embed_obj = match embed {
-            Embeds::Image(url) => serde_json::json!({ "image": url }),
-            Embeds::Video(url) => serde_json::json!({ "video": url }),
-            Embeds::Formula(f) => serde_json::json!({ "formula": f }),
-        };
-        let attrs_js = to_value(&embed_attrs_to_map(attributes)).unwrap();
-        self.delta = self.delta.insert(&to_value(&embed_obj).unwrap(), &attrs_js);
+        let embed = to_js_object(&Embed::from(embed));
+        let attrs_js = to_js_object(&embed_attrs_to_map(attributes));
+        self.delta = self.delta.insert(&embed, &attrs_js);
         self
     }
 
     // delete N characters
-    pub fn delete(&mut self, length: u32) -> &mut Self {
-        self.delta = self.delta.delete(length);
+    pub fn delete(&mut self, length: impl Into<Utf16Index>) -> &mut Self {
+        self.delta = self.delta.delete(length.into().as_u32());
         self
     }
 
     // retain N characters with optional attributes
-    pub fn retain(&mut self, length: u32, attributes: &[Inline]) -> &mut Self {
+    pub fn retain(&mut self, length: impl Into<Utf16Index>, attributes: &[Inline]) -> &mut Self {
         let attrs_js = to_value(&inlines_to_map(attributes)).unwrap();
-        self.delta = self.delta.retain(length, &attrs_js);
+        self.delta = self.delta.retain(length.into().as_u32(), &attrs_js);
         self
     }
 
@@ -94,7 +92,61 @@ impl DeltaEditor {
     pub fn concat(&self, other: &DeltaEditor) -> DeltaEditor {
         DeltaEditor::from_delta(self.delta.concat(&other.delta))
     }
-    pub fn diff(&mut self, other: &mut DeltaEditor) -> DeltaEditor {
+    /// js side diff, see [`Delta::diff`] for the native one
+    pub fn diff(&self, other: &DeltaEditor) -> DeltaEditor {
         DeltaEditor::from_delta(self.delta.diff(&other.delta))
     }
+    /// js side compose, see [`Delta::compose`] for the native one
+    pub fn compose(&self, other: &DeltaEditor) -> DeltaEditor {
+        DeltaEditor::from_delta(self.delta.compose(&other.delta))
+    }
+    /// js side transform, see [`Delta::transform`] for the native one
+    pub fn transform(&self, other: &DeltaEditor, priority: bool) -> DeltaEditor {
+        DeltaEditor::from_delta(self.delta.transform(&other.delta, priority))
+    }
+    /// js side transformPosition, see [`Delta::transform_position`] for the native one
+    pub fn transform_position(&self, index: impl Into<Utf16Index>, priority: bool) -> Utf16Index {
+        Utf16Index(
+            self.delta
+                .transform_position(index.into().as_u32(), priority) as usize,
+        )
+    }
+}
+
+impl From<&Delta> for JsDelta {
+    fn from(delta: &Delta) -> Self {
+        // json compatible so attribute maps become plain objects and `null` survives
+        let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+        let ops = delta
+            .ops()
+            .serialize(&serializer)
+            .expect("Failed to convert ops to JsValue");
+        JsDelta::new(ops)
+    }
+}
+
+impl TryFrom<&JsDelta> for Delta {
+    type Error = serde_wasm_bindgen::Error;
+    fn try_from(delta: &JsDelta) -> Result<Self, Self::Error> {
+        serde_wasm_bindgen::from_value(JsValue::clone(delta))
+    }
+}
+
+impl From<&Delta> for DeltaEditor {
+    fn from(delta: &Delta) -> Self {
+        DeltaEditor::from_delta(delta.into())
+    }
+}
+
+impl From<Delta> for DeltaEditor {
+    fn from(delta: Delta) -> Self {
+        DeltaEditor::from(&delta)
+    }
+}
+
+impl TryFrom<&DeltaEditor> for Delta {
+    type Error = serde_wasm_bindgen::Error;
+    fn try_from(editor: &DeltaEditor) -> Result<Self, Self::Error> {
+        Delta::try_from(editor.get_inner_delta())
+    }
 }
@@ -6,3 +6,9 @@ mod api;
 use api::*;
 mod quill_options;
 pub use quill_options::*;
+mod attributors;
+pub use attributors::*;
+mod blots;
+pub use blots::*;
+mod sanitize;
+pub use sanitize::*;
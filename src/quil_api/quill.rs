@@ -6,17 +6,19 @@ use uuid::Uuid;
 use wasm_bindgen::{JsCast, JsValue, prelude::Closure};
 use web_sys::{Node, js_sys};
 
-use super::{Delta, Quill};
+use super::{Delta as JsDelta, Quill};
 use crate::{
-    DeltaEditor, Embeds, Inline, QuillOptions, hash_map_to_js_object, inlines_to_map,
-    jsvalue_to_inlines,
+    Block, Delta, DeltaEditor, Embeds, Formats, Inline, Line, QuillOptions, SanitizePolicy,
+    Utf16Index, blocks_to_map, hash_map_to_js_object, inlines_to_map, jsvalue_to_formats,
+    to_js_object,
 };
 
 use std::fmt::Display;
+/// both in UTF-16 code units, see [`Utf16Index`]
 #[derive(Debug, Deserialize)]
 pub struct Selection {
-    pub index: u32,
-    pub length: u32,
+    pub index: Utf16Index,
+    pub length: Utf16Index,
 }
 #[derive(Debug, Default)]
 pub enum Source {
@@ -38,31 +40,32 @@ impl Display for Source {
         )
     }
 }
+/// index and length in UTF-16 code units, see [`Utf16Index`]
 #[derive(Default, Debug, Clone, Copy)]
 pub struct ContentRange {
-    index: usize,
-    length: Option<usize>,
+    index: Utf16Index,
+    length: Option<Utf16Index>,
 }
 impl ContentRange {
     pub fn new() -> Self {
         Self {
-            index: 0,
+            index: Utf16Index::ZERO,
             length: None,
         }
     }
-    pub fn set_index(mut self, index: usize) -> Self {
-        self.index = index;
+    pub fn set_index(mut self, index: impl Into<Utf16Index>) -> Self {
+        self.index = index.into();
         self
     }
-    pub fn set_length(mut self, length: usize) -> Self {
-        self.length = Some(length);
+    pub fn set_length(mut self, length: impl Into<Utf16Index>) -> Self {
+        self.length = Some(length.into());
         self
     }
 
-    pub fn index(&self) -> usize {
+    pub fn index(&self) -> Utf16Index {
         self.index
     }
-    pub fn length(&self) -> usize {
+    pub fn length(&self) -> Utf16Index {
         self.length.unwrap_or_default()
     }
 }
@@ -94,7 +97,7 @@ impl Bounds {
 }
 
 pub enum EventChange {
-    Text(Delta, Delta, Source),
+    Text(JsDelta, JsDelta, Source),
     Selection(Option<ContentRange>, Option<ContentRange>, Source),
     Editor(String, Vec<JsValue>),
     Generic(String, Vec<JsValue>),
@@ -117,13 +120,15 @@ pub struct QuillEditor {
     /// todo : consider implementing a wrapper to support different closure kinds (FnMut,FnOnce)
     handlers: DashMap<Uuid, (String, Function)>,
     quill: Quill,
+    sanitize: Option<SanitizePolicy>,
 }
 impl QuillEditor {
     pub fn new(selector: &str) -> Self {
         Self::new_with_options(selector, QuillOptions::default())
     }
     ///```no_run
-    ///  let options = QuillOption::builder()
+    ///  # use quill_wr::*;
+    ///  let options = QuillOptions::builder()
     ///    .theme("snow")
     ///    .placeholder("Write something...")
     ///    .read_only(false)
@@ -142,66 +147,120 @@ impl QuillEditor {
     ///```
     //let quill = QuillEditor::new_with_options("#editor", options);
     pub fn new_with_options(selector: &str, options: QuillOptions) -> Self {
+        options.register_whitelists();
         let js_options = options.to_js_value();
         let quill = Quill::new(selector, &js_options);
         Self {
             quill,
             handlers: DashMap::new(),
+            sanitize: options.sanitize_policy().cloned(),
         }
     }
-    pub fn get_length(&self) -> u32 {
-        self.quill.get_length()
+    fn sanitize_inlines(&self, formats: &[Inline]) -> Vec<Inline> {
+        match &self.sanitize {
+            Some(policy) => formats
+                .iter()
+                .map(|inline| policy.sanitize_inline(inline.clone()))
+                .collect(),
+            None => formats.to_vec(),
+        }
+    }
+    /// the delta sent to Quill: as is without a policy, sanitized with one. `None` when a
+    /// policy is set but the delta can't be read, its urls could not be checked
+    fn sanitize_delta(&self, delta: &DeltaEditor) -> Option<DeltaEditor> {
+        let Some(policy) = &self.sanitize else {
+            let inner = JsValue::clone(delta.get_inner_delta()).unchecked_into();
+            return Some(DeltaEditor::from_delta(inner));
+        };
+        let delta = Delta::try_from(delta).ok()?;
+        Some(delta.sanitized(policy).into())
+    }
+    /// in UTF-16 code units, see [`Utf16Index`] before slicing rust strings with it
+    pub fn get_length(&self) -> Utf16Index {
+        Utf16Index(self.quill.get_length() as usize)
     }
     /// default index=0 , length = get_length()
-    pub fn get_text(&self, index: Option<u32>, length: Option<u32>) -> String {
-        self.quill
-            .get_text(index.unwrap_or(0), length.unwrap_or(self.get_length()))
+    pub fn get_text(&self, index: Option<Utf16Index>, length: Option<Utf16Index>) -> String {
+        self.quill.get_text(
+            index.unwrap_or_default().as_u32(),
+            length.unwrap_or(self.get_length()).as_u32(),
+        )
     }
     /// this method is perfect for serializing content with styles/format to json!!
     ///
     /// default index=0 , length = get_length()
-    pub fn get_content(&self, index: Option<u32>, length: Option<u32>) -> DeltaEditor {
-        let delta = self
-            .quill
-            .get_contents(index.unwrap_or(0), length.unwrap_or(self.get_length()));
+    pub fn get_content(
+        &self,
+        index: Option<Utf16Index>,
+        length: Option<Utf16Index>,
+    ) -> DeltaEditor {
+        let delta = self.quill.get_contents(
+            index.unwrap_or_default().as_u32(),
+            length.unwrap_or(self.get_length()).as_u32(),
+        );
         DeltaEditor::from_delta(delta)
     }
-    /// # warning
-    /// broken unstable method
+    /// rendered natively from [`QuillEditor::get_content`], see [`Delta::to_semantic_html`]
     ///
     ///  default index=0 , length = get_length()
-    pub fn get_semantic_html(&self, index: Option<u32>, length: Option<u32>) -> String {
-        self.quill.get_semantic_html(
-            &to_value(&index.unwrap_or(0)).expect("Failed to convert to js"),
-            length.unwrap_or(self.get_length()),
-        )
+    pub fn get_semantic_html(
+        &self,
+        index: Option<Utf16Index>,
+        length: Option<Utf16Index>,
+    ) -> String {
+        Delta::try_from(&self.get_content(index, length))
+            .map(|content| content.to_semantic_html())
+            .unwrap_or_default()
+    }
+    /// rendered natively from [`QuillEditor::get_content`] with list markers, numbering and
+    /// indentation, see [`Delta::to_plain_text`]
+    ///
+    ///  default index=0 , length = get_length()
+    pub fn get_plain_text(&self, index: Option<Utf16Index>, length: Option<Utf16Index>) -> String {
+        Delta::try_from(&self.get_content(index, length))
+            .map(|content| content.to_plain_text())
+            .unwrap_or_default()
     }
     /// source defaults to 'api'
-    pub fn insert_embed(&self, index: u32, type_m: Embeds, source: Option<Source>) -> DeltaEditor {
+    pub fn insert_embed(
+        &self,
+        index: impl Into<Utf16Index>,
+        type_m: Embeds,
+        source: Option<Source>,
+    ) -> DeltaEditor {
+        let type_m = match &self.sanitize {
+            Some(policy) => policy.sanitize_embed(type_m),
+            None => type_m,
+        };
         DeltaEditor::from_delta(self.quill.insert_embed(
-            index,
+            index.into().as_u32(),
             type_m.get_type_name(),
-            &to_value(type_m.get_value()).expect("failed to convert value "),
+            &to_js_object(&type_m.get_value()),
             &source.unwrap_or(Source::Api).to_string(),
         ))
     }
     pub fn insert_text(
         &self,
-        index: u32,
+        index: impl Into<Utf16Index>,
         text: &str,
         formats: &[Inline],
         source: Option<Source>,
     ) -> DeltaEditor {
-        let formats = &inlines_to_map(formats);
+        let formats = &inlines_to_map(&self.sanitize_inlines(formats));
         let js_formats = hash_map_to_js_object(formats);
         DeltaEditor::from_delta(self.quill.insert_text(
-            index,
+            index.into().as_u32(),
             text,
             &js_formats,
             &source.unwrap_or(Source::Api).to_string(),
         ))
     }
+    /// the delta goes through the url policy first, when one is set. a delta the policy can't
+    /// read is not applied, the returned change is then empty
     pub fn set_content(&self, delta: &DeltaEditor, source: Option<Source>) -> DeltaEditor {
+        let Some(delta) = self.sanitize_delta(delta) else {
+            return DeltaEditor::new(vec![]);
+        };
         DeltaEditor::from_delta(self.quill.set_contents(
             delta.get_inner_delta(),
             &source.unwrap_or(Source::Api).to_string(),
@@ -213,21 +272,34 @@ impl QuillEditor {
                 .set_text(text, &source.unwrap_or(Source::Api).to_string()),
         )
     }
-    pub fn delete_text(&self, index: u32, length: u32, source: Option<Source>) -> DeltaEditor {
+    pub fn delete_text(
+        &self,
+        index: impl Into<Utf16Index>,
+        length: impl Into<Utf16Index>,
+        source: Option<Source>,
+    ) -> DeltaEditor {
         DeltaEditor::from_delta(self.quill.delete_text(
-            index,
-            length,
+            index.into().as_u32(),
+            length.into().as_u32(),
             &source.unwrap_or(Source::Api).to_string(),
         ))
     }
+    /// the delta goes through the url policy first, when one is set. a delta the policy can't
+    /// read is not applied, the returned change is then empty
     pub fn update_content(&self, delta: DeltaEditor, source: Option<Source>) -> DeltaEditor {
+        let Some(delta) = self.sanitize_delta(&delta) else {
+            return DeltaEditor::new(vec![]);
+        };
         DeltaEditor::from_delta(self.quill.update_contents(
             delta.get_inner_delta(),
             &source.unwrap_or(Source::Api).to_string(),
         ))
     }
     pub fn format(&self, name_value: Inline, source: Option<Source>) -> DeltaEditor {
-        let (name, value) = name_value.as_kv();
+        let (name, value) = match &self.sanitize {
+            Some(policy) => policy.sanitize_inline(name_value).as_kv(),
+            None => name_value.as_kv(),
+        };
         DeltaEditor::from_delta(self.quill.format(
             &name,
             &to_value(&value).expect("Failed to convert to jsValue"),
@@ -237,61 +309,105 @@ impl QuillEditor {
     /// source defaults to 'api'
     pub fn format_line(
         &self,
-        index: u32,
-        length: u32,
-        formats: &[Inline],
+        index: impl Into<Utf16Index>,
+        length: impl Into<Utf16Index>,
+        formats: &[Block],
         source: Option<Source>,
     ) -> DeltaEditor {
-        let formats = to_value(&inlines_to_map(formats)).expect("Failed to convert");
+        let formats = hash_map_to_js_object(&blocks_to_map(formats));
         DeltaEditor::from_delta(self.quill.format_line(
-            index,
-            length,
+            index.into().as_u32(),
+            length.into().as_u32(),
             &formats,
             &source.unwrap_or(Source::Api).to_string(),
         ))
     }
     pub fn format_text(
         &self,
-        index: u32,
-        length: u32,
+        index: impl Into<Utf16Index>,
+        length: impl Into<Utf16Index>,
         formats: &[Inline],
         source: Option<Source>,
     ) -> DeltaEditor {
-        let formats = to_value(&inlines_to_map(formats)).expect("Failed to convert");
+        let formats =
+            to_value(&inlines_to_map(&self.sanitize_inlines(formats))).expect("Failed to convert");
         DeltaEditor::from_delta(self.quill.format_text(
-            index,
-            length,
+            index.into().as_u32(),
+            length.into().as_u32(),
             &formats,
             &source.unwrap_or(Source::Api).to_string(),
         ))
     }
-    pub fn get_format(&self, index: u32, length: Option<u32>) -> Vec<Inline> {
-        let js_inlines = self.quill.get_format(index, length.unwrap_or(0));
-        jsvalue_to_inlines(js_inlines)
+    /// both the inline and the line formats of the range
+    pub fn get_format(&self, index: impl Into<Utf16Index>, length: Option<Utf16Index>) -> Formats {
+        let js_formats = self
+            .quill
+            .get_format(index.into().as_u32(), length.unwrap_or_default().as_u32());
+        jsvalue_to_formats(js_formats)
     }
     pub fn remove_format(
         &mut self,
-        index: u32,
-        length: u32,
+        index: impl Into<Utf16Index>,
+        length: impl Into<Utf16Index>,
         source: Option<Source>,
     ) -> DeltaEditor {
         DeltaEditor::from_delta(self.quill.remove_format(
-            index,
-            length,
+            index.into().as_u32(),
+            length.into().as_u32(),
             &source.unwrap_or(Source::Api).to_string(),
         ))
     }
-    pub fn get_bounds(&self, index: u32, length: Option<u32>) -> Option<Bounds> {
-        Bounds::from_js(self.quill.get_bounds(index, length.unwrap_or(0)))
+    /// returns the line containing `index` and the offset of `index` inside that line
+    pub fn get_line(&self, index: impl Into<Utf16Index>) -> Option<(Line, Utf16Index)> {
+        let index = index.into();
+        self.get_document()?
+            .lines()
+            .find(|line| line.index <= index && index < line.index + line.length())
+            .map(|line| {
+                let offset = index - line.index;
+                (line, offset)
+            })
+    }
+    /// returns the lines overlapping `range`, a range without length reaches the end of the document
+    pub fn get_lines(&self, range: ContentRange) -> Vec<Line> {
+        let Some(document) = self.get_document() else {
+            return vec![];
+        };
+        let index = range.index;
+        let end = index.saturating_add(range.length.unwrap_or(Utf16Index::MAX));
+        document
+            .lines()
+            .filter(|line| {
+                line.index + line.length() > index && (line.index < end || line.index <= index)
+            })
+            .collect()
+    }
+    fn get_document(&self) -> Option<Delta> {
+        Delta::try_from(&self.get_content(None, None)).ok()
+    }
+    pub fn get_bounds(
+        &self,
+        index: impl Into<Utf16Index>,
+        length: Option<Utf16Index>,
+    ) -> Option<Bounds> {
+        Bounds::from_js(
+            self.quill
+                .get_bounds(index.into().as_u32(), length.unwrap_or_default().as_u32()),
+        )
     }
     pub fn get_selection(&self, focus: Option<bool>) -> Option<Selection> {
         let js_val = self.quill.get_selection(focus.unwrap_or(false));
         serde_wasm_bindgen::from_value(js_val).ok()
     }
-    pub fn set_selection(&self, index: u32, length: Option<u32>, source: Option<Source>) {
+    pub fn set_selection(
+        &self,
+        index: impl Into<Utf16Index>,
+        length: Option<Utf16Index>,
+        source: Option<Source>,
+    ) {
         self.quill.set_selection(
-            index,
-            length.unwrap_or(0),
+            index.into().as_u32(),
+            length.unwrap_or_default().as_u32(),
             &source.unwrap_or(Source::Api).to_string(),
         );
     }
@@ -351,7 +467,7 @@ impl QuillEditor {
     }
     // its job is to disconnect the handler
     pub fn off(&self, id: Uuid) {
-        if let Some((id, (evnt_name, func))) = self.handlers.remove(&id) {
+        if let Some((_, (evnt_name, func))) = self.handlers.remove(&id) {
             self.quill.off_event(&evnt_name, &func);
         }
     }
@@ -379,7 +495,5 @@ impl QuillEditor {
         let bubble = bubble.unwrap_or(false);
     }
     pub fn get_index(&self, blot: Blot) {}
-    pub fn get_leaf(&self, index: usize) {}
-    pub fn get_line(&self, index: usize) {}
-    pub fn get_lines(&self, range: ContentRange) {}*/
+    pub fn get_leaf(&self, index: usize) {}*/
 }
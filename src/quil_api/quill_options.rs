@@ -2,6 +2,15 @@ use js_sys::{Array, Object, Reflect};
 use wasm_bindgen::JsValue;
 use web_sys::HtmlElement;
 
+use crate::{
+    Attributor, Font, SanitizePolicy, Size, register_fonts, register_sanitize_policy,
+    register_sizes,
+};
+
+/// the whitelists of Quill's class attributors, kept when none is declared
+static QUILL_SIZES: [Size; 3] = [Size::Small, Size::Large, Size::Huge];
+static QUILL_FONTS: [Font; 2] = [Font::Serif, Font::Monospace];
+
 #[derive(Debug, Default)]
 pub struct QuillOptions {
     theme: Option<String>,
@@ -12,6 +21,9 @@ pub struct QuillOptions {
     bounds: Option<String>,
     debug: Option<String>,
     registry: Option<JsValue>,
+    sizes: Option<(Attributor, Vec<Size>)>,
+    fonts: Option<(Attributor, Vec<Font>)>,
+    sanitize: Option<SanitizePolicy>,
 }
 
 impl QuillOptions {
@@ -60,6 +72,45 @@ impl QuillOptions {
 
         obj.into()
     }
+
+    /// registers the declared size and font whitelists and the url policy, Quill keeps them
+    /// globally so this must happen before the editor is created
+    pub fn register_whitelists(&self) {
+        if let Some((attributor, sizes)) = &self.sizes {
+            register_sizes(*attributor, sizes);
+        }
+        if let Some((attributor, fonts)) = &self.fonts {
+            register_fonts(*attributor, fonts);
+        }
+        if let Some(policy) = &self.sanitize {
+            register_sanitize_policy(policy);
+        }
+    }
+    pub fn sanitize_policy(&self) -> Option<&SanitizePolicy> {
+        self.sanitize.as_ref()
+    }
+    /// how sizes are written, Quill defaults to [`Attributor::Class`]
+    pub fn size_attributor(&self) -> Attributor {
+        self.sizes
+            .as_ref()
+            .map(|(attributor, _)| *attributor)
+            .unwrap_or_default()
+    }
+    /// how fonts are written, Quill defaults to [`Attributor::Class`]
+    pub fn font_attributor(&self) -> Attributor {
+        self.fonts
+            .as_ref()
+            .map(|(attributor, _)| *attributor)
+            .unwrap_or_default()
+    }
+    /// the sizes the editor keeps: the declared whitelist, Quill's own otherwise
+    pub fn sizes(&self) -> &[Size] {
+        self.sizes.as_ref().map_or(&QUILL_SIZES, |(_, sizes)| sizes)
+    }
+    /// the fonts the editor keeps: the declared whitelist, Quill's own otherwise
+    pub fn fonts(&self) -> &[Font] {
+        self.fonts.as_ref().map_or(&QUILL_FONTS, |(_, fonts)| fonts)
+    }
 }
 
 #[derive(Debug, Default)]
@@ -201,6 +252,27 @@ impl QuillOptionsBuilder {
         self
     }
 
+    /// the `size` values the editor accepts, e.g.
+    /// `.sizes(Attributor::Style, vec![Size::Px(12.0), Size::Px(18.0)])`
+    pub fn sizes(mut self, attributor: Attributor, sizes: Vec<Size>) -> Self {
+        self.quill_option.sizes = Some((attributor, sizes));
+        self
+    }
+
+    /// the `font` values the editor accepts, with [`Attributor::Class`] each needs a
+    /// `.ql-font-<name>` css rule
+    pub fn fonts(mut self, attributor: Attributor, fonts: Vec<Font>) -> Self {
+        self.quill_option.fonts = Some((attributor, fonts));
+        self
+    }
+
+    /// url policy for links, images and videos, enforced both by Quill and by the
+    /// [`QuillEditor`](crate::QuillEditor) insert, format and set content methods
+    pub fn sanitize(mut self, policy: SanitizePolicy) -> Self {
+        self.quill_option.sanitize = Some(policy);
+        self
+    }
+
     pub fn build(self) -> QuillOptions {
         self.quill_option
     }
@@ -0,0 +1,30 @@
+use js_sys::Reflect;
+use wasm_bindgen::prelude::Closure;
+
+use super::Quill;
+use crate::{SanitizePolicy, UrlPolicy};
+
+/// replaces the static `sanitize` of Quill's link, image and video formats with `policy`, so
+/// urls typed by the user go through the same checks as the ones inserted from rust.
+///
+/// Quill keeps formats globally, this affects every editor created afterwards.
+pub fn register_sanitize_policy(policy: &SanitizePolicy) {
+    override_sanitize("formats/link", policy.link.clone());
+    override_sanitize("formats/image", policy.image.clone());
+    override_sanitize("formats/video", policy.video.clone());
+}
+
+fn override_sanitize(path: &str, policy: UrlPolicy) {
+    let sanitize =
+        Closure::wrap(
+            Box::new(move |url: String| -> String { policy.sanitize(&url).into_owned() })
+                as Box<dyn Fn(String) -> String>,
+        );
+    Reflect::set(
+        &Quill::import(path),
+        &"sanitize".into(),
+        &sanitize.into_js_value(),
+    )
+    .map(|_| ())
+    .unwrap_or_else(|_| panic!("failed to override {path} sanitize"));
+}
@@ -1,10 +1,12 @@
 use js_sys::Object;
-use serde::{Deserialize, Serialize};
-use serde_json::{Number, Value};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Value;
 use serde_wasm_bindgen::to_value;
-use std::{collections::HashMap, fmt::Display, str::FromStr};
+use std::{collections::HashMap, fmt::Display};
 use wasm_bindgen::{JsCast, JsValue};
 
+use crate::Color;
+
 pub fn create_js_object<T: Serialize>(key: &str, value: T) -> JsValue {
     let obj = Object::new();
     js_sys::Reflect::set(
@@ -15,6 +17,12 @@ pub fn create_js_object<T: Serialize>(key: &str, value: T) -> JsValue {
     .expect("failed to create JS object ");
     obj.unchecked_into()
 }
+/// like [`to_value`] but maps become plain js objects, the shape Quill expects
+pub fn to_js_object<T: Serialize + ?Sized>(value: &T) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .expect("failed to convert to JsValue")
+}
 pub fn hash_map_to_js_object<G: Serialize>(map: &HashMap<String, G>) -> JsValue {
     let obj = Object::new();
     for (k, v) in map {
@@ -30,30 +38,44 @@ pub fn inlines_to_map(attrs: &[Inline]) -> HashMap<String, Value> {
 pub fn embed_attrs_to_map(attrs: &[EmbedAttr]) -> HashMap<String, Value> {
     attrs.iter().map(|a| a.as_kv()).collect()
 }
+pub fn blocks_to_map(attrs: &[Block]) -> HashMap<String, Value> {
+    attrs.iter().map(|a| a.as_kv()).collect()
+}
+/// only the inline formats, see [`jsvalue_to_formats`]
 pub fn jsvalue_to_inlines(value: JsValue) -> Vec<Inline> {
+    jsvalue_to_formats(value).inlines
+}
+pub fn jsvalue_to_formats(value: JsValue) -> Formats {
     // Try to parse JsValue as { key: value } map
     let Ok(map): Result<HashMap<String, Value>, _> = serde_wasm_bindgen::from_value(value) else {
-        return vec![];
+        return Formats::default();
     };
+    Formats::from_kvs(map)
+}
 
-    map.into_iter()
-        .filter_map(|(k, v)| match (k.as_str(), v) {
-            ("bold", Value::Bool(true)) => Some(Inline::Bold),
-            ("italic", Value::Bool(true)) => Some(Inline::Italic),
-            ("underline", Value::Bool(true)) => Some(Inline::Underline),
-            ("strike", Value::Bool(true)) => Some(Inline::Strike),
-            ("code", Value::Bool(true)) => Some(Inline::Code),
-            ("link", Value::String(s)) => Some(Inline::Link(s)),
-            ("background", Value::String(s)) => Some(Inline::BgColor(s)),
-            ("color", Value::String(s)) => Some(Inline::Color(s)),
-            ("font", Value::String(s)) => Some(Inline::Font(s)),
-            ("size", Value::Number(s)) => Some(Inline::Size(s.as_f64().unwrap_or_default())),
-            ("script", Value::String(ref s)) if s == "sub" => Some(Inline::Sub),
-            ("script", Value::String(ref s)) if s == "super" => Some(Inline::Sup),
-            ("align", Value::String(s)) => Some(Inline::Align(s)),
-            (k, v) => Some(Inline::Custom(k.to_string(), v)),
-        })
-        .collect()
+/// the formats of a range, as returned by [`QuillEditor::get_format`](crate::QuillEditor::get_format)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Formats {
+    pub inlines: Vec<Inline>,
+    pub blocks: Vec<Block>,
+}
+
+impl Formats {
+    /// splits `name -> value` pairs into inline and line formats, a known line format with an
+    /// invalid value becomes a [`Block::Custom`]
+    pub fn from_kvs(kvs: impl IntoIterator<Item = (String, Value)>) -> Self {
+        let mut formats = Self::default();
+        for (k, v) in kvs {
+            if Block::NAMES.contains(&k.as_str()) {
+                formats
+                    .blocks
+                    .push(Block::from_kv(&k, &v).unwrap_or(Block::Custom(k, v)));
+            } else {
+                formats.inlines.push(Inline::from_kv(k, v));
+            }
+        }
+        formats
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)] // ✅ ADDED: Hash + Eq for HashSet
@@ -65,20 +87,42 @@ pub enum Inline {
     Strike,
     Code,
     /// higlight color (background-color)
-    BgColor(String),
+    BgColor(Color),
     /// text color
-    Color(String),
-    /// font family
-    Font(String),
-    /// text size
-    Size(f64),
+    Color(Color),
+    /// font family, must be whitelisted, see [`QuillOptionsBuilder::fonts`](crate::QuillOptionsBuilder::fonts)
+    Font(Font),
+    /// text size, must be whitelisted, see [`QuillOptionsBuilder::sizes`](crate::QuillOptionsBuilder::sizes)
+    Size(Size),
     Sub,
     Sup,
+    #[deprecated(note = "align is a line format, use `Block::Align`")]
     Align(String),
     Custom(String, Value),
 }
 
 impl Inline {
+    /// unknown names and unexpected values (e.g. a color that does not parse) become
+    /// [`Inline::Custom`]
+    pub fn from_kv(k: String, v: Value) -> Self {
+        let inline = match (k.as_str(), &v) {
+            ("bold", Value::Bool(true)) => Some(Inline::Bold),
+            ("italic", Value::Bool(true)) => Some(Inline::Italic),
+            ("underline", Value::Bool(true)) => Some(Inline::Underline),
+            ("strike", Value::Bool(true)) => Some(Inline::Strike),
+            ("code", Value::Bool(true)) => Some(Inline::Code),
+            ("link", Value::String(s)) => Some(Inline::Link(s.clone())),
+            ("background", Value::String(s)) => Color::parse(s).map(Inline::BgColor),
+            ("color", Value::String(s)) => Color::parse(s).map(Inline::Color),
+            ("font", Value::String(s)) => Some(Inline::Font(Font::parse(s))),
+            ("size", Value::String(s)) => Some(Inline::Size(Size::parse(s))),
+            ("script", Value::String(s)) if s == "sub" => Some(Inline::Sub),
+            ("script", Value::String(s)) if s == "super" => Some(Inline::Sup),
+            _ => None,
+        };
+        inline.unwrap_or(Inline::Custom(k, v))
+    }
+    #[allow(deprecated)]
     pub fn as_kv(&self) -> (String, Value) {
         use Inline::*;
         match self {
@@ -88,10 +132,10 @@ impl Inline {
             Strike => ("strike".into(), Value::Bool(true)),
             Code => ("code".into(), Value::Bool(true)),
             Link(v) => ("link".into(), Value::String(v.clone())),
-            BgColor(v) => ("background".into(), Value::String(v.clone())),
-            Color(v) => ("color".into(), Value::String(v.clone())),
-            Font(v) => ("font".into(), Value::String(v.clone())),
-            Size(v) => ("size".into(), Value::Number(Number::from_f64(*v).unwrap())),
+            BgColor(v) => ("background".into(), Value::String(v.to_string())),
+            Color(v) => ("color".into(), Value::String(v.to_string())),
+            Font(v) => ("font".into(), Value::String(v.to_string())),
+            Size(v) => ("size".into(), Value::String(v.to_string())),
             Sub => ("script".into(), Value::String("sub".into())),
             Sup => ("script".into(), Value::String("super".into())),
             Align(v) => ("align".into(), Value::String(v.clone())),
@@ -100,26 +144,350 @@ impl Inline {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// a `size` value. Quill only renders whitelisted values: `small`, `large` and `huge` out of the
+/// box, anything else has to be registered, see [`register_sizes`](crate::register_sizes).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Size {
+    Small,
+    Large,
+    Huge,
+    /// a pixel size such as `"18px"`, needs the style attributor
+    Px(f64),
+    Custom(String),
+}
+
+impl Size {
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "small" => Self::Small,
+            "large" => Self::Large,
+            "huge" => Self::Huge,
+            _ => value
+                .strip_suffix("px")
+                .and_then(|px| px.parse().ok())
+                .map(Self::Px)
+                .unwrap_or_else(|| Self::Custom(value.to_string())),
+        }
+    }
+}
+
+impl Display for Size {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Small => write!(f, "small"),
+            Self::Large => write!(f, "large"),
+            Self::Huge => write!(f, "huge"),
+            Self::Px(px) => write!(f, "{px}px"),
+            Self::Custom(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+impl From<Size> for JsValue {
+    /// handy for toolbar picker options
+    fn from(value: Size) -> Self {
+        JsValue::from_str(&value.to_string())
+    }
+}
+
+/// a `font` value. Quill only renders whitelisted values: `serif` and `monospace` out of the box,
+/// anything else has to be registered, see [`register_fonts`](crate::register_fonts).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Font {
+    Serif,
+    Monospace,
+    /// a class name (`roboto` -> `.ql-font-roboto`) or a css `font-family`, depending on
+    /// the registered attributor
+    Custom(String),
+}
+
+impl Font {
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "serif" => Self::Serif,
+            "monospace" => Self::Monospace,
+            _ => Self::Custom(value.to_string()),
+        }
+    }
+}
+
+impl Display for Font {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Serif => write!(f, "serif"),
+            Self::Monospace => write!(f, "monospace"),
+            Self::Custom(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+impl From<Font> for JsValue {
+    /// handy for toolbar picker options
+    fn from(value: Font) -> Self {
+        JsValue::from_str(&value.to_string())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ListKind {
+    Ordered,
+    Bullet,
+    Checked,
+    Unchecked,
+}
+
+impl ListKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ordered => "ordered",
+            Self::Bullet => "bullet",
+            Self::Checked => "checked",
+            Self::Unchecked => "unchecked",
+        }
+    }
+    pub fn parse(value: &str) -> Option<Self> {
+        Some(match value {
+            "ordered" => Self::Ordered,
+            "bullet" => Self::Bullet,
+            "checked" => Self::Checked,
+            "unchecked" => Self::Unchecked,
+            _ => return None,
+        })
+    }
+}
+
+/// left is Quill's default, it is never stored
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Align {
+    Center,
+    Right,
+    Justify,
+}
+
+impl Align {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Center => "center",
+            Self::Right => "right",
+            Self::Justify => "justify",
+        }
+    }
+    pub fn parse(value: &str) -> Option<Self> {
+        Some(match value {
+            "center" => Self::Center,
+            "right" => Self::Right,
+            "justify" => Self::Justify,
+            _ => return None,
+        })
+    }
+}
+
+/// a `header` level, `1..=6`, see [`Block::header`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub struct HeaderLevel(u8);
+
+impl HeaderLevel {
+    pub const MIN: Self = Self(1);
+
+    /// `None` unless `1 <= level <= 6`
+    pub fn new(level: u8) -> Option<Self> {
+        (1..=Block::MAX_HEADER)
+            .contains(&level)
+            .then_some(Self(level))
+    }
+    pub fn get(self) -> u8 {
+        self.0
+    }
+}
+
+impl TryFrom<u8> for HeaderLevel {
+    type Error = String;
+
+    fn try_from(level: u8) -> Result<Self, Self::Error> {
+        Self::new(level).ok_or_else(|| format!("invalid header level `{level}`"))
+    }
+}
+
+impl From<HeaderLevel> for u8 {
+    fn from(level: HeaderLevel) -> Self {
+        level.0
+    }
+}
+
+/// an `indent` level, `1..=8`, see [`Block::indent`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub struct IndentLevel(u8);
+
+impl IndentLevel {
+    pub const MIN: Self = Self(1);
+
+    /// `None` unless `1 <= level <= 8`
+    pub fn new(level: u8) -> Option<Self> {
+        (1..=Block::MAX_INDENT)
+            .contains(&level)
+            .then_some(Self(level))
+    }
+    pub fn get(self) -> u8 {
+        self.0
+    }
+}
+
+impl TryFrom<u8> for IndentLevel {
+    type Error = String;
+
+    fn try_from(level: u8) -> Result<Self, Self::Error> {
+        Self::new(level).ok_or_else(|| format!("invalid indent level `{level}`"))
+    }
+}
+
+impl From<IndentLevel> for u8 {
+    fn from(level: IndentLevel) -> Self {
+        level.0
+    }
+}
+
+/// line formats, Quill stores them on the `"\n"` ending the line.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Block {
+    /// see [`Block::header`]
+    Header(HeaderLevel),
+    List(ListKind),
+    Blockquote,
+    /// the language in Quill 2 (`"plain"`, `"rust"`, ...), `None` for Quill 1's `true`
+    CodeBlock(Option<String>),
+    /// see [`Block::indent`]
+    Indent(IndentLevel),
+    Align(Align),
+    /// right to left text
+    Rtl,
+    Custom(String, Value),
+}
+
+impl Block {
+    /// names of the line formats known by Quill
+    pub const NAMES: [&str; 7] = [
+        "header",
+        "list",
+        "blockquote",
+        "code-block",
+        "indent",
+        "align",
+        "direction",
+    ];
+    pub const MAX_HEADER: u8 = 6;
+    pub const MAX_INDENT: u8 = 8;
+
+    /// `None` unless `1 <= level <= 6`
+    pub fn header(level: u8) -> Option<Self> {
+        HeaderLevel::new(level).map(Self::Header)
+    }
+    /// `None` unless `1 <= level <= 8`
+    pub fn indent(level: u8) -> Option<Self> {
+        IndentLevel::new(level).map(Self::Indent)
+    }
+    /// parses a known line format, `None` for unknown names and invalid values
+    pub fn from_kv(k: &str, v: &Value) -> Option<Self> {
+        let level = || v.as_u64().and_then(|n| u8::try_from(n).ok());
+        match (k, v) {
+            ("header", _) => Self::header(level()?),
+            ("list", Value::String(s)) => ListKind::parse(s).map(Self::List),
+            ("blockquote", Value::Bool(true)) => Some(Self::Blockquote),
+            ("code-block", Value::Bool(true)) => Some(Self::CodeBlock(None)),
+            ("code-block", Value::String(s)) => Some(Self::CodeBlock(Some(s.clone()))),
+            ("indent", _) => Self::indent(level()?),
+            ("align", Value::String(s)) => Align::parse(s).map(Self::Align),
+            ("direction", Value::String(s)) if s == "rtl" => Some(Self::Rtl),
+            _ => None,
+        }
+    }
+    pub fn as_kv(&self) -> (String, Value) {
+        use Block::*;
+        match self {
+            Header(v) => ("header".into(), Value::from(v.get())),
+            List(v) => ("list".into(), Value::from(v.as_str())),
+            Blockquote => ("blockquote".into(), Value::Bool(true)),
+            CodeBlock(None) => ("code-block".into(), Value::Bool(true)),
+            CodeBlock(Some(v)) => ("code-block".into(), Value::String(v.clone())),
+            Indent(v) => ("indent".into(), Value::from(v.get())),
+            Align(v) => ("align".into(), Value::from(v.as_str())),
+            Rtl => ("direction".into(), Value::from("rtl")),
+            Custom(k, v) => (k.clone(), v.clone()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum Embeds {
     Video(String),
     Image(String),
     Formula(String),
+    /// any other embed, by blot name, see [`CustomEmbed`] for typed ones
+    Custom(String, Value),
 }
 
 impl Embeds {
-    pub fn get_value(&self) -> &str {
+    pub fn get_value(&self) -> Value {
         match self {
-            Self::Video(v) => v,
-            Self::Image(v) => v,
-            Self::Formula(v) => v,
+            Self::Video(v) | Self::Image(v) | Self::Formula(v) => Value::String(v.clone()),
+            Self::Custom(_, v) => v.clone(),
         }
     }
     pub fn get_type_name(&self) -> &str {
         match self {
             Self::Video(..) => "video",
             Self::Image(..) => "image",
-            _ => "formula",
+            Self::Formula(..) => "formula",
+            Self::Custom(name, _) => name,
+        }
+    }
+}
+
+/// an application defined embed, stored in deltas as `{NAME: <self as json>}`.
+///
+/// the matching blot still has to be registered on the js side (see [`Quill::register`]) for
+/// the editor to render it.
+///
+/// ```
+/// # use quill_wr::*;
+/// # use serde::{Deserialize, Serialize};
+/// #[derive(Serialize, Deserialize, PartialEq, Debug)]
+/// struct Mention {
+///     id: u32,
+///     name: String,
+/// }
+/// impl CustomEmbed for Mention {
+///     const NAME: &'static str = "mention";
+/// }
+///
+/// let mention = Mention { id: 7, name: "Ada".into() };
+/// let mut doc = Delta::new();
+/// doc.insert_embed(mention.to_embed(), &[]).insert_text("\n", &[]);
+/// assert_eq!(doc.to_json().unwrap(), r#"{"ops":[{"insert":{"mention":{"id":7,"name":"Ada"}}},{"insert":"\n"}]}"#);
+///
+/// let Op::Insert { insert: Insert::Embed(embed), .. } = &doc.ops()[0] else { unreachable!() };
+/// assert_eq!(embed.to_custom::<Mention>(), Some(mention));
+/// ```
+///
+/// [`Quill::register`]: https://quilljs.com/docs/api#register
+pub trait CustomEmbed: Serialize + DeserializeOwned {
+    /// the blot name
+    const NAME: &'static str;
+
+    fn to_embed(&self) -> Embeds {
+        Embeds::Custom(
+            Self::NAME.to_string(),
+            serde_json::to_value(self).expect("failed to serialize embed"),
+        )
+    }
+    /// `None` when the name differs or the value does not deserialize into `Self`
+    fn from_embed(embed: &Embeds) -> Option<Self> {
+        match embed {
+            Embeds::Custom(name, value) if name == Self::NAME => {
+                serde_json::from_value(value.clone()).ok()
+            }
+            _ => None,
         }
     }
 }
@@ -11,6 +11,8 @@ pub fn test_quill() {
         .theme("snow")
         .placeholder("Type here...")
         .modules(modules)
+        .sizes(Attributor::Style, vec![Size::Px(12.0), Size::Px(50.0)])
+        .fonts(Attributor::Class, vec![Font::Serif, Font::Monospace])
         .build();
 
     let editor = QuillEditor::new_with_options("#editor", options);
@@ -35,10 +37,10 @@ pub fn test_quill() {
         Inline::Underline,
         Inline::Code,
         Inline::Strike,
-        // Inline::BgColor("red".to_string()),
+        Inline::BgColor(Color::rgb(255, 0, 0)),
         Inline::Sup,
         Inline::Custom("background".to_string(), Value::String("red".into())), //works
-        Inline::Size(50.0),                                                    //not working
+        Inline::Size(Size::Px(50.0)),
     ];
     editor.insert_embed(
         50,
@@ -51,7 +53,7 @@ pub fn test_quill() {
     gloo::console::log!(inserted.to_json().unwrap());
     editor.delete_text(0, 20, None);
     editor.update_content(contnt, None);
-    editor.format(Inline::Color("red".into()), Some(Source::Api));
+    editor.format(Inline::Color(Color::rgb(255, 0, 0)), Some(Source::Api));
     editor.blur();
     editor.disable();
     editor.enable(true);
@@ -69,3 +71,21 @@ pub fn test_quill() {
     let sem_html = editor.get_semantic_html(Some(0), Some(10));
     gloo::console::log!(sem_html);*/
 }
+
+/// a delta the url policy can't read must not reach Quill unchecked
+#[wasm_bindgen]
+pub fn test_sanitize() {
+    let options = QuillOptions::builder()
+        .sanitize(SanitizePolicy::default())
+        .build();
+    let editor = QuillEditor::new_with_options("#sanitized", options);
+    // retaining an embed does not deserialize, see `Op`
+    let crafted = DeltaEditor::from_json(
+        r#"{"ops":[{"retain":{"table":1}},{"insert":"x","attributes":{"link":"javascript:alert(1)"}}]}"#,
+    )
+    .expect("Failed to parse json");
+    let change = editor.update_content(crafted, None);
+    assert_eq!(change.to_json().unwrap(), r#"{"ops":[]}"#);
+    let content = editor.get_content(None, None).to_json().unwrap();
+    assert!(!content.contains("javascript:"), "{content}");
+}
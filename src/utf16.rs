@@ -0,0 +1,186 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Range, Sub, SubAssign},
+};
+use unicode_segmentation::UnicodeSegmentation;
+use wasm_bindgen::JsValue;
+
+/// an index or a length counted in UTF-16 code units.
+///
+/// Quill (like every js string) counts text in UTF-16 code units: `"a😀"` has a length of 3,
+/// while the rust `str` holding it is 5 bytes and 2 chars long. every index and length handed
+/// to or received from Quill and every [`Delta`](crate::Delta) length uses this unit, use the
+/// conversions below before slicing a rust string with it.
+///
+/// ```
+/// # use quill_wr::Utf16Index;
+/// let text = "a😀b";
+/// let index = Utf16Index(3); // right after the emoji
+/// assert_eq!(index.to_byte_offset(text), Some(5));
+/// assert_eq!(index.to_char_offset(text), Some(2));
+/// assert_eq!(Utf16Index::len_of(text), 4);
+/// ```
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Utf16Index(pub usize);
+
+impl Utf16Index {
+    pub const ZERO: Self = Self(0);
+    /// stands for "until the end", like js' `Infinity` in quill-delta
+    pub const MAX: Self = Self(usize::MAX);
+
+    pub fn get(self) -> usize {
+        self.0
+    }
+    /// saturates, as expected by the js bindings
+    pub fn as_u32(self) -> u32 {
+        u32::try_from(self.0).unwrap_or(u32::MAX)
+    }
+    pub fn saturating_add(self, other: Self) -> Self {
+        Self(self.0.saturating_add(other.0))
+    }
+    pub fn saturating_sub(self, other: Self) -> Self {
+        Self(self.0.saturating_sub(other.0))
+    }
+
+    /// UTF-16 length of `text`
+    pub fn len_of(text: &str) -> Self {
+        Self(text.chars().map(char::len_utf16).sum())
+    }
+
+    /// `None` when `byte` is past the end or not on a char boundary
+    pub fn from_byte_offset(text: &str, byte: usize) -> Option<Self> {
+        text.is_char_boundary(byte)
+            .then(|| Self::len_of(&text[..byte]))
+    }
+    /// `None` when the index is past the end or splits a surrogate pair
+    pub fn to_byte_offset(self, text: &str) -> Option<usize> {
+        let mut units = 0;
+        for (byte, ch) in text.char_indices() {
+            if units == self.0 {
+                return Some(byte);
+            }
+            if units > self.0 {
+                return None;
+            }
+            units += ch.len_utf16();
+        }
+        (units == self.0).then_some(text.len())
+    }
+
+    /// `None` when `text` has fewer than `chars` chars
+    pub fn from_char_offset(text: &str, chars: usize) -> Option<Self> {
+        let byte = text
+            .char_indices()
+            .map(|(byte, _)| byte)
+            .chain([text.len()])
+            .nth(chars)?;
+        Self::from_byte_offset(text, byte)
+    }
+    pub fn to_char_offset(self, text: &str) -> Option<usize> {
+        let byte = self.to_byte_offset(text)?;
+        Some(text[..byte].chars().count())
+    }
+
+    /// counts extended grapheme clusters, what a user sees as one character (e.g. `"👍🏽"`)
+    pub fn from_grapheme_offset(text: &str, graphemes: usize) -> Option<Self> {
+        let byte = text
+            .grapheme_indices(true)
+            .map(|(byte, _)| byte)
+            .chain([text.len()])
+            .nth(graphemes)?;
+        Self::from_byte_offset(text, byte)
+    }
+    /// `None` when the index falls inside a grapheme cluster
+    pub fn to_grapheme_offset(self, text: &str) -> Option<usize> {
+        let byte = self.to_byte_offset(text)?;
+        text.grapheme_indices(true)
+            .map(|(byte, _)| byte)
+            .chain([text.len()])
+            .position(|b| b == byte)
+    }
+
+    /// slices `text` with a UTF-16 range, `None` when a bound is out of range or splits a char
+    pub fn slice(text: &str, range: Range<Utf16Index>) -> Option<&str> {
+        let start = range.start.to_byte_offset(text)?;
+        let end = range.end.to_byte_offset(text)?;
+        text.get(start..end)
+    }
+}
+
+/// byte offset of `index` inside `text`, rounding an index that splits a surrogate pair up to
+/// the end of that char and clamping to the end of `text`.
+pub(crate) fn utf16_to_byte(text: &str, index: Utf16Index) -> usize {
+    let mut units = 0;
+    for (byte, ch) in text.char_indices() {
+        if units >= index.0 {
+            return byte;
+        }
+        units += ch.len_utf16();
+    }
+    text.len()
+}
+
+/// lenient version of [`Utf16Index::slice`], see [`utf16_to_byte`]
+pub(crate) fn utf16_slice(text: &str, start: Utf16Index, end: Utf16Index) -> &str {
+    let start = utf16_to_byte(text, start);
+    let end = utf16_to_byte(text, end).max(start);
+    &text[start..end]
+}
+
+impl From<usize> for Utf16Index {
+    fn from(value: usize) -> Self {
+        Self(value)
+    }
+}
+impl From<Utf16Index> for usize {
+    fn from(value: Utf16Index) -> Self {
+        value.0
+    }
+}
+impl From<Utf16Index> for JsValue {
+    fn from(value: Utf16Index) -> Self {
+        JsValue::from(value.0 as f64)
+    }
+}
+impl PartialEq<usize> for Utf16Index {
+    fn eq(&self, other: &usize) -> bool {
+        self.0 == *other
+    }
+}
+impl Display for Utf16Index {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Add for Utf16Index {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+impl Sub for Utf16Index {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 - rhs.0)
+    }
+}
+impl AddAssign for Utf16Index {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+impl SubAssign for Utf16Index {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 -= rhs.0;
+    }
+}
+impl std::iter::Sum for Utf16Index {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        Self(iter.map(|i| i.0).sum())
+    }
+}
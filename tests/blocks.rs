@@ -0,0 +1,127 @@
+mod common;
+
+use common::delta;
+use quill_wr::{
+    Align, Block, Delta, Formats, HeaderLevel, IndentLevel, Inline, ListKind, Schema, delta,
+};
+use serde_json::{Value, json};
+
+#[test]
+fn parses_valid_values_only() {
+    let cases = [
+        ("header", json!(2), Block::header(2)),
+        ("header", json!(7), None),
+        ("header", json!("2"), None),
+        (
+            "list",
+            json!("checked"),
+            Some(Block::List(ListKind::Checked)),
+        ),
+        ("list", json!("dashed"), None),
+        ("blockquote", json!(true), Some(Block::Blockquote)),
+        ("code-block", json!(true), Some(Block::CodeBlock(None))),
+        (
+            "code-block",
+            json!("rust"),
+            Some(Block::CodeBlock(Some("rust".into()))),
+        ),
+        ("indent", json!(8), Block::indent(8)),
+        ("indent", json!(0), None),
+        (
+            "align",
+            json!("justify"),
+            Some(Block::Align(Align::Justify)),
+        ),
+        ("align", json!("left"), None),
+        ("direction", json!("rtl"), Some(Block::Rtl)),
+        ("bold", json!(true), None),
+    ];
+    for (name, value, expected) in cases {
+        let block = Block::from_kv(name, &value);
+        assert_eq!(block, expected, "{name}: {value}");
+        if let Some(block) = block {
+            assert_eq!(block.as_kv(), (name.to_string(), value));
+        }
+    }
+    assert_eq!(Block::header(0), None);
+    assert_eq!(Block::indent(9), None);
+}
+
+#[test]
+fn levels_are_validated_by_serde() {
+    let header = Block::header(2).unwrap();
+    let json = serde_json::to_string(&header).unwrap();
+    assert_eq!(json, r#"{"Header":2}"#);
+    assert_eq!(serde_json::from_str::<Block>(&json).unwrap(), header);
+    assert!(serde_json::from_str::<Block>(r#"{"Header":0}"#).is_err());
+    assert!(serde_json::from_str::<Block>(r#"{"Indent":200}"#).is_err());
+    assert_eq!(
+        HeaderLevel::try_from(7),
+        Err("invalid header level `7`".to_string())
+    );
+    assert_eq!(IndentLevel::new(8).map(IndentLevel::get), Some(8));
+}
+
+#[test]
+fn splits_inline_and_line_formats() {
+    let formats = Formats::from_kvs([
+        ("bold".to_string(), json!(true)),
+        ("header".to_string(), json!(1)),
+        ("align".to_string(), json!("left")),
+        ("mention".to_string(), json!("x")),
+    ]);
+    assert_eq!(
+        formats,
+        Formats {
+            inlines: vec![
+                Inline::Bold,
+                Inline::Custom("mention".into(), Value::from("x"))
+            ],
+            blocks: vec![
+                Block::header(1).unwrap(),
+                Block::Custom("align".into(), Value::from("left"))
+            ],
+        }
+    );
+}
+
+#[test]
+fn round_trips_through_the_delta_model() {
+    let mut doc = Delta::new();
+    doc.insert_text("Title", &[])
+        .insert_newline(&[Block::header(1).unwrap(), Block::Align(Align::Center)])
+        .insert_text("item", &[Inline::Bold])
+        .insert_newline(&[Block::List(ListKind::Ordered), Block::indent(2).unwrap()]);
+    assert_eq!(
+        doc,
+        delta(json!([
+            {"insert": "Title"},
+            {"insert": "\n", "attributes": {"header": 1, "align": "center"}},
+            {"insert": "item", "attributes": {"bold": true}},
+            {"insert": "\n", "attributes": {"list": "ordered", "indent": 2}},
+        ]))
+    );
+    let blocks: Vec<_> = doc.lines().map(|line| line.blocks()).collect();
+    assert_eq!(
+        blocks,
+        vec![
+            vec![Block::Align(Align::Center), Block::header(1).unwrap()],
+            vec![Block::indent(2).unwrap(), Block::List(ListKind::Ordered)],
+        ]
+    );
+    assert_eq!(doc.validate(&Schema::default()), Ok(()));
+}
+
+#[test]
+fn restricts_schema_to_given_blocks() {
+    let schema = Schema::builder()
+        .blocks(&[Block::header(1).unwrap()])
+        .build();
+    assert!(delta!["a", "\n" { header: 6 }].validate(&schema).is_ok());
+    assert!(delta!["a", "\n" { header: 7 }].validate(&schema).is_err());
+    assert!(
+        delta!["a", "\n" { list: "bullet" }]
+            .validate(&schema)
+            .is_err()
+    );
+}
@@ -0,0 +1,93 @@
+use quill_wr::{Color, Formats, Inline};
+use serde_json::json;
+
+#[test]
+fn notations_normalize_to_hex() {
+    let red = Color::rgb(255, 0, 0);
+    for value in [
+        "red",
+        "RED",
+        "#f00",
+        "#FF0000",
+        "#ff0000ff",
+        "rgb(255, 0, 0)",
+        "rgb(255 0 0)",
+        "rgb(100%, 0%, 0%)",
+        "rgba(255, 0, 0, 1)",
+        "hsl(0, 100%, 50%)",
+        "hsl(360deg 100% 50%)",
+        " hsla(0, 100%, 50%, 100%) ",
+    ] {
+        assert_eq!(Color::parse(value), Some(red), "{value}");
+    }
+    assert_eq!(red.to_string(), "#ff0000");
+}
+
+#[test]
+fn alpha() {
+    let half = Color::parse("rgba(0, 102, 204, 0.5)").unwrap();
+    assert_eq!(half, Color::rgba(0, 102, 204, 128));
+    assert_eq!(half.to_string(), "#0066cc80");
+    assert_eq!(Color::parse("rgb(0 102 204 / 50%)"), Some(half));
+    assert_eq!(Color::parse("#06c8"), Some(Color::rgba(0, 102, 204, 136)));
+    assert_eq!(
+        Color::parse("transparent").unwrap().to_string(),
+        "#00000000"
+    );
+}
+
+#[test]
+fn hsl() {
+    assert_eq!(
+        Color::parse("hsl(120, 100%, 25%)"),
+        Some(Color::rgb(0, 128, 0))
+    );
+    assert_eq!(
+        Color::parse("hsl(210, 100%, 40%)"),
+        Some(Color::rgb(0, 102, 204))
+    );
+}
+
+#[test]
+fn rejects_invalid() {
+    for value in [
+        "",
+        "#ff",
+        "#gggggg",
+        "rgb(1, 2)",
+        "rgb(1, 2, 3, 4, 5)",
+        "hsl(0, 100, 50)",
+        "cmyk(0, 0, 0, 0)",
+        "notacolor",
+    ] {
+        assert_eq!(Color::parse(value), None, "{value}");
+    }
+}
+
+#[test]
+fn serializes_as_canonical_string() {
+    let color: Color = serde_json::from_value(json!("rgb(230, 0, 0)")).unwrap();
+    assert_eq!(serde_json::to_value(color).unwrap(), json!("#e60000"));
+    assert!(serde_json::from_value::<Color>(json!("nope")).is_err());
+}
+
+#[test]
+fn parsed_from_quill_formats() {
+    let formats = Formats::from_kvs([
+        ("color".to_string(), json!("rgb(230, 0, 0)")),
+        ("background".to_string(), json!("yellow")),
+        ("color".to_string(), json!("var(--accent)")),
+    ]);
+    assert_eq!(
+        formats.inlines,
+        vec![
+            Inline::Color(Color::rgb(230, 0, 0)),
+            Inline::BgColor(Color::rgb(255, 255, 0)),
+            Inline::Custom("color".into(), json!("var(--accent)")),
+        ]
+    );
+    assert_eq!(
+        Inline::BgColor(Color::rgb(255, 255, 0)).as_kv(),
+        ("background".to_string(), json!("#ffff00"))
+    );
+}
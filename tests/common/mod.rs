@@ -0,0 +1,139 @@
+//! helpers shared by the native delta tests
+#![allow(dead_code)]
+use std::io::{Cursor, Read};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use proptest::prelude::*;
+use quill_wr::{AttributeMap, Delta, Embed, Insert, Op};
+use serde_json::Value;
+use zip::ZipArchive;
+
+/// a 2x3 png
+pub const PNG: &str = "data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAIAAAADCAYAAAC56t6BAAAAEUlEQVR4n\
+                       GP8z8DwnwEJMAEAJgoC/xg5c7QAAAAASUVORK5CYII=";
+
+/// the bytes of [`PNG`]
+pub fn png_bytes() -> Vec<u8> {
+    STANDARD.decode(PNG.split_once(',').unwrap().1).unwrap()
+}
+
+/// the parts of a zip package, docx or odt, in archive order
+pub fn parts(package: &[u8]) -> Vec<(String, Vec<u8>)> {
+    let mut archive = ZipArchive::new(Cursor::new(package)).unwrap();
+    (0..archive.len())
+        .map(|index| {
+            let mut file = archive.by_index(index).unwrap();
+            let mut bytes = vec![];
+            file.read_to_end(&mut bytes).unwrap();
+            (file.name().to_string(), bytes)
+        })
+        .collect()
+}
+
+/// a text part of a zip package
+pub fn part(package: &[u8], name: &str) -> String {
+    let (_, bytes) = parts(package)
+        .into_iter()
+        .find(|(part, _)| part == name)
+        .unwrap();
+    String::from_utf8(bytes).unwrap()
+}
+
+/// builds a delta the way quill-delta's builder does, merging ops on push
+pub fn delta(ops: Value) -> Delta {
+    serde_json::from_value::<Vec<Op>>(ops)
+        .unwrap()
+        .into_iter()
+        .collect()
+}
+
+pub fn arb_attributes() -> impl Strategy<Value = AttributeMap> {
+    prop::collection::btree_map(
+        prop::sample::select(vec!["bold", "italic", "color", "link"]).prop_map(String::from),
+        prop_oneof![
+            Just(Value::Null),
+            Just(Value::Bool(true)),
+            prop::sample::select(vec!["red", "blue"]).prop_map(Value::from),
+        ],
+        0..3,
+    )
+}
+
+pub fn arb_text() -> impl Strategy<Value = String> {
+    // mixes one and two byte utf-8 chars, still one UTF-16 unit each
+    "[abé中]{1,5}"
+}
+
+pub fn arb_document() -> impl Strategy<Value = Delta> {
+    let run = (
+        prop_oneof![
+            4 => arb_text().prop_map(Insert::Text),
+            1 => Just(Insert::Embed(Embed::new("image", "a.png"))),
+        ],
+        arb_attributes(),
+    );
+    prop::collection::vec(run, 0..6).prop_map(|runs| {
+        let mut doc = Delta::new();
+        for (insert, mut attributes) in runs {
+            attributes.retain(|_, v| !v.is_null());
+            doc.insert(insert, attributes);
+        }
+        doc.insert("\n", AttributeMap::new());
+        doc
+    })
+}
+
+#[derive(Debug, Clone)]
+pub enum Action {
+    Retain(usize, AttributeMap),
+    Delete(usize),
+    Insert(String, AttributeMap),
+}
+
+pub fn arb_action() -> impl Strategy<Value = Action> {
+    prop_oneof![
+        (1..4usize, arb_attributes()).prop_map(|(n, a)| Action::Retain(n, a)),
+        (1..4usize).prop_map(Action::Delete),
+        (arb_text(), arb_attributes()).prop_map(|(t, mut a)| {
+            a.retain(|_, v| !v.is_null());
+            Action::Insert(t, a)
+        }),
+    ]
+}
+
+/// turns random actions into a change that fits a document of `length`
+pub fn build_change(length: usize, actions: Vec<Action>) -> Delta {
+    let mut left = length;
+    let mut change = Delta::new();
+    for action in actions {
+        match action {
+            Action::Retain(n, attributes) if left > 0 => {
+                let n = n.min(left);
+                change.retain(n, attributes);
+                left -= n;
+            }
+            Action::Delete(n) if left > 0 => {
+                let n = n.min(left);
+                change.delete(n);
+                left -= n;
+            }
+            Action::Insert(text, attributes) => {
+                change.insert(text, attributes);
+            }
+            _ => {}
+        }
+    }
+    change
+}
+
+pub fn arb_concurrent_changes() -> impl Strategy<Value = (Delta, Delta, Delta)> {
+    arb_document().prop_flat_map(|doc| {
+        let length = doc.length().get();
+        let actions = || prop::collection::vec(arb_action(), 0..8);
+        (
+            Just(doc),
+            actions().prop_map(move |a| build_change(length, a)),
+            actions().prop_map(move |b| build_change(length, b)),
+        )
+    })
+}
@@ -0,0 +1,249 @@
+//! parity fixtures taken from quill-delta's `test/delta/compose.js`
+mod common;
+use common::delta;
+use serde_json::{Value, json};
+
+fn assert_compose(a: Value, b: Value, expected: Value) {
+    assert_eq!(delta(a).compose(&delta(b)), delta(expected));
+}
+
+#[test]
+fn insert_insert() {
+    assert_compose(
+        json!([{"insert": "A"}]),
+        json!([{"insert": "B"}]),
+        json!([{"insert": "BA"}]),
+    );
+}
+
+#[test]
+fn insert_retain() {
+    assert_compose(
+        json!([{"insert": "A"}]),
+        json!([{"retain": 1, "attributes": {"bold": true, "color": "red", "font": null}}]),
+        json!([{"insert": "A", "attributes": {"bold": true, "color": "red"}}]),
+    );
+}
+
+#[test]
+fn insert_delete() {
+    assert_compose(json!([{"insert": "A"}]), json!([{"delete": 1}]), json!([]));
+}
+
+#[test]
+fn delete_insert() {
+    assert_compose(
+        json!([{"delete": 1}]),
+        json!([{"insert": "B"}]),
+        json!([{"insert": "B"}, {"delete": 1}]),
+    );
+}
+
+#[test]
+fn delete_retain() {
+    assert_compose(
+        json!([{"delete": 1}]),
+        json!([{"retain": 1, "attributes": {"bold": true, "color": "red"}}]),
+        json!([{"delete": 1}, {"retain": 1, "attributes": {"bold": true, "color": "red"}}]),
+    );
+}
+
+#[test]
+fn delete_delete() {
+    assert_compose(
+        json!([{"delete": 1}]),
+        json!([{"delete": 1}]),
+        json!([{"delete": 2}]),
+    );
+}
+
+#[test]
+fn retain_insert() {
+    assert_compose(
+        json!([{"retain": 1, "attributes": {"color": "blue"}}]),
+        json!([{"insert": "B"}]),
+        json!([{"insert": "B"}, {"retain": 1, "attributes": {"color": "blue"}}]),
+    );
+}
+
+#[test]
+fn retain_retain() {
+    assert_compose(
+        json!([{"retain": 1, "attributes": {"color": "blue"}}]),
+        json!([{"retain": 1, "attributes": {"bold": true, "color": "red", "font": null}}]),
+        json!([{"retain": 1, "attributes": {"bold": true, "color": "red", "font": null}}]),
+    );
+}
+
+#[test]
+fn retain_delete() {
+    assert_compose(
+        json!([{"retain": 1, "attributes": {"color": "blue"}}]),
+        json!([{"delete": 1}]),
+        json!([{"delete": 1}]),
+    );
+}
+
+#[test]
+fn insert_in_middle_of_text() {
+    assert_compose(
+        json!([{"insert": "Hello"}]),
+        json!([{"retain": 3}, {"insert": "X"}]),
+        json!([{"insert": "HelXlo"}]),
+    );
+}
+
+#[test]
+fn insert_and_delete_ordering() {
+    let expected = json!([{"insert": "HelXo"}]);
+    assert_compose(
+        json!([{"insert": "Hello"}]),
+        json!([{"retain": 3}, {"insert": "X"}, {"delete": 1}]),
+        expected.clone(),
+    );
+    assert_compose(
+        json!([{"insert": "Hello"}]),
+        json!([{"retain": 3}, {"delete": 1}, {"insert": "X"}]),
+        expected,
+    );
+}
+
+#[test]
+fn insert_embed() {
+    assert_compose(
+        json!([{"insert": {"image": "http://quilljs.com/image.png"}, "attributes": {"width": "300"}}]),
+        json!([{"retain": 1, "attributes": {"alt": "logo"}}]),
+        json!([{"insert": {"image": "http://quilljs.com/image.png"}, "attributes": {"width": "300", "alt": "logo"}}]),
+    );
+}
+
+#[test]
+fn delete_entire_text() {
+    assert_compose(
+        json!([{"retain": 4}, {"insert": "Hello"}]),
+        json!([{"delete": 9}]),
+        json!([{"delete": 4}]),
+    );
+}
+
+#[test]
+fn retain_more_than_length_of_text() {
+    assert_compose(
+        json!([{"insert": "Hello"}]),
+        json!([{"retain": 10}]),
+        json!([{"insert": "Hello"}]),
+    );
+}
+
+#[test]
+fn retain_empty_embed() {
+    assert_compose(
+        json!([{"insert": {"image": "http://quilljs.com/image.png"}}]),
+        json!([{"retain": 1}]),
+        json!([{"insert": {"image": "http://quilljs.com/image.png"}}]),
+    );
+}
+
+#[test]
+fn remove_all_attributes() {
+    assert_compose(
+        json!([{"insert": "A", "attributes": {"bold": true}}]),
+        json!([{"retain": 1, "attributes": {"bold": null}}]),
+        json!([{"insert": "A"}]),
+    );
+}
+
+#[test]
+fn remove_all_embed_attributes() {
+    assert_compose(
+        json!([{"insert": {"image": "http://quilljs.com/image.png"}, "attributes": {"bold": true}}]),
+        json!([{"retain": 1, "attributes": {"bold": null}}]),
+        json!([{"insert": {"image": "http://quilljs.com/image.png"}}]),
+    );
+}
+
+#[test]
+fn retain_start_optimization() {
+    assert_compose(
+        json!([
+            {"insert": "A", "attributes": {"bold": true}},
+            {"insert": "B"},
+            {"insert": "C", "attributes": {"bold": true}},
+            {"delete": 1}
+        ]),
+        json!([{"retain": 3}, {"insert": "D"}]),
+        json!([
+            {"insert": "A", "attributes": {"bold": true}},
+            {"insert": "B"},
+            {"insert": "C", "attributes": {"bold": true}},
+            {"insert": "D"},
+            {"delete": 1}
+        ]),
+    );
+}
+
+#[test]
+fn retain_start_optimization_split() {
+    assert_compose(
+        json!([
+            {"insert": "A", "attributes": {"bold": true}},
+            {"insert": "B"},
+            {"insert": "C", "attributes": {"bold": true}},
+            {"retain": 5},
+            {"delete": 1}
+        ]),
+        json!([{"retain": 4}, {"insert": "D"}]),
+        json!([
+            {"insert": "A", "attributes": {"bold": true}},
+            {"insert": "B"},
+            {"insert": "C", "attributes": {"bold": true}},
+            {"retain": 1},
+            {"insert": "D"},
+            {"retain": 4},
+            {"delete": 1}
+        ]),
+    );
+}
+
+#[test]
+fn retain_end_optimization() {
+    assert_compose(
+        json!([
+            {"insert": "A", "attributes": {"bold": true}},
+            {"insert": "B"},
+            {"insert": "C", "attributes": {"bold": true}}
+        ]),
+        json!([{"delete": 1}]),
+        json!([{"insert": "B"}, {"insert": "C", "attributes": {"bold": true}}]),
+    );
+}
+
+#[test]
+fn retain_end_optimization_join() {
+    assert_compose(
+        json!([
+            {"insert": "A", "attributes": {"bold": true}},
+            {"insert": "B"},
+            {"insert": "C", "attributes": {"bold": true}},
+            {"insert": "D"},
+            {"insert": "E", "attributes": {"bold": true}},
+            {"insert": "F"}
+        ]),
+        json!([{"retain": 1}, {"delete": 1}]),
+        json!([
+            {"insert": "AC", "attributes": {"bold": true}},
+            {"insert": "D"},
+            {"insert": "E", "attributes": {"bold": true}},
+            {"insert": "F"}
+        ]),
+    );
+}
+
+#[test]
+fn lengths_are_utf16() {
+    assert_compose(
+        json!([{"insert": "a😀b\n"}]),
+        json!([{"retain": 1}, {"delete": 2}, {"insert": "🙂"}]),
+        json!([{"insert": "a🙂b\n"}]),
+    );
+}
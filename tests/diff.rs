@@ -0,0 +1,130 @@
+//! fixtures from quill-delta's `test/delta/diff.js`, plus word granularity and a property test
+mod common;
+use common::*;
+use proptest::prelude::*;
+use quill_wr::DiffGranularity;
+use serde_json::{Value, json};
+
+fn assert_diff(a: Value, b: Value, expected: Value) {
+    let (a, b) = (delta(a), delta(b));
+    let diff = a.diff(&b).unwrap();
+    assert_eq!(diff, delta(expected));
+    assert_eq!(a.compose(&diff), b);
+}
+
+#[test]
+fn insert() {
+    assert_diff(
+        json!([{"insert": "A"}]),
+        json!([{"insert": "AB"}]),
+        json!([{"retain": 1}, {"insert": "B"}]),
+    );
+}
+
+#[test]
+fn delete() {
+    assert_diff(
+        json!([{"insert": "AB"}]),
+        json!([{"insert": "A"}]),
+        json!([{"retain": 1}, {"delete": 1}]),
+    );
+}
+
+#[test]
+fn retain() {
+    assert_diff(
+        json!([{"insert": "A"}]),
+        json!([{"insert": "A"}]),
+        json!([]),
+    );
+}
+
+#[test]
+fn format() {
+    assert_diff(
+        json!([{"insert": "A"}]),
+        json!([{"insert": "A", "attributes": {"bold": true}}]),
+        json!([{"retain": 1, "attributes": {"bold": true}}]),
+    );
+}
+
+#[test]
+fn object_attributes() {
+    let doc =
+        json!([{"insert": "A", "attributes": {"font": {"family": "Helvetica", "size": "15px"}}}]);
+    assert_diff(doc.clone(), doc, json!([]));
+}
+
+#[test]
+fn embed_match() {
+    let doc = json!([{"insert": {"image": "http://quilljs.com"}}]);
+    assert_diff(doc.clone(), doc, json!([]));
+}
+
+#[test]
+fn embed_mismatch() {
+    assert_diff(
+        json!([{"insert": {"image": "http://quilljs.com"}}]),
+        json!([{"insert": {"image": "http://github.com"}}]),
+        json!([{"insert": {"image": "http://github.com"}}, {"delete": 1}]),
+    );
+}
+
+#[test]
+fn inconvenient_indexes() {
+    assert_diff(
+        json!([
+            {"insert": "12", "attributes": {"bold": true}},
+            {"insert": "34", "attributes": {"italic": true}}
+        ]),
+        json!([{"insert": "123", "attributes": {"color": "red"}}]),
+        json!([
+            {"retain": 2, "attributes": {"bold": null, "color": "red"}},
+            {"retain": 1, "attributes": {"italic": null, "color": "red"}},
+            {"delete": 1}
+        ]),
+    );
+}
+
+#[test]
+fn non_documents() {
+    let document = delta(json!([{"insert": "A"}]));
+    let change = delta(json!([{"retain": 1}, {"insert": "B"}]));
+    assert_eq!(document.diff(&change), None);
+    assert_eq!(change.diff(&document), None);
+}
+
+#[test]
+fn word_granularity() {
+    let a = delta(json!([{"insert": "the quick fox\n"}]));
+    let b = delta(json!([{"insert": "the quack fox\n"}]));
+    let by_word = a.diff_with(&b, DiffGranularity::Word).unwrap();
+    assert_eq!(
+        by_word,
+        delta(json!([{"retain": 4}, {"insert": "quack"}, {"delete": 5}]))
+    );
+    assert_eq!(a.compose(&by_word), b);
+    assert_eq!(
+        a.diff(&b).unwrap(),
+        delta(json!([{"retain": 6}, {"insert": "a"}, {"delete": 1}]))
+    );
+}
+
+#[test]
+fn utf16_lengths() {
+    assert_diff(
+        json!([{"insert": "😀a\n"}]),
+        json!([{"insert": "😀b\n"}]),
+        json!([{"retain": 2}, {"insert": "b"}, {"delete": 1}]),
+    );
+}
+
+proptest! {
+    #[test]
+    fn diff_turns_a_into_b(a in arb_document(), b in arb_document()) {
+        for granularity in [DiffGranularity::Char, DiffGranularity::Word] {
+            let diff = a.diff_with(&b, granularity).unwrap();
+            prop_assert_eq!(a.compose(&diff), b.clone());
+        }
+    }
+}
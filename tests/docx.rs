@@ -0,0 +1,225 @@
+mod common;
+
+use common::{PNG, part, parts, png_bytes};
+use quill_wr::{Delta, DocxExport, Embed, delta};
+
+fn document(doc: &Delta) -> String {
+    part(&doc.to_docx().unwrap(), "word/document.xml")
+}
+
+#[test]
+fn package_parts_are_well_formed_xml() {
+    let doc = delta![
+        "a <b> & \u{1}c\u{fffe}\u{ffff}" { link: "https://quilljs.com/?a=1&b=2" },
+        insert(Embed::new("image", PNG)) { alt: "\"dot\"" },
+        "\n" { list: "ordered" },
+    ];
+    let docx = doc.to_docx().unwrap();
+    let names: Vec<_> = parts(&docx).into_iter().map(|(name, _)| name).collect();
+    assert_eq!(
+        names,
+        [
+            "[Content_Types].xml",
+            "_rels/.rels",
+            "word/_rels/document.xml.rels",
+            "word/document.xml",
+            "word/styles.xml",
+            "word/numbering.xml",
+            "word/media/image1.png",
+        ]
+    );
+    for (name, bytes) in parts(&docx) {
+        if !name.ends_with(".png") {
+            let xml = String::from_utf8(bytes).unwrap();
+            roxmltree::Document::parse(&xml).unwrap_or_else(|error| panic!("{name}: {error}"));
+        }
+    }
+    assert!(part(&docx, "[Content_Types].xml").contains("Extension=\"png\""));
+    assert!(delta![].to_docx().is_ok());
+}
+
+#[test]
+fn paragraph_styles() {
+    let doc = delta![
+        "Title",
+        "\n" { header: 2, align: "center" },
+        "quote",
+        "\n" { blockquote: true },
+        "code",
+        "\n" { "code-block": "rust" },
+        "rtl",
+        "\n" { direction: "rtl", align: "justify", indent: 2 },
+    ];
+    let xml = document(&doc);
+    assert!(xml.contains(
+        "<w:p><w:pPr><w:pStyle w:val=\"Heading2\"/><w:jc w:val=\"center\"/></w:pPr>\
+         <w:r><w:t xml:space=\"preserve\">Title</w:t></w:r></w:p>"
+    ));
+    assert!(xml.contains("<w:pStyle w:val=\"Quote\"/>"));
+    assert!(xml.contains("<w:pStyle w:val=\"Code\"/>"));
+    assert!(xml.contains("<w:pPr><w:bidi/><w:ind w:left=\"1440\"/><w:jc w:val=\"both\"/></w:pPr>"));
+    let styles = part(&doc.to_docx().unwrap(), "word/styles.xml");
+    assert!(styles.contains("w:styleId=\"Heading2\"><w:name w:val=\"heading 2\"/>"));
+}
+
+#[test]
+fn run_properties() {
+    let doc = delta![
+        "x" {
+            bold: true,
+            italic: true,
+            underline: true,
+            strike: true,
+            color: "#ff0000",
+            background: "rgb(255, 255, 0)",
+            size: "18px",
+            font: "serif",
+        },
+        "2" { script: "sub", code: true },
+        "a\tb",
+        "\n",
+    ];
+    let xml = document(&doc);
+    assert!(xml.contains(
+        "<w:r><w:rPr><w:rFonts w:ascii=\"Georgia\" w:hAnsi=\"Georgia\" w:cs=\"Georgia\"/>\
+         <w:b/><w:bCs/><w:i/><w:iCs/><w:strike/><w:color w:val=\"FF0000\"/>\
+         <w:sz w:val=\"27\"/><w:szCs w:val=\"27\"/><w:u w:val=\"single\"/>\
+         <w:shd w:val=\"clear\" w:color=\"auto\" w:fill=\"FFFF00\"/></w:rPr>\
+         <w:t xml:space=\"preserve\">x</w:t></w:r>"
+    ));
+    assert!(xml.contains(
+        "<w:rPr><w:rFonts w:ascii=\"Courier New\" w:hAnsi=\"Courier New\" w:cs=\"Courier New\"/>\
+         <w:vertAlign w:val=\"subscript\"/></w:rPr>"
+    ));
+    assert!(xml.contains(
+        "<w:r><w:t xml:space=\"preserve\">a</w:t><w:tab/><w:t xml:space=\"preserve\">b</w:t></w:r>"
+    ));
+}
+
+#[test]
+fn font_sizes_stay_within_word_limits() {
+    let doc = delta![
+        "a" { size: "infpx" },
+        "b" { size: "1e6px" },
+        "c" { size: "0.1px" },
+        "\n",
+    ];
+    let xml = document(&doc);
+    assert!(xml.contains("<w:r><w:t xml:space=\"preserve\">a</w:t></w:r>"));
+    assert!(xml.contains("<w:sz w:val=\"3276\"/><w:szCs w:val=\"3276\"/>"));
+    assert!(xml.contains("<w:sz w:val=\"2\"/><w:szCs w:val=\"2\"/>"));
+}
+
+#[test]
+fn lists_restart_their_numbering() {
+    let doc = delta![
+        "one",
+        "\n" { list: "ordered" },
+        "a",
+        "\n" { list: "ordered", indent: 1 },
+        "bullet",
+        "\n" { list: "bullet" },
+        "two",
+        "\n" { list: "ordered" },
+        "todo",
+        "\n" { list: "unchecked", indent: 1 },
+        "break\n",
+        "one",
+        "\n" { list: "ordered" },
+    ];
+    let docx = doc.to_docx().unwrap();
+    let xml = part(&docx, "word/document.xml");
+    let lists: Vec<_> = xml
+        .match_indices("<w:numPr>")
+        .map(|(start, _)| &xml[start..start + 70])
+        .map(|numbering| numbering.split("</w:numPr>").next().unwrap())
+        .collect();
+    assert_eq!(
+        lists,
+        [
+            "<w:numPr><w:ilvl w:val=\"0\"/><w:numId w:val=\"4\"/>",
+            "<w:numPr><w:ilvl w:val=\"1\"/><w:numId w:val=\"4\"/>",
+            "<w:numPr><w:ilvl w:val=\"0\"/><w:numId w:val=\"1\"/>",
+            "<w:numPr><w:ilvl w:val=\"0\"/><w:numId w:val=\"4\"/>",
+            "<w:numPr><w:ilvl w:val=\"1\"/><w:numId w:val=\"3\"/>",
+            "<w:numPr><w:ilvl w:val=\"0\"/><w:numId w:val=\"5\"/>",
+        ]
+    );
+    let numbering = part(&docx, "word/numbering.xml");
+    assert!(numbering.contains(
+        "<w:num w:numId=\"5\"><w:abstractNumId w:val=\"3\"/>\
+         <w:lvlOverride w:ilvl=\"0\"><w:startOverride w:val=\"1\"/></w:lvlOverride>"
+    ));
+    assert!(numbering.contains(
+        "<w:lvl w:ilvl=\"1\"><w:start w:val=\"1\"/><w:numFmt w:val=\"lowerLetter\"/>\
+         <w:lvlText w:val=\"%2.\"/>"
+    ));
+    assert!(numbering.contains("<w:lvlText w:val=\"☐\"/>"));
+}
+
+#[test]
+fn links_share_one_hyperlink() {
+    let doc = delta![
+        "a" { link: "https://quilljs.com" },
+        "b" { link: "https://quilljs.com", bold: true },
+        " ",
+        "c" { link: "https://quilljs.com" },
+        "\n",
+        { video: "https://youtube.com/embed/x" },
+        "\n",
+    ];
+    let docx = doc.to_docx().unwrap();
+    let xml = part(&docx, "word/document.xml");
+    assert_eq!(xml.matches("<w:hyperlink r:id=\"rId3\"").count(), 2);
+    assert!(xml.contains(
+        "<w:p><w:hyperlink r:id=\"rId4\" w:history=\"1\"><w:r>\
+         <w:t xml:space=\"preserve\">https://youtube.com/embed/x</w:t></w:r></w:hyperlink></w:p>"
+    ));
+    let relationships = part(&docx, "word/_rels/document.xml.rels");
+    assert!(relationships.contains(
+        "<Relationship Id=\"rId3\" \
+         Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink\" \
+         Target=\"https://quilljs.com\" TargetMode=\"External\"/>"
+    ));
+}
+
+#[test]
+fn images_are_embedded_from_bytes() {
+    let png = png_bytes();
+    let doc = delta![
+        { image: "https://cdn.example/dot.png" } { width: "40", alt: "dot" },
+        { image: "https://cdn.example/dot.png" },
+        { image: "https://cdn.example/missing.png" } { alt: "missing" },
+        insert(Embed::new("image", PNG)),
+        "\n",
+    ];
+    let export = DocxExport::builder()
+        .images(move |src| (src == "https://cdn.example/dot.png").then(|| png.clone()))
+        .build();
+    let docx = doc.to_docx_with(&export).unwrap();
+    let xml = part(&docx, "word/document.xml");
+    // 40px wide keeps the 2:3 ratio, 9525 emu per pixel
+    assert!(xml.contains("<wp:extent cx=\"381000\" cy=\"571500\"/>"));
+    assert!(xml.contains("descr=\"dot\""));
+    assert_eq!(xml.matches("<a:blip r:embed=\"rId3\"/>").count(), 2);
+    assert!(xml.contains("<a:blip r:embed=\"rId4\"/>"));
+    assert!(xml.contains("<w:t xml:space=\"preserve\">missing</w:t>"));
+    let names: Vec<_> = parts(&docx).into_iter().map(|(name, _)| name).collect();
+    assert!(names.contains(&"word/media/image1.png".to_string()));
+    assert!(names.contains(&"word/media/image2.png".to_string()));
+}
+
+#[test]
+fn image_sizes_stay_within_word_limits() {
+    let doc = delta![
+        insert(Embed::new("image", PNG)) { width: "NaN", height: "inf" },
+        insert(Embed::new("image", PNG)) { width: "1e308" },
+        insert(Embed::new("image", PNG)) { width: "1", height: "1e300" },
+        "\n",
+    ];
+    let xml = document(&doc);
+    // unreadable sizes fall back to the 2x3 of the image
+    assert!(xml.contains("<wp:extent cx=\"19050\" cy=\"28575\"/>"));
+    assert!(xml.contains("<wp:extent cx=\"5943600\" cy=\"8915400\"/>"));
+    assert!(xml.contains("<wp:extent cx=\"9525\" cy=\"27273042316900\"/>"));
+}
@@ -0,0 +1,393 @@
+mod common;
+
+use std::{
+    cell::RefCell,
+    io::{Cursor, Write},
+    rc::Rc,
+};
+
+use common::{PNG, png_bytes};
+use quill_wr::{Delta, DocxImport, DocxImportError, DocxWarning, Embed, Inline, Schema, delta};
+use zip::{ZipWriter, write::SimpleFileOptions};
+
+const NAMESPACES: &str = "xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\" \
+     xmlns:r=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships\" \
+     xmlns:wp=\"http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing\" \
+     xmlns:a=\"http://schemas.openxmlformats.org/drawingml/2006/main\" \
+     xmlns:m=\"http://schemas.openxmlformats.org/officeDocument/2006/math\"";
+
+const RELATIONSHIPS: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+
+/// a package with `body` as its document, the relationships `(id, type, target)` and other
+/// parts
+fn docx(body: &str, relationships: &[(&str, &str, &str)], parts: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut zip = ZipWriter::new(Cursor::new(vec![]));
+    let mut part = |name: &str, bytes: &[u8]| {
+        zip.start_file(name, SimpleFileOptions::default()).unwrap();
+        zip.write_all(bytes).unwrap();
+    };
+    part(
+        "_rels/.rels",
+        format!(
+            "<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">\
+             <Relationship Id=\"rId1\" Type=\"{RELATIONSHIPS}/officeDocument\" \
+             Target=\"word/document.xml\"/></Relationships>"
+        )
+        .as_bytes(),
+    );
+    let mut rels = String::from(
+        "<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">",
+    );
+    for (id, kind, target) in relationships {
+        let mode = if target.starts_with("http") {
+            " TargetMode=\"External\""
+        } else {
+            ""
+        };
+        rels.push_str(&format!(
+            "<Relationship Id=\"{id}\" Type=\"{RELATIONSHIPS}/{kind}\" Target=\"{target}\"{mode}/>"
+        ));
+    }
+    rels.push_str("</Relationships>");
+    part("word/_rels/document.xml.rels", rels.as_bytes());
+    part(
+        "word/document.xml",
+        format!("<w:document {NAMESPACES}><w:body>{body}</w:body></w:document>").as_bytes(),
+    );
+    for (name, bytes) in parts {
+        part(name, bytes);
+    }
+    zip.finish().unwrap().into_inner()
+}
+
+fn drawing(id: &str, alt: &str) -> String {
+    format!(
+        "<w:r><w:drawing><wp:inline><wp:extent cx=\"190500\" cy=\"285750\"/>\
+         <wp:docPr id=\"1\" name=\"Picture 1\" descr=\"{alt}\"/><a:graphic><a:graphicData>\
+         <a:blip r:embed=\"{id}\"/></a:graphicData></a:graphic></wp:inline></w:drawing></w:r>"
+    )
+}
+
+#[test]
+fn docx_export_round_trips() {
+    let doc = delta![
+        "Title",
+        "\n" { header: 2, align: "center" },
+        "x" {
+            bold: true,
+            italic: true,
+            underline: true,
+            strike: true,
+            color: "#ff0000",
+            background: "#ffff00",
+            size: "large",
+            font: "serif",
+        },
+        "2" { script: "sub" },
+        "c" { code: true },
+        "a\tb ",
+        "l" { link: "https://quilljs.com" },
+        insert(Embed::new("image", PNG)) { alt: "dot", width: "2" },
+        "\n" { direction: "rtl", align: "justify", indent: 2 },
+        "one",
+        "\n" { list: "ordered" },
+        "a",
+        "\n" { list: "ordered", indent: 1 },
+        "b",
+        "\n" { list: "bullet" },
+        "c",
+        "\n" { list: "checked" },
+        "d",
+        "\n" { list: "unchecked", indent: 2 },
+        "q",
+        "\n" { blockquote: true },
+        "code",
+        "\n" { "code-block": true },
+        "\n",
+    ];
+    let imported = Delta::from_docx(&doc.to_docx().unwrap()).unwrap();
+    assert_eq!(imported.delta, doc);
+    assert_eq!(imported.warnings, []);
+    assert_eq!(
+        Delta::from_docx(&delta![].to_docx().unwrap())
+            .unwrap()
+            .delta,
+        delta!["\n"]
+    );
+}
+
+#[test]
+fn styles_and_numbering_definitions() {
+    let styles = "<w:styles xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\">\
+         <w:docDefaults><w:rPrDefault><w:rPr><w:sz w:val=\"24\"/></w:rPr></w:rPrDefault></w:docDefaults>\
+         <w:style w:type=\"paragraph\" w:default=\"1\" w:styleId=\"Normal\"><w:name w:val=\"Normal\"/></w:style>\
+         <w:style w:type=\"paragraph\" w:styleId=\"Heading3\"><w:name w:val=\"heading 3\"/>\
+         <w:rPr><w:b/><w:sz w:val=\"32\"/></w:rPr></w:style>\
+         <w:style w:type=\"paragraph\" w:styleId=\"Chapter\"><w:name w:val=\"Chapter\"/>\
+         <w:basedOn w:val=\"Heading3\"/></w:style>\
+         <w:style w:type=\"paragraph\" w:styleId=\"IntenseQuote\"><w:name w:val=\"Intense Quote\"/>\
+         <w:pPr><w:ind w:left=\"864\"/></w:pPr></w:style>\
+         <w:style w:type=\"paragraph\" w:styleId=\"SourceCode\"><w:name w:val=\"Source Code\"/></w:style>\
+         <w:style w:type=\"paragraph\" w:styleId=\"ListBullet\"><w:name w:val=\"List Bullet\"/>\
+         <w:pPr><w:numPr><w:numId w:val=\"2\"/></w:numPr></w:pPr></w:style>\
+         <w:style w:type=\"character\" w:styleId=\"Strong\"><w:name w:val=\"Strong\"/>\
+         <w:rPr><w:b/></w:rPr></w:style>\
+         </w:styles>";
+    let numbering = "<w:numbering xmlns:w=\"http://schemas.openxmlformats.org/wordprocessingml/2006/main\">\
+         <w:abstractNum w:abstractNumId=\"7\">\
+         <w:lvl w:ilvl=\"0\"><w:numFmt w:val=\"decimal\"/><w:lvlText w:val=\"%1.\"/></w:lvl>\
+         <w:lvl w:ilvl=\"1\"><w:numFmt w:val=\"bullet\"/><w:lvlText w:val=\"☐\"/></w:lvl>\
+         </w:abstractNum>\
+         <w:abstractNum w:abstractNumId=\"8\">\
+         <w:lvl w:ilvl=\"0\"><w:numFmt w:val=\"bullet\"/><w:lvlText w:val=\"•\"/></w:lvl>\
+         </w:abstractNum>\
+         <w:num w:numId=\"1\"><w:abstractNumId w:val=\"7\"/></w:num>\
+         <w:num w:numId=\"2\"><w:abstractNumId w:val=\"8\"/></w:num>\
+         <w:num w:numId=\"3\"><w:abstractNumId w:val=\"8\"/><w:lvlOverride w:ilvl=\"0\">\
+         <w:lvl w:ilvl=\"0\"><w:numFmt w:val=\"upperRoman\"/></w:lvl></w:lvlOverride></w:num>\
+         </w:numbering>";
+    let body = "<w:p><w:pPr><w:pStyle w:val=\"Chapter\"/></w:pPr>\
+         <w:r><w:rPr><w:sz w:val=\"32\"/></w:rPr><w:t>Chapter</w:t></w:r></w:p>\
+         <w:p><w:pPr><w:pStyle w:val=\"IntenseQuote\"/></w:pPr><w:r><w:t>quote</w:t></w:r></w:p>\
+         <w:p><w:pPr><w:pStyle w:val=\"SourceCode\"/></w:pPr>\
+         <w:r><w:rPr><w:b/></w:rPr><w:t xml:space=\"preserve\">  let a;</w:t></w:r></w:p>\
+         <w:p><w:pPr><w:numPr><w:ilvl w:val=\"0\"/><w:numId w:val=\"1\"/></w:numPr></w:pPr>\
+         <w:r><w:t>one</w:t></w:r></w:p>\
+         <w:p><w:pPr><w:numPr><w:ilvl w:val=\"1\"/><w:numId w:val=\"1\"/></w:numPr></w:pPr>\
+         <w:r><w:t>todo</w:t></w:r></w:p>\
+         <w:p><w:pPr><w:pStyle w:val=\"ListBullet\"/></w:pPr><w:r><w:t>bullet</w:t></w:r></w:p>\
+         <w:p><w:pPr><w:numPr><w:numId w:val=\"3\"/></w:numPr></w:pPr><w:r><w:t>I</w:t></w:r></w:p>\
+         <w:p><w:pPr><w:pStyle w:val=\"ListBullet\"/><w:numPr><w:numId w:val=\"0\"/></w:numPr>\
+         <w:ind w:left=\"1440\"/></w:pPr>\
+         <w:r><w:rPr><w:rStyle w:val=\"Strong\"/></w:rPr><w:t>strong</w:t></w:r>\
+         <w:r><w:rPr><w:rStyle w:val=\"Strong\"/><w:b w:val=\"0\"/><w:sz w:val=\"48\"/></w:rPr>\
+         <w:t>huge</w:t></w:r>\
+         <w:r><w:rPr><w:sz w:val=\"18\"/><w:color w:val=\"auto\"/><w:highlight w:val=\"cyan\"/>\
+         <w:rFonts w:ascii=\"Consolas\" w:hAnsi=\"Consolas\"/></w:rPr><w:t>small</w:t></w:r></w:p>";
+    let docx = docx(
+        body,
+        &[
+            ("rId1", "styles", "styles.xml"),
+            ("rId2", "numbering", "/word/numbering.xml"),
+        ],
+        &[
+            ("word/styles.xml", styles.as_bytes()),
+            ("word/numbering.xml", numbering.as_bytes()),
+        ],
+    );
+    let imported = Delta::from_docx(&docx).unwrap();
+    assert_eq!(
+        imported.delta,
+        delta![
+            "Chapter",
+            "\n" { header: 3 },
+            "quote",
+            "\n" { blockquote: true },
+            "  let a;",
+            "\n" { "code-block": true },
+            "one",
+            "\n" { list: "ordered" },
+            "todo",
+            "\n" { list: "unchecked", indent: 1 },
+            "bullet",
+            "\n" { list: "bullet" },
+            "I",
+            "\n" { list: "ordered" },
+            "strong" { bold: true },
+            "huge" { size: "huge" },
+            "small" { size: "small", background: "#00ffff", code: true },
+            "\n" { indent: 2 },
+        ]
+    );
+    assert_eq!(imported.warnings, []);
+}
+
+#[test]
+fn outline_levels_past_headers_are_text() {
+    let paragraph = |level: &str| {
+        format!(
+            "<w:p><w:pPr><w:outlineLvl w:val=\"{level}\"/></w:pPr><w:r><w:t>{level}</w:t></w:r></w:p>"
+        )
+    };
+    let body: String = ["0", "5", "6", "9", "255"].map(paragraph).concat();
+    let imported = Delta::from_docx(&docx(&body, &[], &[])).unwrap();
+    assert_eq!(
+        imported.delta,
+        delta!["0", "\n" { header: 1 }, "5", "\n" { header: 6 }, "6\n9\n255\n"]
+    );
+}
+
+#[test]
+fn unsupported_constructs_become_warnings() {
+    let body = "<w:tbl><w:tblPr/><w:tr><w:tc><w:p><w:r><w:t>cell</w:t></w:r></w:p></w:tc>\
+         <w:tc><w:p><w:r><w:t>other</w:t></w:r></w:p></w:tc></w:tr></w:tbl>\
+         <w:p><w:hyperlink w:anchor=\"top\"><w:r><w:t>back</w:t></w:r></w:hyperlink>\
+         <w:r><w:t>note</w:t></w:r><w:r><w:footnoteReference w:id=\"1\"/></w:r>\
+         <w:r><w:br/><w:t>next</w:t></w:r>\
+         <w:ins><w:r><w:t xml:space=\"preserve\"> added</w:t></w:r></w:ins>\
+         <w:del><w:r><w:delText>removed</w:delText></w:r></w:del>\
+         <m:oMath><m:r><m:t>x</m:t></m:r></m:oMath></w:p>\
+         <w:tbl><w:tr><w:tc><w:p/></w:tc></w:tr></w:tbl>\
+         <w:sectPr/>";
+    let imported = Delta::from_docx(&docx(body, &[], &[])).unwrap();
+    assert_eq!(
+        imported.delta,
+        delta!["cell\nother\nbacknote\nnext added\n\n"]
+    );
+    assert_eq!(
+        imported.warnings,
+        [
+            DocxWarning::Unsupported("table".into()),
+            DocxWarning::Unsupported("internal link".into()),
+            DocxWarning::Unsupported("footnote".into()),
+            DocxWarning::Unsupported("equation".into()),
+        ]
+    );
+    assert_eq!(imported.warnings[0].to_string(), "unsupported table");
+}
+
+#[test]
+fn images_go_through_the_asset_callback() {
+    let png = png_bytes();
+    let body = format!(
+        "<w:p><w:hyperlink r:id=\"rId1\"><w:r><w:t>link</w:t></w:r></w:hyperlink>{}{}{}</w:p>",
+        drawing("rId2", "dot"),
+        drawing("rId3", ""),
+        drawing("rId4", "remote"),
+    );
+    let docx = docx(
+        &body,
+        &[
+            ("rId1", "hyperlink", "https://quilljs.com"),
+            ("rId2", "image", "media/image1.png"),
+            ("rId3", "image", "media/image2.emf"),
+            ("rId4", "image", "https://cdn.example/remote.png"),
+        ],
+        &[
+            ("word/media/image1.png", &png),
+            ("word/media/image2.emf", b"emf"),
+        ],
+    );
+
+    let imported = Delta::from_docx(&docx).unwrap();
+    assert_eq!(
+        imported.delta,
+        delta![
+            "link" { link: "https://quilljs.com" },
+            insert(Embed::new("image", PNG)) { alt: "dot", width: "20" },
+            { image: "https://cdn.example/remote.png" } { alt: "remote", width: "20" },
+            "\n",
+        ]
+    );
+    assert_eq!(
+        imported.warnings,
+        [DocxWarning::Image("word/media/image2.emf".into())]
+    );
+
+    let assets = Rc::new(RefCell::new(vec![]));
+    let seen = assets.clone();
+    let import = DocxImport::builder()
+        .assets(move |asset| {
+            seen.borrow_mut().push((
+                asset.path.to_string(),
+                asset.content_type.to_string(),
+                asset.bytes.len(),
+            ));
+            let name = asset.path.rsplit('/').next().unwrap();
+            Some(format!("https://uploads.example/{name}"))
+        })
+        .build();
+    let imported = Delta::from_docx_with(&docx, &import).unwrap();
+    assert_eq!(
+        assets.borrow().as_slice(),
+        [
+            (
+                "word/media/image1.png".into(),
+                "image/png".into(),
+                png.len()
+            ),
+            ("word/media/image2.emf".into(), "image/x-emf".into(), 3),
+        ]
+    );
+    assert_eq!(
+        imported.delta.ops()[2]
+            .as_insert()
+            .unwrap()
+            .as_embed()
+            .unwrap()
+            .value,
+        "https://uploads.example/image2.emf"
+    );
+    assert_eq!(imported.warnings, []);
+}
+
+#[test]
+fn schema_filters_formats() {
+    let doc = delta![
+        "a" { bold: true, italic: true },
+        insert(Embed::new("image", PNG)),
+        "\n" { header: 1 },
+    ];
+    let import = DocxImport::builder()
+        .schema(Schema::builder().inlines(&[Inline::Bold]).build())
+        .build();
+    let imported = Delta::from_docx_with(&doc.to_docx().unwrap(), &import).unwrap();
+    assert_eq!(imported.delta, delta!["a" { bold: true }, "\n"]);
+}
+
+#[test]
+fn invalid_packages_are_errors() {
+    assert!(matches!(
+        Delta::from_docx(b"not a zip"),
+        Err(DocxImportError::Zip(_))
+    ));
+
+    let mut zip = ZipWriter::new(Cursor::new(vec![]));
+    zip.start_file("readme.txt", SimpleFileOptions::default())
+        .unwrap();
+    let empty = zip.finish().unwrap().into_inner();
+    assert_eq!(
+        Delta::from_docx(&empty),
+        Err(DocxImportError::MissingPart("word/document.xml".into()))
+    );
+
+    let mut zip = ZipWriter::new(Cursor::new(vec![]));
+    zip.start_file("word/document.xml", SimpleFileOptions::default())
+        .unwrap();
+    zip.write_all(b"<w:document>").unwrap();
+    let malformed = zip.finish().unwrap().into_inner();
+    let error = Delta::from_docx(&malformed).unwrap_err();
+    assert!(matches!(&error, DocxImportError::Xml { part, .. } if part == "word/document.xml"));
+    assert!(error.to_string().starts_with("malformed word/document.xml"));
+}
+
+#[test]
+fn reads_stop_at_the_size_limit() {
+    let import = DocxImport::builder().max_size(64 << 10).build();
+    let text = " ".repeat(1 << 20);
+    let bomb = docx(
+        &format!("<w:p><w:r><w:t>{text}</w:t></w:r></w:p>"),
+        &[],
+        &[],
+    );
+    let error = Delta::from_docx_with(&bomb, &import).unwrap_err();
+    assert_eq!(error, DocxImportError::TooLarge("word/document.xml".into()));
+    assert_eq!(
+        error.to_string(),
+        "word/document.xml is over the size limit"
+    );
+
+    let image = vec![0; 1 << 20];
+    let docx = docx(
+        &format!("<w:p>{}</w:p>", drawing("rId1", "big")),
+        &[("rId1", "image", "media/big.png")],
+        &[("word/media/big.png", &image)],
+    );
+    let imported = Delta::from_docx_with(&docx, &import).unwrap();
+    assert_eq!(imported.delta, delta!["\n"]);
+    assert_eq!(
+        imported.warnings,
+        [DocxWarning::Image("word/media/big.png".into())]
+    );
+    assert!(Delta::from_docx(&docx).is_ok());
+}
@@ -0,0 +1,196 @@
+use quill_wr::{Attributor, Font, QuillOptions, SanitizePolicy, Size, UrlPolicy, delta};
+
+const UI: &str = "<span class=\"ql-ui\" contenteditable=\"false\"></span>";
+
+#[test]
+fn empty_document_is_one_empty_paragraph() {
+    assert_eq!(delta![].to_editor_html(), "<p><br></p>");
+    assert_eq!(delta!["\n"].to_editor_html(), "<p><br></p>");
+}
+
+#[test]
+fn block_classes() {
+    let doc = delta![
+        "Title",
+        "\n" { header: 1, align: "center" },
+        "quote",
+        "\n" { blockquote: true, indent: 2 },
+        "rtl",
+        "\n" { direction: "rtl", align: "right" },
+    ];
+    assert_eq!(
+        doc.to_editor_html(),
+        "<h1 class=\"ql-align-center\">Title</h1>\
+         <blockquote class=\"ql-indent-2\">quote</blockquote>\
+         <p class=\"ql-align-right ql-direction-rtl\">rtl</p>"
+    );
+}
+
+#[test]
+fn lists_share_one_flat_ol() {
+    let doc = delta![
+        "one",
+        "\n" { list: "ordered" },
+        "a",
+        "\n" { list: "bullet", indent: 1 },
+        "\n" { list: "checked" },
+        "after",
+        "\n",
+    ];
+    assert_eq!(
+        doc.to_editor_html(),
+        format!(
+            "<ol><li data-list=\"ordered\">{UI}one</li>\
+             <li data-list=\"bullet\" class=\"ql-indent-1\">{UI}a</li>\
+             <li data-list=\"checked\">{UI}<br></li></ol><p>after</p>"
+        )
+    );
+}
+
+#[test]
+fn code_blocks_share_a_container() {
+    let doc = delta![
+        "let a = 1 < 2;",
+        "\n" { "code-block": "rust" },
+        "\n" { "code-block": "rust" },
+        "x",
+        "\n" { "code-block": true },
+        "after",
+        "\n",
+    ];
+    assert_eq!(
+        doc.to_editor_html(),
+        "<div class=\"ql-code-block-container\" spellcheck=\"false\">\
+         <div class=\"ql-code-block\" data-language=\"rust\">let a = 1 &lt; 2;</div>\
+         <div class=\"ql-code-block\" data-language=\"rust\"><br></div>\
+         <div class=\"ql-code-block\">x</div></div><p>after</p>"
+    );
+}
+
+#[test]
+fn inline_formats_follow_quill_order() {
+    let doc = delta![
+        "x" { bold: true, italic: true, underline: true, strike: true, code: true },
+        "y" { link: "https://quilljs.com", bold: true, color: "red", size: "large" },
+        "\n",
+    ];
+    assert_eq!(
+        doc.to_editor_html(),
+        "<p><code><strong><em><s><u>x</u></s></em></strong></code>\
+         <strong><a href=\"https://quilljs.com\" rel=\"noopener noreferrer\" target=\"_blank\">\
+         <span class=\"ql-size-large\" style=\"color: red;\">y</span></a></strong></p>"
+    );
+}
+
+#[test]
+fn style_attributors_follow_the_options() {
+    let options = QuillOptions::builder()
+        .sizes(Attributor::Style, vec![Size::Px(18.0)])
+        .fonts(Attributor::Class, vec![Font::Serif])
+        .build();
+    let doc = delta!["x" { size: "18px", font: "serif" }, "\n"];
+    assert_eq!(
+        doc.to_editor_html_with(&options),
+        "<p><span class=\"ql-font-serif\" style=\"font-size: 18px;\">x</span></p>"
+    );
+}
+
+#[test]
+fn invalid_attributor_values_are_dropped() {
+    let doc = delta![
+        "x" {
+            color: "red; position:fixed; inset:0",
+            background: "rgb(0, 0, 0); x: url(y)",
+            font: "x ql-hidden",
+            size: "\"huge",
+        },
+        "y" { color: "rgb(0, 0, 255)", font: "monospace", size: "Big" },
+        "\n",
+    ];
+    assert_eq!(
+        doc.to_editor_html(),
+        "<p>x<span class=\"ql-font-monospace\" style=\"color: rgb(0, 0, 255);\">y</span></p>"
+    );
+
+    let options = QuillOptions::builder()
+        .sizes(Attributor::Style, vec![Size::Custom("1.5em".into())])
+        .fonts(
+            Attributor::Style,
+            vec![Font::Custom("Roboto, sans-serif".into())],
+        )
+        .build();
+    let doc = delta![
+        "x" { size: "1.5em", font: "Roboto, sans-serif" },
+        "y" { size: "2.5em", font: "x; color: red" },
+        "\n",
+    ];
+    assert_eq!(
+        doc.to_editor_html_with(&options),
+        "<p><span style=\"font-family: Roboto, sans-serif; font-size: 1.5em;\">x</span>y</p>"
+    );
+}
+
+#[test]
+fn values_outside_the_whitelist_are_dropped() {
+    // Quill's own whitelists when none is declared
+    let doc = delta![
+        "x" { size: "gigantic", font: "roboto" },
+        "y" { size: "huge", font: "serif" },
+        "\n",
+    ];
+    assert_eq!(
+        doc.to_editor_html(),
+        "<p>x<span class=\"ql-font-serif ql-size-huge\">y</span></p>"
+    );
+
+    let options = QuillOptions::builder()
+        .sizes(Attributor::Style, vec![Size::Px(12.0), Size::Px(50.0)])
+        .fonts(Attributor::Class, vec![Font::Custom("roboto".into())])
+        .build();
+    let doc = delta![
+        "x" { size: "18px", font: "serif" },
+        "y" { size: "50px", font: "roboto" },
+        "\n",
+    ];
+    assert_eq!(
+        doc.to_editor_html_with(&options),
+        "<p>x<span class=\"ql-font-roboto\" style=\"font-size: 50px;\">y</span></p>"
+    );
+}
+
+#[test]
+fn embeds() {
+    let doc = delta![
+        { video: "https://youtube.com/embed/x" },
+        "see ",
+        { image: "a.png" } { width: "20", alt: "a" },
+        { formula: "e=mc^2" },
+        "\n",
+    ];
+    assert_eq!(
+        doc.to_editor_html(),
+        "<iframe class=\"ql-video\" frameborder=\"0\" allowfullscreen=\"true\" \
+         src=\"https://youtube.com/embed/x\"></iframe>\
+         <p>see <img src=\"a.png\" alt=\"a\" width=\"20\">\
+         <span class=\"ql-formula\" data-value=\"e=mc^2\">\u{feff}\
+         <span contenteditable=\"false\">e=mc^2</span>\u{feff}</span></p>"
+    );
+}
+
+#[test]
+fn urls_follow_the_options_policy() {
+    let doc = delta!["x" { link: "https://evil.com" }, { image: "javascript:x" }, "\n"];
+    assert!(doc.to_editor_html().contains("href=\"https://evil.com\""));
+    assert!(doc.to_editor_html().contains("src=\"//:0\""));
+
+    let options = QuillOptions::builder()
+        .sanitize(SanitizePolicy {
+            link: UrlPolicy::builder().domains(&["quilljs.com"]).build(),
+            ..Default::default()
+        })
+        .build();
+    assert!(
+        doc.to_editor_html_with(&options)
+            .contains("href=\"about:blank\"")
+    );
+}
@@ -0,0 +1,111 @@
+use quill_wr::{
+    CustomEmbed, Delta, Embed, EmbedAttr, Embeds, Insert, Op, Schema, ValidationErrorKind, delta,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Mention {
+    id: u32,
+    name: String,
+}
+impl CustomEmbed for Mention {
+    const NAME: &'static str = "mention";
+}
+
+/// quill stores valueless embeds as `true`
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Divider(bool);
+impl CustomEmbed for Divider {
+    const NAME: &'static str = "divider";
+}
+
+fn embeds(delta: &Delta) -> Vec<Embeds> {
+    delta
+        .ops()
+        .iter()
+        .filter_map(|op| match op {
+            Op::Insert {
+                insert: Insert::Embed(embed),
+                ..
+            } => Some(Embeds::from(embed.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn custom_embeds_round_trip() {
+    let mention = Mention {
+        id: 7,
+        name: "Ada".into(),
+    };
+    let mut doc = Delta::new();
+    doc.insert_embed(mention.to_embed(), &[EmbedAttr::Alt("@Ada".into())])
+        .insert_embed(Divider(true).to_embed(), &[])
+        .insert_embed(Embeds::Image("a.png".into()), &[])
+        .insert_text("\n", &[]);
+    assert_eq!(
+        doc,
+        delta![
+            { mention: { "id": 7, "name": "Ada" } } { alt: "@Ada" },
+            { divider: true },
+            { image: "a.png" },
+            "\n",
+        ]
+    );
+
+    let parsed = Delta::from_json(&doc.to_json().unwrap()).unwrap();
+    let embeds = embeds(&parsed);
+    assert_eq!(Mention::from_embed(&embeds[0]), Some(mention));
+    assert_eq!(Divider::from_embed(&embeds[1]), Some(Divider(true)));
+    assert_eq!(Mention::from_embed(&embeds[1]), None);
+    assert_eq!(embeds[2], Embeds::Image("a.png".into()));
+}
+
+#[test]
+fn unknown_embeds_are_kept() {
+    let doc = Delta::from_json(
+        r#"{"ops":[{"insert":{"tweet":{"id":"20","theme":"dark"}}},{"insert":{"image":{"src":"a.png"}}},{"insert":"\n"}]}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        embeds(&doc),
+        vec![
+            Embeds::Custom("tweet".into(), json!({"id": "20", "theme": "dark"})),
+            Embeds::Custom("image".into(), json!({"src": "a.png"})),
+        ]
+    );
+    let tweet = Embed::from(embeds(&doc).remove(0));
+    assert_eq!(
+        tweet,
+        Embed::new("tweet", json!({"id": "20", "theme": "dark"}))
+    );
+    assert_eq!(tweet.to_custom::<Mention>(), None);
+}
+
+#[test]
+fn schema_checks_custom_embed_values() {
+    let schema = Schema::builder()
+        .custom_embed::<Mention>()
+        .embeds(&[Embeds::Custom("divider".into(), json!(true))])
+        .build();
+    let valid = delta![{ mention: { "id": 1, "name": "x" } }, { divider: true }, "\n"];
+    assert_eq!(valid.validate(&schema), Ok(()));
+
+    let invalid = delta![{ mention: { "id": "1" } }, { divider: "yes" }, { tweet: 1 }, "\n"];
+    let kinds: Vec<_> = invalid
+        .validate(&schema)
+        .unwrap_err()
+        .into_iter()
+        .map(|error| error.kind)
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            ValidationErrorKind::InvalidEmbedValue("mention".into(), json!({"id": "1"})),
+            ValidationErrorKind::InvalidEmbedValue("divider".into(), json!("yes")),
+            ValidationErrorKind::UnknownEmbed("tweet".into()),
+        ]
+    );
+}
@@ -0,0 +1,178 @@
+use quill_wr::{SanitizePolicy, UrlPolicy, delta};
+
+#[test]
+fn headers_and_paragraphs() {
+    let doc = delta!["Title", "\n" { header: 2 }, "body", "\n", "\n"];
+    assert_eq!(
+        doc.to_semantic_html(),
+        "<h2>Title</h2><p>body</p><p><br></p>"
+    );
+}
+
+#[test]
+fn lists_nest_by_indent() {
+    let doc = delta![
+        "one",
+        "\n" { list: "ordered" },
+        "a",
+        "\n" { list: "bullet", indent: 1 },
+        "b",
+        "\n" { list: "bullet", indent: 1 },
+        "two",
+        "\n" { list: "ordered" },
+        "after",
+        "\n",
+    ];
+    assert_eq!(
+        doc.to_semantic_html(),
+        "<ol><li>one<ul><li>a</li><li>b</li></ul></li><li>two</li></ol><p>after</p>"
+    );
+}
+
+#[test]
+fn list_kind_change_starts_a_new_list() {
+    let doc = delta![
+        "done",
+        "\n" { list: "checked" },
+        "todo",
+        "\n" { list: "unchecked" },
+        "first",
+        "\n" { list: "ordered" },
+    ];
+    assert_eq!(
+        doc.to_semantic_html(),
+        "<ul><li data-list=\"checked\">done</li><li data-list=\"unchecked\">todo</li></ul>\
+         <ol><li>first</li></ol>"
+    );
+}
+
+#[test]
+fn code_lines_share_one_pre() {
+    let doc = delta![
+        "fn main() {",
+        "\n" { "code-block": "rust" },
+        "    1 < 2",
+        "\n" { "code-block": "rust" },
+        "}",
+        "\n" { "code-block": "rust" },
+        "plain",
+        "\n" { "code-block": "plain" },
+    ];
+    assert_eq!(
+        doc.to_semantic_html(),
+        "<pre><code class=\"language-rust\">fn main() {\n    1 &lt; 2\n}</code></pre>\
+         <pre><code>plain</code></pre>"
+    );
+}
+
+#[test]
+fn block_styles() {
+    let doc = delta![
+        "quote",
+        "\n" { blockquote: true },
+        "centered",
+        "\n" { align: "center", indent: 2 },
+        "rtl",
+        "\n" { direction: "rtl", align: "right" },
+    ];
+    assert_eq!(
+        doc.to_semantic_html(),
+        "<blockquote>quote</blockquote>\
+         <p style=\"text-align: center; padding-left: 6em\">centered</p>\
+         <p style=\"text-align: right\" dir=\"rtl\">rtl</p>"
+    );
+}
+
+#[test]
+fn inline_formats_nest_in_a_fixed_order() {
+    let doc = delta![
+        "x" { code: true, bold: true, italic: true, underline: true, strike: true },
+        "2" { script: "super" },
+        "link" { link: "https://quilljs.com", bold: true },
+        "\n",
+    ];
+    assert_eq!(
+        doc.to_semantic_html(),
+        "<p><u><s><em><strong><code>x</code></strong></em></s></u><sup>2</sup>\
+         <a href=\"https://quilljs.com\" rel=\"noopener noreferrer\" target=\"_blank\">\
+         <strong>link</strong></a></p>"
+    );
+}
+
+#[test]
+fn styles_become_a_span() {
+    let doc = delta![
+        "red" { color: "red", background: "#00ff00", size: "large", font: "serif" },
+        "\n",
+    ];
+    assert_eq!(
+        doc.to_semantic_html(),
+        "<p><span style=\"background-color: #00ff00; color: #ff0000; \
+         font-family: Georgia, Times New Roman, serif; font-size: 1.5em\">red</span></p>"
+    );
+}
+
+#[test]
+fn custom_styles_cannot_inject_css() {
+    let doc = delta![
+        "x" { font: "'Roboto', sans-serif; position: fixed", size: "1em;{}" },
+        "\n",
+    ];
+    assert_eq!(
+        doc.to_semantic_html(),
+        "<p><span style=\"font-family: Roboto, sans-serif position: fixed; \
+         font-size: 1em\">x</span></p>"
+    );
+}
+
+#[test]
+fn embeds() {
+    let doc = delta![
+        { video: "https://youtube.com/embed/x" } { width: "300" },
+        "see ",
+        { image: "a.png" } { alt: "an \"a\"" },
+        { formula: "e=mc^2" },
+        { mention: { "id": 1 } },
+        "\n",
+    ];
+    assert_eq!(
+        doc.to_semantic_html(),
+        "<iframe src=\"https://youtube.com/embed/x\" frameborder=\"0\" allowfullscreen=\"true\" \
+         width=\"300\"></iframe>\
+         <p>see <img src=\"a.png\" alt=\"an &quot;a&quot;\">\
+         <span class=\"ql-formula\" data-value=\"e=mc^2\">e=mc^2</span></p>"
+    );
+}
+
+#[test]
+fn text_is_escaped() {
+    let doc = delta!["<script>alert(\"&\")</script>", "\n"];
+    assert_eq!(
+        doc.to_semantic_html(),
+        "<p>&lt;script&gt;alert(&quot;&amp;&quot;)&lt;/script&gt;</p>"
+    );
+}
+
+#[test]
+fn urls_are_sanitized() {
+    let doc = delta![
+        "x" { link: "javascript:alert(1)" },
+        { image: "javascript:alert(1)" },
+        "\n",
+    ];
+    assert_eq!(
+        doc.to_semantic_html(),
+        "<p><a href=\"about:blank\" rel=\"noopener noreferrer\" target=\"_blank\">x</a>\
+         <img src=\"//:0\"></p>"
+    );
+
+    let policy = SanitizePolicy {
+        link: UrlPolicy::builder().domains(&["quilljs.com"]).build(),
+        ..Default::default()
+    };
+    let doc = delta!["x" { link: "https://evil.com" }, "\n"];
+    assert!(
+        doc.to_semantic_html_with(&policy)
+            .contains("href=\"about:blank\"")
+    );
+}
@@ -0,0 +1,219 @@
+use quill_wr::{Block, Delta, HtmlImport, HtmlImportError, Inline, Schema, TagHandling, delta};
+
+#[test]
+fn blocks_become_lines() {
+    let doc = Delta::from_html(
+        "<h2>Title</h2>\n<p>one</p>\n<blockquote>quote</blockquote><div>two</div><p></p>",
+    );
+    assert_eq!(
+        doc,
+        delta![
+            "Title",
+            "\n" { header: 2 },
+            "one\n",
+            "quote",
+            "\n" { blockquote: true },
+            "two\n\n",
+        ]
+    );
+}
+
+#[test]
+fn whitespace_collapses_outside_pre() {
+    let doc = Delta::from_html("<p>  a \n  b  <b>c</b> <i>d</i>\n</p><pre>  x\n    y</pre>");
+    assert_eq!(
+        doc,
+        delta![
+            "a b ",
+            "c" { bold: true },
+            " ",
+            "d" { italic: true },
+            "\n",
+            "  x",
+            "\n" { "code-block": true },
+            "    y",
+            "\n" { "code-block": true },
+        ]
+    );
+}
+
+#[test]
+fn inline_tags_and_styles() {
+    let doc = Delta::from_html(
+        "<p><strong><em>a</em></strong><u>b</u><s>c</s><sub>d</sub><sup>e</sup><code>f</code>\
+         <a href=\"https://quilljs.com\">g</a>\
+         <span style=\"font-weight: 700; font-style: italic; text-decoration: underline; \
+         color: rgb(255, 0, 0); background-color: yellow\">h</span></p>",
+    );
+    assert_eq!(
+        doc,
+        delta![
+            "a" { bold: true, italic: true },
+            "b" { underline: true },
+            "c" { strike: true },
+            "d" { script: "sub" },
+            "e" { script: "super" },
+            "f" { code: true },
+            "g" { link: "https://quilljs.com" },
+            "h" {
+                bold: true,
+                italic: true,
+                underline: true,
+                color: "#ff0000",
+                background: "yellow",
+            },
+            "\n",
+        ]
+    );
+}
+
+#[test]
+fn nested_lists_get_an_indent() {
+    let doc = Delta::from_html(
+        "<ol><li>one<ul><li>a</li><li>b</li></ul></li><li>two</li></ol>\
+         <ul data-checked=\"true\"><li>done</li></ul>",
+    );
+    assert_eq!(
+        doc,
+        delta![
+            "one",
+            "\n" { list: "ordered" },
+            "a",
+            "\n" { list: "bullet", indent: 1 },
+            "b",
+            "\n" { list: "bullet", indent: 1 },
+            "two",
+            "\n" { list: "ordered" },
+            "done",
+            "\n" { list: "checked" },
+        ]
+    );
+}
+
+#[test]
+fn quill_markup_round_trips() {
+    let doc = delta![
+        "Title",
+        "\n" { header: 1, align: "center" },
+        "big" { size: "large" },
+        " ",
+        "serif" { font: "serif" },
+        "\n" { direction: "rtl", align: "right" },
+        "one",
+        "\n" { list: "ordered" },
+        "a",
+        "\n" { list: "unchecked", indent: 2 },
+        "let a = 1;",
+        "\n" { "code-block": "rust" },
+        "  indented",
+        "\n" { "code-block": "rust" },
+        { video: "https://youtube.com/embed/x" },
+        "see ",
+        { image: "a.png" } { alt: "a", width: "20" },
+        { formula: "e=mc^2" },
+        "\n",
+    ];
+    assert_eq!(Delta::from_html(&doc.to_editor_html()), doc);
+}
+
+#[test]
+fn semantic_html_round_trips() {
+    let doc = delta![
+        "Title",
+        "\n" { header: 3 },
+        "a" { bold: true, link: "https://quilljs.com" },
+        "\n" { blockquote: true },
+        "fn main() {}",
+        "\n" { "code-block": "rust" },
+    ];
+    assert_eq!(Delta::from_html(&doc.to_semantic_html()), doc);
+}
+
+#[test]
+fn tables_become_text_lines() {
+    let doc = Delta::from_html(
+        "<table><thead><tr><th>name</th><th>age</th></tr></thead>\
+         <tbody><tr><td>Ada</td><td>36</td></tr></tbody></table>",
+    );
+    assert_eq!(doc, delta!["name\nage\nAda\n36\n"]);
+}
+
+#[test]
+fn images_and_breaks() {
+    let doc = Delta::from_html(
+        "<p>a<br>b<img src=\"cat.png\" alt=\"cat\" width=\"10\" onerror=\"x\"></p>\
+         <p><img src=\"javascript:alert(1)\"></p>",
+    );
+    assert_eq!(
+        doc,
+        delta![
+            "a\nb",
+            { image: "cat.png" } { alt: "cat", width: "10" },
+            "\n",
+            { image: "//:0" },
+            "\n",
+        ]
+    );
+}
+
+#[test]
+fn ignored_elements_and_unknown_tags() {
+    let html = "<html><head><title>t</title><style>p { color: red }</style></head>\
+                <body><script>alert(1)</script><p>a <mark>b</mark></p><!-- c --></body></html>";
+    assert_eq!(Delta::from_html(html), delta!["a b\n"]);
+
+    let import = HtmlImport::builder()
+        .unknown_tags(TagHandling::Drop)
+        .build();
+    assert_eq!(Delta::from_html_with(html, &import), Ok(delta!["a \n"]));
+
+    let import = HtmlImport::builder()
+        .unknown_tags(TagHandling::Reject)
+        .build();
+    assert_eq!(
+        Delta::from_html_with(html, &import),
+        Err(HtmlImportError::UnknownTag("mark".into()))
+    );
+}
+
+#[test]
+fn tag_overrides() {
+    let html =
+        "<p><a href=\"https://quilljs.com\"><b>a</b></a></p><table><tr><td>x</td></tr></table>";
+    let import = HtmlImport::builder()
+        .tag("a", TagHandling::Unwrap)
+        .tag("TABLE", TagHandling::Drop)
+        .build();
+    assert_eq!(
+        Delta::from_html_with(html, &import),
+        Ok(delta!["a" { bold: true }, "\n"])
+    );
+}
+
+#[test]
+fn schema_filters_formats() {
+    let import = HtmlImport::builder()
+        .schema(
+            Schema::builder()
+                .inlines(&[Inline::Bold])
+                .blocks(&[Block::header(1).unwrap()])
+                .build(),
+        )
+        .build();
+    let doc = Delta::from_html_with(
+        "<h7>x</h7><h2><b>a</b><i>b</i></h2><blockquote>c</blockquote><img src=\"a.png\">",
+        &import,
+    );
+    assert_eq!(
+        doc,
+        Ok(delta!["x\n", "a" { bold: true }, "b", "\n" { header: 2 }, "c\n"])
+    );
+}
+
+#[test]
+fn deeply_nested_html() {
+    // past what the call stack would take, divs would make the parser itself quadratic
+    let depth = 100_000;
+    let html = format!("{}x{}", "<span>".repeat(depth), "</span>".repeat(depth));
+    assert_eq!(Delta::from_html(&html), delta!["x\n"]);
+}
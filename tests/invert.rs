@@ -0,0 +1,71 @@
+//! fixtures from quill-delta's `test/delta/invert.js`, plus an undo property test
+mod common;
+use common::*;
+use proptest::prelude::*;
+use serde_json::{Value, json};
+
+fn assert_invert(change: Value, base: Value, expected: Value) {
+    let (change, base) = (delta(change), delta(base));
+    let inverted = change.invert(&base);
+    assert_eq!(inverted, delta(expected));
+    assert_eq!(base.compose(&change).compose(&inverted), base);
+}
+
+#[test]
+fn insert() {
+    assert_invert(
+        json!([{"retain": 2}, {"insert": "A"}]),
+        json!([{"insert": "123456"}]),
+        json!([{"retain": 2}, {"delete": 1}]),
+    );
+}
+
+#[test]
+fn delete() {
+    assert_invert(
+        json!([{"retain": 2}, {"delete": 3}]),
+        json!([{"insert": "123"}, {"insert": "4", "attributes": {"bold": true}}, {"insert": "56"}]),
+        json!([{"retain": 2}, {"insert": "3"}, {"insert": "4", "attributes": {"bold": true}}, {"insert": "5"}]),
+    );
+}
+
+#[test]
+fn retain() {
+    assert_invert(
+        json!([{"retain": 2}, {"retain": 3, "attributes": {"bold": true}}]),
+        json!([{"insert": "123456"}]),
+        json!([{"retain": 2}, {"retain": 3, "attributes": {"bold": null}}]),
+    );
+}
+
+#[test]
+fn retain_on_a_delta_with_different_attributes() {
+    assert_invert(
+        json!([{"retain": 4, "attributes": {"italic": true}}]),
+        json!([{"insert": "123"}, {"insert": {"image": "a.png"}, "attributes": {"bold": true}}]),
+        json!([{"retain": 4, "attributes": {"italic": null}}]),
+    );
+}
+
+#[test]
+fn retain_restores_previous_values() {
+    assert_invert(
+        json!([{"retain": 3, "attributes": {"color": "red", "bold": null}}]),
+        json!([
+            {"insert": "12", "attributes": {"color": "blue", "bold": true}},
+            {"insert": "3\n"}
+        ]),
+        json!([
+            {"retain": 2, "attributes": {"color": "blue", "bold": true}},
+            {"retain": 1, "attributes": {"bold": null, "color": null}}
+        ]),
+    );
+}
+
+proptest! {
+    #[test]
+    fn invert_undoes_change((doc, change, _) in arb_concurrent_changes()) {
+        let inverted = change.invert(&doc);
+        prop_assert_eq!(doc.compose(&change).compose(&inverted), doc);
+    }
+}
@@ -0,0 +1,100 @@
+//! fixtures from quill-delta's `eachLine` tests
+mod common;
+use common::delta;
+use quill_wr::AttributeMap;
+use serde_json::{Value, json};
+
+fn collect_lines(doc: Value) -> Vec<(Value, Value, usize)> {
+    delta(doc)
+        .lines()
+        .map(|line| {
+            (
+                serde_json::to_value(line.content.ops()).unwrap(),
+                serde_json::to_value(&line.attributes).unwrap(),
+                line.index.get(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn expected() {
+    let lines = collect_lines(json!([
+        {"insert": "Hello\n\n"},
+        {"insert": "World", "attributes": {"bold": true}},
+        {"insert": {"image": "octocat.png"}},
+        {"insert": "\n", "attributes": {"align": "right"}},
+        {"insert": "!"}
+    ]));
+    assert_eq!(
+        lines,
+        vec![
+            (json!([{"insert": "Hello"}]), json!({}), 0),
+            (json!([]), json!({}), 6),
+            (
+                json!([
+                    {"insert": "World", "attributes": {"bold": true}},
+                    {"insert": {"image": "octocat.png"}}
+                ]),
+                json!({"align": "right"}),
+                7
+            ),
+            (json!([{"insert": "!"}]), json!({}), 14),
+        ]
+    );
+}
+
+#[test]
+fn trailing_newline() {
+    let lines = collect_lines(json!([{"insert": "Hello\nWorld!\n"}]));
+    assert_eq!(
+        lines,
+        vec![
+            (json!([{"insert": "Hello"}]), json!({}), 0),
+            (json!([{"insert": "World!"}]), json!({}), 6),
+        ]
+    );
+}
+
+#[test]
+fn non_document() {
+    let lines = collect_lines(json!([{"retain": 1}, {"delete": 2}]));
+    assert!(lines.is_empty());
+}
+
+#[test]
+fn early_return() {
+    let doc = delta(json!([{"insert": "Hello\nNew\nWorld!"}]));
+    let mut count = 0;
+    doc.each_line(|_, _: &AttributeMap, i| {
+        count += 1;
+        i < 1
+    });
+    assert_eq!(count, 2);
+}
+
+#[test]
+fn block_attributes_and_utf16_indexes() {
+    let doc = delta(json!([
+        {"insert": "😀 Title"},
+        {"insert": "\n", "attributes": {"header": 1}},
+        {"insert": "item"},
+        {"insert": "\n", "attributes": {"list": "bullet"}}
+    ]));
+    let lines: Vec<_> = doc.lines().collect();
+    assert_eq!(lines[0].text(), "😀 Title");
+    assert_eq!(lines[0].attributes.get("header"), Some(&json!(1)));
+    assert_eq!(lines[1].index, 9);
+    assert_eq!(lines[1].attributes.get("list"), Some(&json!("bullet")));
+}
+
+#[test]
+fn lengths_count_the_newline_only_when_there_is_one() {
+    let doc = delta(json!([{"insert": "Hello\nWorld!"}]));
+    let lines: Vec<_> = doc.lines().collect();
+    assert_eq!(lines[0].length(), 6);
+    assert!(lines[0].newline);
+    assert_eq!(lines[1].length(), 6);
+    assert!(!lines[1].newline);
+    assert_eq!(lines[1].index + lines[1].length(), doc.length());
+}
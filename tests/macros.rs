@@ -0,0 +1,66 @@
+mod common;
+
+use common::delta;
+use quill_wr::{Delta, delta};
+use serde_json::json;
+
+#[test]
+fn text_and_formats() {
+    let color = "#ccc";
+    let name = String::from("Grey");
+    assert_eq!(
+        delta![
+            "Gandalf" { bold: true },
+            " the ",
+            insert(name.as_str()) { color: color, size: 1.5 },
+            "\n" { header: 1, "code-block": "rust", },
+        ],
+        delta(json!([
+            {"insert": "Gandalf", "attributes": {"bold": true}},
+            {"insert": " the "},
+            {"insert": "Grey", "attributes": {"color": "#ccc", "size": 1.5}},
+            {"insert": "\n", "attributes": {"header": 1, "code-block": "rust"}},
+        ]))
+    );
+}
+
+#[test]
+fn embeds() {
+    assert_eq!(
+        delta![
+            { image: "a.png" } { width: "120", alt: "a, b" },
+            { mention: { "id": 7, "tags": [1, 2] } },
+            "\n",
+        ],
+        delta(json!([
+            {"insert": {"image": "a.png"}, "attributes": {"width": "120", "alt": "a, b"}},
+            {"insert": {"mention": {"id": 7, "tags": [1, 2]}}},
+            {"insert": "\n"},
+        ]))
+    );
+}
+
+#[test]
+fn changes_merge_like_push() {
+    let change = delta![
+        retain(2),
+        retain(3) { bold: null, indent: -1 + 2 },
+        delete(1),
+        delete(2),
+        "x",
+        "y",
+        retain(4),
+    ];
+    assert_eq!(
+        change,
+        delta(json!([
+            {"retain": 2},
+            {"retain": 3, "attributes": {"bold": null, "indent": 1}},
+            {"insert": "xy"},
+            {"delete": 3},
+            {"retain": 4},
+        ]))
+    );
+    assert_eq!(delta![], Delta::new());
+    assert_eq!(delta!["", delete(0)], Delta::new());
+}
@@ -0,0 +1,160 @@
+use quill_wr::{MarkdownExport, UnsupportedFormats, delta};
+
+fn html_export() -> MarkdownExport {
+    MarkdownExport::builder()
+        .unsupported_formats(UnsupportedFormats::Html)
+        .build()
+}
+
+#[test]
+fn headings_and_paragraphs() {
+    let doc = delta![
+        "Title",
+        "\n" { header: 1 },
+        "\n",
+        "one\ntwo",
+        "\n",
+        "Sub",
+        "\n" { header: 3 },
+    ];
+    assert_eq!(doc.to_markdown(), "# Title\n\none\n\ntwo\n\n### Sub\n");
+    assert_eq!(delta![].to_markdown(), "");
+}
+
+#[test]
+fn emphasis_hugs_the_text() {
+    let doc = delta![
+        "a",
+        " b " { bold: true },
+        "c" { bold: true, italic: true },
+        " d" { strike: true },
+        " ",
+        "e" { code: true },
+        "\n",
+    ];
+    assert_eq!(doc.to_markdown(), "a **b *c*** ~~d~~ `e`\n");
+}
+
+#[test]
+fn links_and_images() {
+    let doc = delta![
+        "see ",
+        "the docs" { link: "https://quilljs.com", bold: true },
+        " ",
+        { image: "a cat.png" } { alt: "cat" },
+        " ",
+        { formula: "e=mc^2" },
+        "\n",
+    ];
+    assert_eq!(
+        doc.to_markdown(),
+        "see [**the docs**](https://quilljs.com) ![cat](<a cat.png>) $e=mc^2$\n"
+    );
+}
+
+#[test]
+fn nested_lists() {
+    let doc = delta![
+        "one",
+        "\n" { list: "ordered" },
+        "a",
+        "\n" { list: "bullet", indent: 1 },
+        "b",
+        "\n" { list: "ordered", indent: 1 },
+        "c",
+        "\n" { list: "ordered", indent: 1 },
+        "two",
+        "\n" { list: "ordered" },
+        "done",
+        "\n" { list: "checked" },
+        "todo",
+        "\n" { list: "unchecked", indent: 3 },
+        "after\n",
+        "again",
+        "\n" { list: "ordered" },
+    ];
+    assert_eq!(
+        doc.to_markdown(),
+        "1. one\n   - a\n   1. b\n   2. c\n2. two\n- [x] done\n  - [ ] todo\n\nafter\n\n1. again\n"
+    );
+}
+
+#[test]
+fn code_blocks_are_fenced() {
+    let doc = delta![
+        "fn main() {",
+        "\n" { "code-block": "rust" },
+        "    let a = \"```\";",
+        "\n" { "code-block": "rust" },
+        "}",
+        "\n" { "code-block": "rust" },
+        "*x*",
+        "\n" { "code-block": true },
+    ];
+    assert_eq!(
+        doc.to_markdown(),
+        "````rust\nfn main() {\n    let a = \"```\";\n}\n````\n\n```\n*x*\n```\n"
+    );
+    assert_eq!(
+        delta!["a `b`" { code: true }, "\n"].to_markdown(),
+        "`` a `b` ``\n"
+    );
+}
+
+#[test]
+fn blockquotes() {
+    let doc = delta![
+        "one",
+        "\n" { blockquote: true },
+        "two" { italic: true },
+        "\n" { blockquote: true },
+        "after\n",
+    ];
+    assert_eq!(doc.to_markdown(), "> one\n>\n> *two*\n\nafter\n");
+}
+
+#[test]
+fn markdown_syntax_is_escaped() {
+    let doc = delta!["# not *a* [heading]\n1. no list\n- nor this &amp; <b>\n"];
+    assert_eq!(
+        doc.to_markdown(),
+        "\\# not \\*a\\* \\[heading\\]\n\n1\\. no list\n\n\\- nor this \\&amp; \\<b\\>\n"
+    );
+}
+
+#[test]
+fn unsupported_formats_are_dropped_by_default() {
+    let doc = delta![
+        "u" { underline: true },
+        "c" { color: "#ff0000", size: "large" },
+        { image: "a.png" } { width: "20" },
+        "\n" { align: "center" },
+        { video: "https://youtube.com/embed/x" },
+        "\n",
+    ];
+    assert_eq!(
+        doc.to_markdown(),
+        "uc![](a.png)\n\n[video](https://youtube.com/embed/x)\n"
+    );
+}
+
+#[test]
+fn unsupported_formats_as_html() {
+    let doc = delta![
+        "u" { underline: true, bold: true },
+        "c" { color: "#ff0000", size: "large" },
+        "2" { script: "super" },
+        { image: "a.png" } { width: "20", alt: "a" },
+        "\n" { align: "center" },
+        { video: "https://youtube.com/embed/x" },
+        "\n",
+    ];
+    assert_eq!(
+        doc.to_markdown_with(&html_export()),
+        "<div align=\"center\">\n\n\
+         <u>**u**</u><span style=\"color: #ff0000; font-size: 1.5em\">c</span><sup>2</sup>\
+         <img src=\"a.png\" alt=\"a\" width=\"20\">\n\n</div>\n\n\
+         <iframe src=\"https://youtube.com/embed/x\" frameborder=\"0\" allowfullscreen=\"true\">\
+         </iframe>\n"
+    );
+}
@@ -0,0 +1,147 @@
+use quill_wr::{Delta, Inline, MarkdownImport, Schema, delta};
+
+#[test]
+fn headings_and_paragraphs() {
+    let doc = Delta::from_markdown("# Title\n\none\ntwo  \nthree\n\nSub\n---\n");
+    assert_eq!(
+        doc,
+        delta![
+            "Title",
+            "\n" { header: 1 },
+            "one two\nthree\n",
+            "Sub",
+            "\n" { header: 2 },
+        ]
+    );
+    assert_eq!(Delta::from_markdown(""), delta!["\n"]);
+}
+
+#[test]
+fn inline_formats() {
+    let doc = Delta::from_markdown(
+        "*a* **b** ***c*** ~~d~~ `e` [f](https://quilljs.com) <u>g</u>x<sup>2</sup> $e=mc^2$",
+    );
+    assert_eq!(
+        doc,
+        delta![
+            "a" { italic: true },
+            " ",
+            "b" { bold: true },
+            " ",
+            "c" { bold: true, italic: true },
+            " ",
+            "d" { strike: true },
+            " ",
+            "e" { code: true },
+            " ",
+            "f" { link: "https://quilljs.com" },
+            " ",
+            "g" { underline: true },
+            "x",
+            "2" { script: "super" },
+            " ",
+            { formula: "e=mc^2" },
+            "\n",
+        ]
+    );
+}
+
+#[test]
+fn nested_and_task_lists() {
+    let doc =
+        Delta::from_markdown("1. one\n   - a\n   - [ ] b\n2. two\n\n   more\n\n- [x] done\n-\n");
+    assert_eq!(
+        doc,
+        delta![
+            "one",
+            "\n" { list: "ordered" },
+            "a",
+            "\n" { list: "bullet", indent: 1 },
+            "b",
+            "\n" { list: "unchecked", indent: 1 },
+            "two",
+            "\n" { list: "ordered" },
+            "more",
+            "\n" { indent: 1 },
+            "done",
+            "\n" { list: "checked" },
+            "\n" { list: "bullet" },
+        ]
+    );
+}
+
+#[test]
+fn code_blocks_and_quotes() {
+    let doc = Delta::from_markdown(
+        "```rust ignore\nfn main() {\n\n}\n```\n\n    plain\n\n> quoted *text*\n> # title\n",
+    );
+    assert_eq!(
+        doc,
+        delta![
+            "fn main() {",
+            "\n\n" { "code-block": "rust" },
+            "}",
+            "\n" { "code-block": "rust" },
+            "plain",
+            "\n" { "code-block": true },
+            "quoted ",
+            "text" { italic: true },
+            "\n" { blockquote: true },
+            "title",
+            "\n" { header: 1 },
+        ]
+    );
+}
+
+#[test]
+fn images_and_html_blocks() {
+    let doc = Delta::from_markdown(
+        "![a *cat*](cat.png) ![](javascript:alert(1))\n\n<p>x <b>y</b></p>\n\n<div align=\"center\">\n\nz\n\n</div>\n",
+    );
+    assert_eq!(
+        doc,
+        delta![
+            { image: "cat.png" } { alt: "a cat" },
+            " ",
+            { image: "//:0" },
+            "\nx ",
+            "y" { bold: true },
+            "\nz\n",
+        ]
+    );
+}
+
+#[test]
+fn schema_filters_formats() {
+    let import = MarkdownImport::builder()
+        .schema(Schema::builder().inlines(&[Inline::Bold]).build())
+        .build();
+    let doc = Delta::from_markdown_with("# **a** *b* ![c](c.png)\n\n- d\n", &import);
+    assert_eq!(doc, delta!["a" { bold: true }, " b \nd\n"]);
+}
+
+#[test]
+fn markdown_export_round_trips() {
+    let doc = delta![
+        "Title",
+        "\n" { header: 2 },
+        "a ",
+        "b" { bold: true, link: "https://quilljs.com" },
+        " *not italic* ",
+        "c" { code: true },
+        "\n",
+        "one",
+        "\n" { list: "ordered" },
+        "two",
+        "\n" { list: "checked", indent: 1 },
+        "quote",
+        "\n" { blockquote: true },
+        "let a = 1;",
+        "\n" { "code-block": "rust" },
+        "",
+        { image: "a.png" } { alt: "a" },
+        { formula: "x^2" },
+        "\n",
+    ];
+    assert_eq!(Delta::from_markdown(&doc.to_markdown()), doc);
+}
@@ -0,0 +1,53 @@
+use quill_wr::Delta;
+
+fn normalized_json(json: &str) -> String {
+    Delta::from_json(json)
+        .unwrap()
+        .normalized()
+        .to_json()
+        .unwrap()
+}
+
+#[test]
+fn merges_and_drops_noise() {
+    let json = r#"{"ops":[
+        {"insert":"Hel","attributes":{}},
+        {"insert":"lo"},
+        {"insert":""},
+        {"insert":" World","attributes":{"color":"red","bold":true}},
+        {"retain":0},
+        {"delete":1},
+        {"delete":2},
+        {"retain":3},
+        {"retain":2,"attributes":{}}
+    ]}"#;
+    assert_eq!(
+        normalized_json(json),
+        r#"{"ops":[{"insert":"Hello"},{"insert":" World","attributes":{"bold":true,"color":"red"}},{"delete":3}]}"#
+    );
+}
+
+#[test]
+fn keeps_formatting_retains() {
+    let json = r#"{"ops":[{"retain":2},{"retain":3,"attributes":{"bold":null}},{"retain":4}]}"#;
+    assert_eq!(
+        normalized_json(json),
+        r#"{"ops":[{"retain":2},{"retain":3,"attributes":{"bold":null}}]}"#
+    );
+}
+
+#[test]
+fn equal_documents_serialize_identically() {
+    let a = r#"{"ops":[{"insert":"a","attributes":{"italic":true,"bold":true}},{"insert":{"video":{"url":"v.mp4","autoplay":false}}},{"insert":"\n"}]}"#;
+    let b = r#"{"ops":[{"insert":"a","attributes":{"bold":true,"italic":true}},{"insert":{"video":{"autoplay":false,"url":"v.mp4"}},"attributes":{}},{"insert":""},{"insert":"\n","attributes":{}}]}"#;
+    assert_eq!(normalized_json(a), normalized_json(b));
+}
+
+#[test]
+fn inserts_go_before_deletes() {
+    let json = r#"{"ops":[{"retain":1},{"delete":2},{"insert":"x"}]}"#;
+    assert_eq!(
+        normalized_json(json),
+        r#"{"ops":[{"retain":1},{"insert":"x"},{"delete":2}]}"#
+    );
+}
@@ -0,0 +1,327 @@
+mod common;
+
+use std::io::Cursor;
+
+use common::{PNG, part, parts, png_bytes};
+use quill_wr::{Delta, Embed, OdtExport, delta};
+use roxmltree::Document;
+use zip::{CompressionMethod, ZipArchive};
+
+/// the `<office:text>` of the document
+fn text(doc: &Delta) -> String {
+    let content = part(&doc.to_odt().unwrap(), "content.xml");
+    let start = content.find("<office:text>").unwrap() + "<office:text>".len();
+    let end = content.find("</office:text>").unwrap();
+    content[start..end].to_string()
+}
+
+#[test]
+fn package_follows_odf() {
+    let doc = delta![
+        "a <b> & \u{1}c\u{fffe}\u{ffff}" { link: "https://quilljs.com/?a=1&b=2" },
+        insert(Embed::new("image", PNG)) { alt: "\"dot\"" },
+        "\n" { list: "ordered" },
+    ];
+    let odt = doc.to_odt().unwrap();
+    // the mimetype comes first and uncompressed, so the format can be sniffed
+    let mut archive = ZipArchive::new(Cursor::new(&odt)).unwrap();
+    let mimetype = archive.by_index(0).unwrap();
+    assert_eq!(mimetype.name(), "mimetype");
+    assert_eq!(mimetype.compression(), CompressionMethod::Stored);
+    assert!(mimetype.extra_data().is_none_or(|extra| extra.is_empty()));
+    assert_eq!(
+        part(&odt, "mimetype"),
+        "application/vnd.oasis.opendocument.text"
+    );
+
+    // the manifest lists every part but itself and the mimetype
+    let manifest = part(&odt, "META-INF/manifest.xml");
+    let manifest = Document::parse(&manifest).unwrap();
+    let entries: Vec<_> = manifest
+        .root_element()
+        .children()
+        .filter(|node| node.has_tag_name("file-entry"))
+        .map(|node| {
+            let attribute =
+                |name| node.attribute(("urn:oasis:names:tc:opendocument:xmlns:manifest:1.0", name));
+            (
+                attribute("full-path").unwrap(),
+                attribute("media-type").unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        entries,
+        [
+            ("/", "application/vnd.oasis.opendocument.text"),
+            ("content.xml", "text/xml"),
+            ("styles.xml", "text/xml"),
+            ("Pictures/image1.png", "image/png"),
+        ]
+    );
+    let names: Vec<_> = parts(&odt).into_iter().map(|(name, _)| name).collect();
+    for (path, _) in &entries[1..] {
+        assert!(names.contains(&path.to_string()), "{path}");
+    }
+    assert_eq!(names.len(), entries.len() + 1);
+
+    // content and styles are versioned office documents, automatic styles before the body
+    for (name, root, children) in [
+        (
+            "content.xml",
+            "document-content",
+            ["automatic-styles", "body"],
+        ),
+        (
+            "styles.xml",
+            "document-styles",
+            ["styles", "automatic-styles"],
+        ),
+    ] {
+        let xml = part(&odt, name);
+        let document = Document::parse(&xml).unwrap_or_else(|error| panic!("{name}: {error}"));
+        let element = document.root_element();
+        assert_eq!(element.tag_name().name(), root);
+        assert_eq!(
+            element.attribute((
+                "urn:oasis:names:tc:opendocument:xmlns:office:1.0",
+                "version"
+            )),
+            Some("1.2")
+        );
+        let found: Vec<_> = element
+            .children()
+            .filter(|node| node.is_element())
+            .map(|node| node.tag_name().name())
+            .filter(|name| children.contains(name))
+            .collect();
+        assert_eq!(found, children, "{name}");
+    }
+    assert_eq!(text(&delta![]), "<text:p text:style-name=\"Standard\"/>");
+}
+
+#[test]
+fn paragraph_styles() {
+    let doc = delta![
+        "Title",
+        "\n" { header: 2, align: "center" },
+        "quote",
+        "\n" { blockquote: true },
+        "code",
+        "\n" { "code-block": "rust" },
+        "rtl",
+        "\n" { direction: "rtl", align: "justify", indent: 2 },
+        "centered",
+        "\n" { align: "center" },
+    ];
+    let odt = doc.to_odt().unwrap();
+    let content = part(&odt, "content.xml");
+    assert!(content.contains(
+        "<style:style style:name=\"P1\" style:family=\"paragraph\" \
+         style:parent-style-name=\"Heading_20_2\">\
+         <style:paragraph-properties fo:text-align=\"center\"/></style:style>\
+         <style:style style:name=\"P2\" style:family=\"paragraph\" \
+         style:parent-style-name=\"Standard\"><style:paragraph-properties \
+         fo:margin-left=\"1in\" fo:text-align=\"justify\" style:writing-mode=\"rl-tb\"/>\
+         </style:style>"
+    ));
+    assert_eq!(
+        text(&doc),
+        "<text:h text:style-name=\"P1\" text:outline-level=\"2\">Title</text:h>\
+         <text:p text:style-name=\"Quotations\">quote</text:p>\
+         <text:p text:style-name=\"Preformatted_20_Text\">code</text:p>\
+         <text:p text:style-name=\"P2\">rtl</text:p>\
+         <text:p text:style-name=\"P3\">centered</text:p>"
+    );
+    let styles = part(&odt, "styles.xml");
+    assert!(styles.contains(
+        "<style:style style:name=\"Heading_20_2\" style:display-name=\"Heading 2\" \
+         style:family=\"paragraph\" style:parent-style-name=\"Heading\" \
+         style:next-style-name=\"Standard\" style:default-outline-level=\"2\""
+    ));
+    assert!(styles.contains("<style:style style:name=\"Quotations\""));
+    assert!(styles.contains("<style:master-page style:name=\"Standard\""));
+}
+
+#[test]
+fn inline_formats_share_automatic_styles() {
+    let doc = delta![
+        "x" {
+            bold: true,
+            italic: true,
+            underline: true,
+            strike: true,
+            color: "#ff0000",
+            size: "18px",
+            font: "serif",
+        },
+        " ",
+        "y" { bold: true },
+        "2" { script: "sub", code: true },
+        "z" { bold: true },
+        "\n",
+    ];
+    let odt = doc.to_odt().unwrap();
+    let content = part(&odt, "content.xml");
+    assert!(content.contains(
+        "<style:style style:name=\"T1\" style:family=\"text\"><style:text-properties \
+         fo:font-family=\"'Georgia'\" fo:font-weight=\"bold\" style:font-weight-complex=\"bold\" \
+         fo:font-style=\"italic\" style:font-style-complex=\"italic\" \
+         style:text-underline-style=\"solid\" style:text-underline-width=\"auto\" \
+         style:text-underline-color=\"font-color\" style:text-line-through-style=\"solid\" \
+         style:text-line-through-type=\"single\" fo:color=\"#ff0000\" fo:font-size=\"13.5pt\"/>\
+         </style:style>"
+    ));
+    assert!(content.contains(
+        "<style:text-properties fo:font-family=\"'Courier New'\" \
+         style:text-position=\"sub 58%\"/>"
+    ));
+    assert_eq!(content.matches("style:family=\"text\"").count(), 3);
+    assert_eq!(
+        text(&doc),
+        "<text:p text:style-name=\"Standard\"><text:span text:style-name=\"T1\">x</text:span> \
+         <text:span text:style-name=\"T2\">y</text:span>\
+         <text:span text:style-name=\"T3\">2</text:span>\
+         <text:span text:style-name=\"T2\">z</text:span></text:p>"
+    );
+}
+
+#[test]
+fn font_sizes_stay_within_bounds() {
+    let doc = delta![
+        "a" { size: "infpx" },
+        "b" { size: "1e6px" },
+        "c" { size: "0.1px" },
+        "\n",
+    ];
+    let content = part(&doc.to_odt().unwrap(), "content.xml");
+    assert!(content.contains("<style:text-properties fo:font-size=\"1638pt\"/>"));
+    assert!(content.contains("<style:text-properties fo:font-size=\"1pt\"/>"));
+    assert_eq!(content.matches("style:family=\"text\"").count(), 2);
+}
+
+#[test]
+fn spaces_and_tabs_are_kept() {
+    let doc = delta!["  a  b\t c ", "  d" { bold: true }, "\n"];
+    assert_eq!(
+        text(&doc),
+        "<text:p text:style-name=\"Standard\"><text:s text:c=\"2\"/>a <text:s/>b<text:tab/>\
+         <text:s/>c <text:span text:style-name=\"T1\"><text:s text:c=\"2\"/>d</text:span>\
+         </text:p>"
+    );
+}
+
+#[test]
+fn nested_lists_continue_their_numbering() {
+    let doc = delta![
+        "one",
+        "\n" { list: "ordered" },
+        "a",
+        "\n" { list: "ordered", indent: 1 },
+        "bullet",
+        "\n" { list: "bullet" },
+        "two",
+        "\n" { list: "ordered" },
+        "todo",
+        "\n" { list: "unchecked", indent: 2 },
+        "break\n",
+        "one",
+        "\n" { list: "checked" },
+    ];
+    assert_eq!(
+        text(&doc),
+        "<text:list text:style-name=\"Numbering\" xml:id=\"list1\"><text:list-item>\
+         <text:p text:style-name=\"Standard\">one</text:p>\
+         <text:list text:style-name=\"Numbering\"><text:list-item>\
+         <text:p text:style-name=\"Standard\">a</text:p>\
+         </text:list-item></text:list></text:list-item></text:list>\
+         <text:list text:style-name=\"Bullet\"><text:list-item>\
+         <text:p text:style-name=\"Standard\">bullet</text:p></text:list-item></text:list>\
+         <text:list text:style-name=\"Numbering\" xml:id=\"list2\" text:continue-list=\"list1\">\
+         <text:list-item><text:p text:style-name=\"Standard\">two</text:p>\
+         <text:list text:style-name=\"Unchecked\"><text:list-item>\
+         <text:list text:style-name=\"Unchecked\"><text:list-item>\
+         <text:p text:style-name=\"Standard\">todo</text:p>\
+         </text:list-item></text:list></text:list-item></text:list>\
+         </text:list-item></text:list>\
+         <text:p text:style-name=\"Standard\">break</text:p>\
+         <text:list text:style-name=\"Checked\"><text:list-item>\
+         <text:p text:style-name=\"Standard\">one</text:p></text:list-item></text:list>"
+    );
+    let styles = part(&doc.to_odt().unwrap(), "styles.xml");
+    assert!(styles.contains(
+        "<text:list-level-style-number text:level=\"2\" style:num-suffix=\".\" \
+         style:num-format=\"a\">"
+    ));
+    assert!(
+        styles.contains("<text:list-level-style-bullet text:level=\"1\" text:bullet-char=\"☐\">")
+    );
+}
+
+#[test]
+fn links_share_one_hyperlink() {
+    let doc = delta![
+        "a" { link: "https://quilljs.com/?a&b" },
+        "b" { link: "https://quilljs.com/?a&b", bold: true },
+        " ",
+        "c" { link: "https://quilljs.com/?a&b" },
+        "\n",
+        { video: "https://youtube.com/embed/x" },
+        "\n",
+    ];
+    let link = "<text:a xlink:type=\"simple\" xlink:href=\"https://quilljs.com/?a&amp;b\" \
+                text:style-name=\"Internet_20_link\" \
+                text:visited-style-name=\"Visited_20_Internet_20_Link\">";
+    assert_eq!(
+        text(&doc),
+        format!(
+            "<text:p text:style-name=\"Standard\">{link}a\
+             <text:span text:style-name=\"T1\">b</text:span></text:a> {link}c</text:a></text:p>\
+             <text:p text:style-name=\"Standard\"><text:a xlink:type=\"simple\" \
+             xlink:href=\"https://youtube.com/embed/x\" text:style-name=\"Internet_20_link\" \
+             text:visited-style-name=\"Visited_20_Internet_20_Link\">\
+             https://youtube.com/embed/x</text:a></text:p>"
+        )
+    );
+}
+
+#[test]
+fn images_are_embedded_from_bytes() {
+    let png = png_bytes();
+    let doc = delta![
+        { image: "https://cdn.example/dot.png" } { width: "48", alt: "dot" },
+        { image: "https://cdn.example/dot.png" },
+        { image: "https://cdn.example/missing.png" } { alt: "missing" },
+        "\n",
+    ];
+    let export = OdtExport::builder()
+        .images(move |src| (src == "https://cdn.example/dot.png").then(|| png.clone()))
+        .build();
+    let odt = doc.to_odt_with(&export).unwrap();
+    let content = part(&odt, "content.xml");
+    // 48px wide keeps the 2:3 ratio, 96 pixels per inch
+    assert!(content.contains(
+        "<draw:frame draw:name=\"Image1\" text:anchor-type=\"as-char\" svg:width=\"0.5in\" \
+         svg:height=\"0.75in\" draw:z-index=\"0\"><draw:image \
+         xlink:href=\"Pictures/image1.png\" xlink:type=\"simple\" xlink:show=\"embed\" \
+         xlink:actuate=\"onLoad\"/><svg:desc>dot</svg:desc></draw:frame>"
+    ));
+    assert_eq!(
+        content
+            .matches("xlink:href=\"Pictures/image1.png\"")
+            .count(),
+        2
+    );
+    assert!(content.contains("</draw:frame>missing</text:p>"));
+    let names: Vec<_> = parts(&odt).into_iter().map(|(name, _)| name).collect();
+    assert_eq!(names.last().unwrap(), "Pictures/image1.png");
+}
+
+#[test]
+fn unreadable_image_sizes_are_ignored() {
+    let doc = delta![
+        insert(Embed::new("image", PNG)) { width: "NaN", height: "inf" },
+        "\n",
+    ];
+    assert!(text(&doc).contains("svg:width=\"0.0208in\" svg:height=\"0.0313in\""));
+}
@@ -0,0 +1,112 @@
+use quill_wr::{PlainTextExport, delta};
+
+#[test]
+fn lines_keep_their_text() {
+    assert_eq!(delta![].to_plain_text(), "");
+    let doc = delta![
+        "Title",
+        "\n" { header: 1 },
+        "\n",
+        "a " { bold: true },
+        "b",
+        "\n" { indent: 2 },
+        "quote",
+        "\n" { blockquote: true },
+        "  code",
+        "\n" { "code-block": "rust", indent: 1 },
+    ];
+    assert_eq!(doc.to_plain_text(), "Title\n\n      a b\n> quote\n  code\n");
+}
+
+#[test]
+fn ordered_lists_follow_quill_numbering() {
+    let doc = delta![
+        "one",
+        "\n" { list: "ordered" },
+        "a",
+        "\n" { list: "ordered", indent: 1 },
+        "i",
+        "\n" { list: "ordered", indent: 2 },
+        "ii",
+        "\n" { list: "ordered", indent: 2 },
+        "b",
+        "\n" { list: "ordered", indent: 1 },
+        "bullet",
+        "\n" { list: "bullet" },
+        "two",
+        "\n" { list: "ordered" },
+        "a again",
+        "\n" { list: "ordered", indent: 1 },
+        "break\n",
+        "one again",
+        "\n" { list: "ordered" },
+    ];
+    assert_eq!(
+        doc.to_plain_text(),
+        "1. one\n   a. a\n      i. i\n      ii. ii\n   b. b\n• bullet\n2. two\n   a. a again\n\
+         break\n1. one again\n"
+    );
+}
+
+#[test]
+fn long_lists_keep_counting() {
+    let mut doc = delta![];
+    for _ in 0..28 {
+        doc = doc.concat(&delta!["\n" { list: "ordered", indent: 1 }]);
+    }
+    let text = doc.to_plain_text();
+    let markers: Vec<_> = text.lines().map(str::trim).collect();
+    assert_eq!(markers[25..], ["z.", "aa.", "ab."]);
+}
+
+#[test]
+fn checkboxes_and_bullets() {
+    let doc = delta![
+        "done",
+        "\n" { list: "checked" },
+        "todo",
+        "\n" { list: "unchecked", indent: 1 },
+        "item",
+        "\n" { list: "bullet" },
+    ];
+    assert_eq!(doc.to_plain_text(), "☑ done\n   ☐ todo\n• item\n");
+
+    let export = PlainTextExport::builder()
+        .bullet("-")
+        .checkboxes("[x]", "[ ]")
+        .build();
+    assert_eq!(
+        doc.to_plain_text_with(&export),
+        "[x] done\n   [ ] todo\n- item\n"
+    );
+}
+
+#[test]
+fn embeds_become_placeholders() {
+    let doc = delta![
+        { video: "https://youtube.com/embed/x" },
+        "\n",
+        "see ",
+        { image: "cat.png" } { alt: "a cat" },
+        { image: "dog.png" },
+        " ",
+        { formula: "e=mc^2" },
+        { mention: { "name": "Ada" } },
+        "\n",
+    ];
+    assert_eq!(
+        doc.to_plain_text(),
+        "[video: https://youtube.com/embed/x]\nsee [image: a cat][image] e=mc^2[mention]\n"
+    );
+
+    let export = PlainTextExport::builder()
+        .embed("image", |_, _| String::new())
+        .embed("mention", |embed, _| {
+            format!("@{}", embed.value["name"].as_str().unwrap_or_default())
+        })
+        .build();
+    assert_eq!(
+        doc.to_plain_text_with(&export),
+        "[video: https://youtube.com/embed/x]\nsee  e=mc^2@Ada\n"
+    );
+}
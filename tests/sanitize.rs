@@ -0,0 +1,166 @@
+use quill_wr::{Delta, Embeds, Inline, SanitizePolicy, UrlPolicy, delta};
+use serde_json::json;
+
+#[test]
+fn default_policy_matches_quill() {
+    let policy = UrlPolicy::default();
+    for url in [
+        "https://quilljs.com",
+        "HTTP://example.com",
+        "mailto:a@b.c",
+        "tel:+123",
+        "sms:123",
+        "/relative/path",
+        "page.html?a=b:c",
+        "#anchor",
+        "//cdn.example.com/x",
+    ] {
+        assert!(policy.is_allowed(url), "{url}");
+    }
+    for url in [
+        "javascript:alert(1)",
+        "JaVaScRiPt:alert(1)",
+        "  javascript:alert(1)",
+        "java\tscript:alert(1)",
+        "java\nscript:alert(1)",
+        "\u{1}javascript:alert(1)",
+        "data:text/html;base64,PHNjcmlwdD4=",
+        "vbscript:msgbox",
+        "ftp://example.com",
+    ] {
+        assert!(!policy.is_allowed(url), "{url:?}");
+        assert_eq!(policy.sanitize(url), "about:blank");
+    }
+}
+
+#[test]
+fn image_policy_allows_data_urls() {
+    let policy = UrlPolicy::image();
+    assert!(policy.is_allowed("data:image/png;base64,AAAA"));
+    assert!(!policy.is_allowed("mailto:a@b.c"));
+    assert_eq!(policy.sanitize("javascript:alert(1)"), "//:0");
+}
+
+#[test]
+fn relative_and_domains() {
+    let policy = UrlPolicy::builder()
+        .protocols(&["https"])
+        .allow_relative(false)
+        .domains(&["Example.com"])
+        .replacement("#")
+        .build();
+    assert!(policy.is_allowed("https://example.com/a"));
+    assert!(policy.is_allowed("https://user:pw@docs.example.com:8443/a"));
+    assert!(policy.is_allowed("https://EXAMPLE.COM./a"));
+    assert!(!policy.is_allowed("https://example.com.evil.net"));
+    assert!(!policy.is_allowed("https://evil.net/?https://example.com"));
+    assert!(!policy.is_allowed("https://example.com@evil.net"));
+    assert!(!policy.is_allowed("http://example.com"));
+    assert!(!policy.is_allowed("/a"));
+    assert_eq!(policy.sanitize("//evil.net"), "#");
+
+    let relative = UrlPolicy::builder().domains(&["example.com"]).build();
+    assert!(relative.is_allowed("/a"));
+    assert!(relative.is_allowed("//example.com/a"));
+    assert!(!relative.is_allowed("//evil.net/a"));
+    assert!(relative.is_allowed("mailto:someone@evil.net"));
+    // browsers find the host after any mix of slashes, or none after a special scheme
+    for url in [
+        "/\\evil.com/x",
+        "\\/evil.com",
+        "https:/\\evil.com",
+        "https:evil.com",
+        "https:///evil.com",
+        "https://evil.com\\@example.com",
+    ] {
+        assert_eq!(relative.sanitize(url), "about:blank", "{url}");
+    }
+    assert!(relative.is_allowed("https:\\\\example.com/a"));
+    assert!(relative.is_allowed("\\a"));
+    assert!(!relative.is_allowed("https:///"));
+    assert!(!relative.is_allowed("file:///etc/passwd"));
+}
+
+#[test]
+fn sanitizes_deltas() {
+    let policy = SanitizePolicy::default();
+    let json = r#"{"ops":[
+        {"insert":"safe","attributes":{"link":"https://a.com","bold":true}},
+        {"insert":"xss","attributes":{"link":"javascript:alert(1)"}},
+        {"insert":{"image":"javascript:alert(1)"}},
+        {"insert":{"image":"data:image/png;base64,AAAA"}},
+        {"insert":{"video":"data:text/html,x"}},
+        {"insert":{"formula":"javascript:x"}},
+        {"insert":"\n"}
+    ]}"#;
+    assert_eq!(
+        Delta::from_json_sanitized(json, &policy).unwrap(),
+        delta![
+            "safe" { link: "https://a.com", bold: true },
+            "xss" { link: "about:blank" },
+            { image: "//:0" },
+            { image: "data:image/png;base64,AAAA" },
+            { video: "about:blank" },
+            { formula: "javascript:x" },
+            "\n",
+        ]
+    );
+    let change = delta![retain(3) { link: "javascript:x" }, retain(2) { link: null }];
+    assert_eq!(
+        change.sanitized(&policy),
+        delta![retain(3) { link: "about:blank" }, retain(2) { link: null }]
+    );
+}
+
+#[test]
+fn sanitizes_typed_values() {
+    let policy = SanitizePolicy::default();
+    assert_eq!(
+        policy.sanitize_inline(Inline::Link("javascript:x".into())),
+        Inline::Link("about:blank".into())
+    );
+    assert_eq!(policy.sanitize_inline(Inline::Bold), Inline::Bold);
+    assert_eq!(
+        policy.sanitize_embed(Embeds::Image("vbscript:x".into())),
+        Embeds::Image("//:0".into())
+    );
+}
+
+#[test]
+fn custom_values_are_sanitized_by_name() {
+    let policy = SanitizePolicy::default();
+    assert_eq!(
+        policy.sanitize_inline(Inline::Custom("link".into(), json!("javascript:alert(1)"))),
+        Inline::Link("about:blank".into())
+    );
+    assert_eq!(
+        policy.sanitize_inline(Inline::Custom(
+            "link".into(),
+            json!(["javascript:alert(1)"])
+        )),
+        Inline::Link("about:blank".into())
+    );
+    assert_eq!(
+        policy.sanitize_inline(Inline::Custom("mention".into(), json!("javascript:x"))),
+        Inline::Custom("mention".into(), json!("javascript:x"))
+    );
+    assert_eq!(
+        policy.sanitize_embed(Embeds::Custom(
+            "video".into(),
+            json!({"src": "javascript:x"})
+        )),
+        Embeds::Video("about:blank".into())
+    );
+    let doc = delta![{ image: ["javascript:x"] } { link: { "href": "javascript:x" } }, "\n"];
+    assert_eq!(
+        doc.sanitized(&policy),
+        delta![{ image: "//:0" } { link: "about:blank" }, "\n"]
+    );
+}
+
+#[test]
+fn deltas_that_cannot_be_read_are_rejected() {
+    // the editor only applies what it could sanitize, this one has to be refused
+    let json = r#"{"ops":[{"retain":{"table":1}},{"insert":"x","attributes":{"link":"javascript:alert(1)"}}]}"#;
+    assert!(Delta::from_json_sanitized(json, &SanitizePolicy::default()).is_err());
+}
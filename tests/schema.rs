@@ -0,0 +1,101 @@
+use quill_wr::{
+    Align, Block, Color, Delta, FormatKind, Inline, Schema, ValidationError,
+    ValidationErrorKind as Kind,
+};
+use serde_json::json;
+
+fn validate(json: &str, schema: &Schema) -> Result<(), Vec<ValidationError>> {
+    Delta::from_json(json).unwrap().validate(schema)
+}
+
+#[test]
+fn accepts_quill_document() {
+    let doc = r##"{"ops":[
+        {"insert":"Title"},{"insert":"\n","attributes":{"header":1}},
+        {"insert":"bold","attributes":{"bold":true,"color":"#ff0000"}},
+        {"insert":{"image":"a.png"},"attributes":{"width":"120"}},
+        {"insert":"\n\n","attributes":{"list":"bullet","indent":1}}
+    ]}"##;
+    assert_eq!(validate(doc, &Schema::default()), Ok(()));
+}
+
+#[test]
+fn reports_every_error_with_its_op() {
+    let doc = r##"{"ops":[
+        {"insert":"a","attributes":{"blink":true,"header":2}},
+        {"retain":1},
+        {"insert":{"tweet":"1"}},
+        {"insert":"b","attributes":{"bold":null,"link":3}},
+        {"insert":"\n","attributes":{"header":9}},
+        {"insert":"tail"}
+    ]}"##;
+    let errors = validate(doc, &Schema::default()).unwrap_err();
+    let errors: Vec<_> = errors.into_iter().map(|e| (e.op_index, e.kind)).collect();
+    assert_eq!(
+        errors,
+        vec![
+            (0, Kind::UnknownFormat("blink".into())),
+            (0, Kind::BlockFormatOnText("header".into())),
+            (1, Kind::NotAnInsert),
+            (2, Kind::UnknownEmbed("tweet".into())),
+            (3, Kind::NullFormat("bold".into())),
+            (3, Kind::InvalidFormatValue("link".into(), json!(3))),
+            (4, Kind::InvalidFormatValue("header".into(), json!(9))),
+            (6, Kind::MissingTrailingNewline),
+        ]
+    );
+}
+
+#[test]
+fn missing_newline_is_reported_past_the_last_op() {
+    let errors = validate(r#"{"ops":[]}"#, &Schema::default()).unwrap_err();
+    assert_eq!(errors[0].op_index, 0);
+    assert_eq!(errors[0].kind, Kind::MissingTrailingNewline);
+    let errors = validate(r#"{"ops":[{"insert":"a"}]}"#, &Schema::default()).unwrap_err();
+    assert_eq!(errors[0].op_index, 1);
+    assert_eq!(
+        errors[0].to_string(),
+        "op 1: a document must end with a newline"
+    );
+}
+
+#[test]
+fn restricted_schema() {
+    let schema = Schema::builder()
+        .inlines(&[Inline::Bold])
+        .blocks(&[Block::Align(Align::Center)])
+        .block("header", FormatKind::OneOf(vec![json!(1), json!(2)]))
+        .build();
+    let ok = r#"{"ops":[{"insert":"a","attributes":{"bold":true}},{"insert":"\n","attributes":{"align":"center","header":2}}]}"#;
+    assert_eq!(validate(ok, &schema), Ok(()));
+    let italic = r#"{"ops":[{"insert":"a","attributes":{"italic":true}},{"insert":"\n"}]}"#;
+    let errors = validate(italic, &schema).unwrap_err();
+    assert_eq!(errors[0].kind, Kind::UnknownFormat("italic".into()));
+    assert_eq!(errors[0].to_string(), "op 0: unknown format `italic`");
+}
+
+#[test]
+fn colors_must_parse_and_can_be_restricted_to_a_palette() {
+    let doc = |color: &str| {
+        format!(
+            r#"{{"ops":[{{"insert":"a","attributes":{{"color":"{color}"}}}},{{"insert":"\n"}}]}}"#
+        )
+    };
+    let schema = Schema::default();
+    assert_eq!(validate(&doc("rgb(230, 0, 0)"), &schema), Ok(()));
+    let errors = validate(&doc("url(x)"), &schema).unwrap_err();
+    assert_eq!(
+        errors[0].kind,
+        Kind::InvalidFormatValue("color".into(), json!("url(x)"))
+    );
+
+    let palette = Schema::builder()
+        .palette(&[Color::rgb(0, 0, 0), Color::rgb(230, 0, 0)])
+        .build();
+    assert_eq!(validate(&doc("#e60000"), &palette), Ok(()));
+    assert_eq!(validate(&doc("black"), &palette), Ok(()));
+    assert!(validate(&doc("#e60001"), &palette).is_err());
+    let background =
+        r##"{"ops":[{"insert":"a","attributes":{"background":"#0000ff"}},{"insert":"\n"}]}"##;
+    assert!(validate(background, &palette).is_err());
+}
@@ -0,0 +1,87 @@
+use quill_wr::{Delta, Embed, Insert, Op, Utf16Index, delta};
+use serde_json::{Value, json};
+
+fn round_trip(ops: Value) {
+    let delta: Delta = serde_json::from_value(json!({ "ops": ops })).unwrap();
+    assert_eq!(serde_json::to_value(&delta).unwrap(), json!({ "ops": ops }));
+}
+
+#[test]
+fn ops_round_trip_as_quill_json() {
+    round_trip(json!([
+        {"insert": "Hello "},
+        {"insert": "World", "attributes": {"bold": true, "color": "#e60000"}},
+        {"insert": {"image": "https://quilljs.com/logo.png"}, "attributes": {"width": "120"}},
+        {"insert": {"mention": {"id": 7, "name": "Ada"}}},
+        {"insert": "\n", "attributes": {"header": 1}},
+    ]));
+    round_trip(json!([
+        {"retain": 5},
+        {"retain": 3, "attributes": {"bold": null, "link": "https://quilljs.com"}},
+        {"delete": 2},
+        {"insert": "x"},
+    ]));
+    round_trip(json!([]));
+}
+
+#[test]
+fn ops_deserialize_into_the_model() {
+    let delta = Delta::from_json(
+        r#"{"ops":[{"insert":{"formula":"e=mc^2"}},{"retain":2,"attributes":{}},{"delete":1}]}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        delta.ops(),
+        [
+            Op::Insert {
+                insert: Insert::Embed(Embed::new("formula", "e=mc^2")),
+                attributes: None,
+            },
+            Op::Retain {
+                retain: 2.into(),
+                attributes: Some(Default::default()),
+            },
+            Op::Delete { delete: 1.into() },
+        ]
+    );
+    // empty attribute maps built by the model are not written
+    assert_eq!(
+        serde_json::to_value(Op::insert("a", Default::default())).unwrap(),
+        json!({"insert": "a"})
+    );
+    assert_eq!(
+        serde_json::to_value(Op::retain(1, Default::default())).unwrap(),
+        json!({"retain": 1})
+    );
+}
+
+#[test]
+fn rejects_what_the_model_cannot_hold() {
+    for json in [
+        // an embed has exactly one key
+        r#"{"ops":[{"insert":{"image":"a.png","video":"b"}}]}"#,
+        r#"{"ops":[{"insert":{}}]}"#,
+        // retaining an embed, see `Op`
+        r#"{"ops":[{"retain":{"table":{"rows":[]}},"attributes":{"bold":true}}]}"#,
+        r#"{"ops":[{"delete":-1}]}"#,
+        r#"{"ops":[{"insert":1}]}"#,
+        // the end of delta marker
+        r#"{"ops":[{"retain":18446744073709551615},{"retain":1}]}"#,
+        r#"{"ops":[{"delete":18446744073709551615}]}"#,
+    ] {
+        assert!(Delta::from_json(json).is_err(), "{json}");
+    }
+}
+
+#[test]
+fn huge_lengths_saturate() {
+    let json = r#"{"ops":[{"retain":18446744073709551614},{"retain":2},{"delete":1}]}"#;
+    let change = Delta::from_json(json).unwrap();
+    assert_eq!(change.length(), Utf16Index::MAX);
+    let mut merged = Delta::new();
+    for op in change.clone().into_ops() {
+        merged.push(op);
+    }
+    assert_eq!(merged.ops()[0].len(), Utf16Index::MAX);
+    assert_eq!(change.invert(&delta!["x\n"]), Delta::new());
+}
@@ -0,0 +1,37 @@
+use quill_wr::{Font, Formats, Inline, Size};
+use serde_json::json;
+
+#[test]
+fn size_values() {
+    for (value, size) in [
+        ("small", Size::Small),
+        ("huge", Size::Huge),
+        ("18px", Size::Px(18.0)),
+        ("12.5px", Size::Px(12.5)),
+        ("2em", Size::Custom("2em".into())),
+    ] {
+        assert_eq!(Size::parse(value), size);
+        assert_eq!(size.to_string(), value);
+    }
+}
+
+#[test]
+fn font_values() {
+    assert_eq!(Font::parse("monospace"), Font::Monospace);
+    assert_eq!(Font::parse("roboto"), Font::Custom("roboto".into()));
+    assert_eq!(Font::Serif.to_string(), "serif");
+}
+
+#[test]
+fn sizes_and_fonts_are_strings_in_deltas() {
+    let inlines = [Inline::Size(Size::Px(18.0)), Inline::Font(Font::Serif)];
+    let kvs: Vec<_> = inlines.iter().map(Inline::as_kv).collect();
+    assert_eq!(
+        kvs,
+        vec![
+            ("size".to_string(), json!("18px")),
+            ("font".to_string(), json!("serif"))
+        ]
+    );
+    assert_eq!(Formats::from_kvs(kvs).inlines, inlines);
+}
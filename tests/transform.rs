@@ -0,0 +1,225 @@
+//! parity fixtures from quill-delta's `test/delta/transform.js` and `transform-position.js`,
+//! plus convergence property tests
+mod common;
+use common::*;
+use proptest::prelude::*;
+use serde_json::{Value, json};
+
+fn assert_transform(a: Value, b: Value, priority: bool, expected: Value) {
+    assert_eq!(delta(a).transform(&delta(b), priority), delta(expected));
+}
+
+#[test]
+fn insert_insert() {
+    let (a, b) = (json!([{"insert": "A"}]), json!([{"insert": "B"}]));
+    assert_transform(
+        a.clone(),
+        b.clone(),
+        true,
+        json!([{"retain": 1}, {"insert": "B"}]),
+    );
+    assert_transform(a, b, false, json!([{"insert": "B"}]));
+}
+
+#[test]
+fn insert_retain() {
+    assert_transform(
+        json!([{"insert": "A"}]),
+        json!([{"retain": 1, "attributes": {"bold": true, "color": "red"}}]),
+        true,
+        json!([{"retain": 1}, {"retain": 1, "attributes": {"bold": true, "color": "red"}}]),
+    );
+}
+
+#[test]
+fn insert_delete() {
+    assert_transform(
+        json!([{"insert": "A"}]),
+        json!([{"delete": 1}]),
+        true,
+        json!([{"retain": 1}, {"delete": 1}]),
+    );
+}
+
+#[test]
+fn delete_insert() {
+    assert_transform(
+        json!([{"delete": 1}]),
+        json!([{"insert": "B"}]),
+        true,
+        json!([{"insert": "B"}]),
+    );
+}
+
+#[test]
+fn delete_retain() {
+    assert_transform(
+        json!([{"delete": 1}]),
+        json!([{"retain": 1, "attributes": {"bold": true, "color": "red"}}]),
+        true,
+        json!([]),
+    );
+}
+
+#[test]
+fn delete_delete() {
+    assert_transform(
+        json!([{"delete": 1}]),
+        json!([{"delete": 1}]),
+        true,
+        json!([]),
+    );
+}
+
+#[test]
+fn retain_insert() {
+    assert_transform(
+        json!([{"retain": 1, "attributes": {"color": "blue"}}]),
+        json!([{"insert": "B"}]),
+        true,
+        json!([{"insert": "B"}]),
+    );
+}
+
+#[test]
+fn retain_retain() {
+    let a = json!([{"retain": 1, "attributes": {"color": "blue"}}]);
+    let b = json!([{"retain": 1, "attributes": {"bold": true, "color": "red"}}]);
+    assert_transform(
+        a.clone(),
+        b.clone(),
+        true,
+        json!([{"retain": 1, "attributes": {"bold": true}}]),
+    );
+    assert_transform(b, a, true, json!([]));
+}
+
+#[test]
+fn retain_retain_without_priority() {
+    let a = json!([{"retain": 1, "attributes": {"color": "blue"}}]);
+    let b = json!([{"retain": 1, "attributes": {"bold": true, "color": "red"}}]);
+    assert_transform(
+        a.clone(),
+        b.clone(),
+        false,
+        json!([{"retain": 1, "attributes": {"bold": true, "color": "red"}}]),
+    );
+    assert_transform(
+        b,
+        a,
+        false,
+        json!([{"retain": 1, "attributes": {"color": "blue"}}]),
+    );
+}
+
+#[test]
+fn retain_delete() {
+    assert_transform(
+        json!([{"retain": 1, "attributes": {"color": "blue"}}]),
+        json!([{"delete": 1}]),
+        true,
+        json!([{"delete": 1}]),
+    );
+}
+
+#[test]
+fn conflicting_appends() {
+    let a = json!([{"retain": 3}, {"insert": "aa"}]);
+    let b = json!([{"retain": 3}, {"insert": "bb"}]);
+    assert_transform(
+        a.clone(),
+        b.clone(),
+        true,
+        json!([{"retain": 5}, {"insert": "bb"}]),
+    );
+    assert_transform(b, a, false, json!([{"retain": 3}, {"insert": "aa"}]));
+}
+
+#[test]
+fn prepend_append() {
+    let a = json!([{"insert": "aa"}]);
+    let b = json!([{"retain": 3}, {"insert": "bb"}]);
+    assert_transform(
+        a.clone(),
+        b.clone(),
+        false,
+        json!([{"retain": 5}, {"insert": "bb"}]),
+    );
+    assert_transform(b, a, false, json!([{"insert": "aa"}]));
+}
+
+#[test]
+fn trailing_deletes_with_differing_lengths() {
+    let a = json!([{"retain": 2}, {"delete": 1}]);
+    let b = json!([{"delete": 3}]);
+    assert_transform(a.clone(), b.clone(), false, json!([{"delete": 2}]));
+    assert_transform(b, a, false, json!([]));
+}
+
+#[test]
+fn transform_position() {
+    let cases = [
+        (json!([{"insert": "A"}]), 2, false, 3),
+        (json!([{"retain": 2}, {"insert": "A"}]), 1, false, 1),
+        (json!([{"retain": 2}, {"insert": "A"}]), 2, true, 2),
+        (json!([{"retain": 2}, {"insert": "A"}]), 2, false, 3),
+        (json!([{"delete": 2}]), 4, false, 2),
+        (json!([{"retain": 4}, {"delete": 2}]), 2, false, 2),
+        (json!([{"retain": 1}, {"delete": 4}]), 2, false, 1),
+        (
+            json!([{"retain": 2}, {"insert": "A"}, {"delete": 2}]),
+            4,
+            false,
+            3,
+        ),
+        (
+            json!([{"retain": 2}, {"insert": "A"}, {"delete": 4}]),
+            4,
+            false,
+            3,
+        ),
+        (
+            json!([{"delete": 1}, {"retain": 1}, {"delete": 4}]),
+            4,
+            false,
+            1,
+        ),
+    ];
+    for (change, index, priority, expected) in cases {
+        assert_eq!(
+            delta(change.clone()).transform_position(index, priority),
+            expected,
+            "{change} at {index}"
+        );
+    }
+}
+
+//------------------------- convergence ---------------------------------
+
+proptest! {
+    #[test]
+    fn transform_converges((doc, a, b) in arb_concurrent_changes()) {
+        let a_then_b = a.compose(&a.transform(&b, true));
+        let b_then_a = b.compose(&b.transform(&a, false));
+        prop_assert_eq!(doc.compose(&a_then_b), doc.compose(&b_then_a));
+        prop_assert_eq!(
+            doc.compose(&a).compose(&a.transform(&b, true)),
+            doc.compose(&b).compose(&b.transform(&a, false))
+        );
+    }
+
+    #[test]
+    fn transform_converges_without_document((a, b) in arb_concurrent_changes().prop_map(|(_, a, b)| (a, b))) {
+        prop_assert_eq!(
+            a.compose(&a.transform(&b, true)),
+            b.compose(&b.transform(&a, false))
+        );
+    }
+
+    #[test]
+    fn transform_position_follows_inserts((doc, a, _b) in arb_concurrent_changes(), index in 0..8usize) {
+        let index = index.min(doc.length().get());
+        let moved = a.transform_position(index, false);
+        prop_assert!(moved <= doc.compose(&a).length());
+    }
+}
@@ -0,0 +1,40 @@
+use quill_wr::Utf16Index;
+
+#[test]
+fn byte_and_char_offsets() {
+    let text = "a😀中b";
+    assert_eq!(Utf16Index::len_of(text), 5);
+    assert_eq!(Utf16Index(3).to_byte_offset(text), Some(5));
+    assert_eq!(Utf16Index(4).to_byte_offset(text), Some(8));
+    assert_eq!(Utf16Index(5).to_byte_offset(text), Some(9));
+    // inside the surrogate pair, and past the end
+    assert_eq!(Utf16Index(2).to_byte_offset(text), None);
+    assert_eq!(Utf16Index(6).to_byte_offset(text), None);
+
+    assert_eq!(Utf16Index::from_byte_offset(text, 5), Some(Utf16Index(3)));
+    assert_eq!(Utf16Index::from_byte_offset(text, 2), None);
+    assert_eq!(Utf16Index::from_char_offset(text, 3), Some(Utf16Index(4)));
+    assert_eq!(Utf16Index(4).to_char_offset(text), Some(3));
+}
+
+#[test]
+fn grapheme_offsets() {
+    // thumbs up + skin tone modifier is one grapheme, 4 UTF-16 units
+    let text = "x👍🏽y";
+    assert_eq!(
+        Utf16Index::from_grapheme_offset(text, 2),
+        Some(Utf16Index(5))
+    );
+    assert_eq!(Utf16Index(5).to_grapheme_offset(text), Some(2));
+    assert_eq!(Utf16Index(3).to_grapheme_offset(text), None);
+}
+
+#[test]
+fn slice() {
+    let text = "日本😀語";
+    assert_eq!(
+        Utf16Index::slice(text, Utf16Index(2)..Utf16Index(4)),
+        Some("😀")
+    );
+    assert_eq!(Utf16Index::slice(text, Utf16Index(1)..Utf16Index(3)), None);
+}